@@ -0,0 +1,106 @@
+//! Matching latency benchmark: prebuilt MatchIndex vs. indexing on every query.
+//!
+//! Usage: cargo run --release --example match_bench [iterations]
+//!
+//! Builds synthetic registries of 10, 100, 1000 and 5000 categories by
+//! cloning the seed categories in registry.json under numbered slugs, then
+//! times the same queries two ways:
+//! - per-query: build a MatchIndex for every query, which re-normalizes every
+//!   query pattern of every category (the cost each query paid before the
+//!   index was built at registry load)
+//! - prebuilt: build the MatchIndex once and reuse it, as the server does
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use three_good_sources::matcher::{match_query, MatchConfig, MatchIndex, MatchMode};
+use three_good_sources::registry::types::Category;
+use three_good_sources::registry::Registry;
+
+const QUERIES: [&str; 5] = [
+    "learn rust",
+    "bitcoin node",
+    "how do I self-host my own email server",
+    "password manager",
+    "quantum physics supercollider",
+];
+
+const SIZES: [usize; 4] = [10, 100, 1000, 5000];
+
+fn main() {
+    let iterations: u32 = std::env::args()
+        .nth(1)
+        .map(|arg| arg.parse().expect("iterations must be a positive integer"))
+        .unwrap_or(20);
+
+    let registry_json =
+        std::fs::read_to_string("registry.json").expect("registry.json must exist at project root");
+    let registry: Registry =
+        serde_json::from_str(&registry_json).expect("registry.json must be a valid registry");
+
+    let config = MatchConfig {
+        match_threshold: 0.4,
+        match_fuzzy_weight: 0.7,
+        match_keyword_weight: 0.3,
+        match_mode: MatchMode::Fuzzy,
+        match_bm25_weight: 0.5,
+    };
+
+    println!(
+        "{} queries x {} iterations per size (times are mean per query)\n",
+        QUERIES.len(),
+        iterations
+    );
+    println!(
+        "{:>10}  {:>14}  {:>14}  {:>14}  {:>8}",
+        "categories", "index build", "per-query", "prebuilt", "speedup"
+    );
+
+    for size in SIZES {
+        let categories = synthetic_categories(&registry, size);
+
+        let build_start = Instant::now();
+        let index = MatchIndex::new(&categories, &registry.matching);
+        let build_time = build_start.elapsed();
+
+        let per_query = time_queries(iterations, |query| {
+            let index = MatchIndex::new(&categories, &registry.matching);
+            let _ = match_query(query, &index, &config);
+        });
+        let prebuilt = time_queries(iterations, |query| {
+            let _ = match_query(query, &index, &config);
+        });
+
+        println!(
+            "{:>10}  {:>14?}  {:>14?}  {:>14?}  {:>7.1}x",
+            size,
+            build_time,
+            per_query,
+            prebuilt,
+            per_query.as_secs_f64() / prebuilt.as_secs_f64()
+        );
+    }
+}
+
+/// Clone the seed categories under numbered slugs until there are `size`
+fn synthetic_categories(registry: &Registry, size: usize) -> HashMap<String, Category> {
+    let mut seeds: Vec<(&String, &Category)> = registry.categories.iter().collect();
+    seeds.sort_by_key(|(slug, _)| *slug);
+
+    (0..size)
+        .map(|i| {
+            let (slug, category) = seeds[i % seeds.len()];
+            (format!("{}-{}", slug, i), category.clone())
+        })
+        .collect()
+}
+
+/// Mean time per query over `iterations` passes through QUERIES
+fn time_queries(iterations: u32, mut run: impl FnMut(&str)) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        for query in QUERIES {
+            run(query);
+        }
+    }
+    start.elapsed() / (iterations * QUERIES.len() as u32)
+}
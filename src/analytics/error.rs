@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Query log errors
+#[derive(Debug, Error)]
+pub enum AnalyticsError {
+    /// Failed to open the query log for appending
+    #[error("Failed to open query log at {path}: {error}")]
+    FileOpen { path: String, error: String },
+
+    /// Failed to append an event
+    #[error("Failed to write query log at {path}: {error}")]
+    FileWrite { path: String, error: String },
+
+    /// Failed to read the query log
+    #[error("Failed to read query log at {path}: {error}")]
+    FileRead { path: String, error: String },
+}
@@ -0,0 +1,9 @@
+pub mod error;
+pub mod report;
+pub mod store;
+pub mod types;
+
+pub use error::AnalyticsError;
+pub use report::{format_report, parse_since, run};
+pub use store::{read_events, QueryLog};
+pub use types::{CategoryStats, QueryEvent, QueryOutcome, QueryStats, QueryStatsParams, UnmatchedQueryStats, DEFAULT_UNMATCHED_LIMIT};
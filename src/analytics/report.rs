@@ -0,0 +1,206 @@
+use super::store::read_events;
+use super::types::{QueryStats, DEFAULT_UNMATCHED_LIMIT};
+use chrono::NaiveDate;
+use std::fmt::Write;
+
+const USAGE: &str = "\
+Usage: three-good-sources query-stats [--log <path>] [--since <YYYY-MM-DD>] [--limit <n>] [--json]
+
+Summarize the query analytics log.
+
+  --log <path>     Query log to read (default: $QUERY_LOG_PATH)
+  --since <date>   Only count queries made on or after this day
+  --limit <n>      Unmatched queries to list (default 50)
+  --json           Print the report as JSON";
+
+/// Parse a `since` day (YYYY-MM-DD)
+pub fn parse_since(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date {:?}: expected YYYY-MM-DD", value))
+}
+
+/// Render stats as a plain-text report for curators
+pub fn format_report(stats: &QueryStats) -> String {
+    let mut out = String::new();
+
+    match stats.since {
+        Some(since) => writeln!(out, "Queries since {}: {}", since, stats.total).unwrap(),
+        None => writeln!(out, "Queries: {}", stats.total).unwrap(),
+    }
+    writeln!(out, "Matched: {}", stats.matched).unwrap();
+    writeln!(out, "Unmatched: {}", stats.unmatched).unwrap();
+
+    if !stats.categories.is_empty() {
+        writeln!(out, "\nMatched categories (queries, mean score, min score):").unwrap();
+        for category in &stats.categories {
+            writeln!(
+                out,
+                "  {:<32} {:>6}  {:.2}  {:.2}",
+                category.slug, category.count, category.mean_score, category.min_score
+            )
+            .unwrap();
+        }
+    }
+
+    if !stats.unmatched_queries.is_empty() {
+        writeln!(out, "\nTop unmatched queries (normalized):").unwrap();
+        for query in &stats.unmatched_queries {
+            writeln!(
+                out,
+                "  {:>6}  \"{}\" (closest: {} {:.2}, last seen {})",
+                query.count, query.query, query.closest_slug, query.closest_score, query.last_seen
+            )
+            .unwrap();
+        }
+    }
+
+    out
+}
+
+/// Entry point for the `query-stats` subcommand
+pub async fn run(args: Vec<String>) -> Result<(), String> {
+    let mut log_path: Option<String> = None;
+    let mut since: Option<NaiveDate> = None;
+    let mut limit = DEFAULT_UNMATCHED_LIMIT;
+    let mut json = false;
+
+    let mut i = 2usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--log" | "--since" | "--limit" => {
+                let flag = args[i].clone();
+                i += 1;
+                let Some(value) = args.get(i) else {
+                    return Err(format!("{} requires a value\n\n{}", flag, USAGE));
+                };
+                match flag.as_str() {
+                    "--log" => log_path = Some(value.clone()),
+                    "--since" => since = Some(parse_since(value)?),
+                    _ => {
+                        limit = value.parse().map_err(|_| {
+                            format!("--limit must be a non-negative integer\n\n{}", USAGE)
+                        })?;
+                    }
+                }
+            }
+            "--json" => json = true,
+            unknown => {
+                return Err(format!("Unknown flag: {}\n\n{}", unknown, USAGE));
+            }
+        }
+        i += 1;
+    }
+
+    dotenvy::dotenv().ok();
+    let log_path = log_path
+        .or_else(|| std::env::var("QUERY_LOG_PATH").ok())
+        .ok_or_else(|| format!("--log is required when QUERY_LOG_PATH is not set\n\n{}", USAGE))?;
+
+    let events = read_events(&log_path).await.map_err(|e| e.to_string())?;
+    let stats = QueryStats::from_events(&events, since, limit);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&stats).expect("QueryStats always serializes")
+        );
+    } else {
+        print!("{}", format_report(&stats));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::types::{QueryEvent, QueryOutcome};
+    use crate::test_support::TempPath;
+
+    fn make_args(flags: &[&str]) -> Vec<String> {
+        ["three-good-sources", "query-stats"]
+            .iter()
+            .chain(flags)
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(
+            parse_since("2026-10-01").unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()
+        );
+        assert!(parse_since("10/01/2026").is_err());
+        assert!(parse_since("2026-13-01").is_err());
+    }
+
+    #[test]
+    fn test_format_report() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let events = vec![
+            QueryEvent {
+                date,
+                outcome: QueryOutcome::Matched {
+                    slug: "rust-learning".to_string(),
+                    score: 0.75,
+                },
+            },
+            QueryEvent {
+                date,
+                outcome: QueryOutcome::Unmatched {
+                    query: "sourdough bread".to_string(),
+                    closest_slug: "home-automation-private".to_string(),
+                    closest_score: 0.12,
+                },
+            },
+        ];
+        let report = format_report(&QueryStats::from_events(&events, Some(date), 10));
+
+        assert!(report.contains("Queries since 2026-10-01: 2"));
+        assert!(report.contains("Matched: 1"));
+        assert!(report.contains("rust-learning"));
+        assert!(report.contains("0.75"));
+        assert!(report.contains(
+            "\"sourdough bread\" (closest: home-automation-private 0.12, last seen 2026-10-01)"
+        ));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = format_report(&QueryStats::from_events(&[], None, 10));
+        assert_eq!(report, "Queries: 0\nMatched: 0\nUnmatched: 0\n");
+    }
+
+    #[tokio::test]
+    async fn test_run_arg_errors() {
+        let result = run(make_args(&["--bogus"])).await;
+        assert!(result.unwrap_err().contains("Unknown flag: --bogus"));
+
+        let result = run(make_args(&["--since", "yesterday"])).await;
+        assert!(result.unwrap_err().contains("expected YYYY-MM-DD"));
+
+        let result = run(make_args(&["--limit", "-1"])).await;
+        assert!(result.unwrap_err().contains("--limit must be"));
+
+        let result = run(make_args(&["--log"])).await;
+        assert!(result.unwrap_err().contains("--log requires a value"));
+    }
+
+    #[tokio::test]
+    async fn test_run_reads_log() {
+        let path = TempPath::new("report.jsonl");
+        let event = QueryEvent {
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            outcome: QueryOutcome::Matched {
+                slug: "rust-learning".to_string(),
+                score: 0.75,
+            },
+        };
+        std::fs::write(&path, serde_json::to_string(&event).unwrap() + "\n").unwrap();
+
+        let path_str = path.display().to_string();
+        assert!(run(make_args(&["--log", &path_str, "--json"])).await.is_ok());
+        assert!(run(make_args(&["--log", &path_str, "--since", "2026-09-01"])).await.is_ok());
+    }
+}
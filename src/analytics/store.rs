@@ -0,0 +1,193 @@
+use super::error::AnalyticsError;
+use super::types::QueryEvent;
+use std::path::{Path, PathBuf};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Append-only JSONL log of get_sources queries.
+///
+/// One `QueryEvent` per line. Lines are only ever appended, so the file can
+/// be rotated or truncated by an operator at any time while the server runs
+/// (a rotated file is not reopened; restart to switch files).
+#[derive(Debug)]
+pub struct QueryLog {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl QueryLog {
+    /// Open (creating if needed) the log at `path` for appending
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, AnalyticsError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .map_err(|e| AnalyticsError::FileOpen {
+                path: path.display().to_string(),
+                error: e.to_string(),
+            })?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Path the log appends to
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append one event as a single line
+    pub async fn append(&self, event: &QueryEvent) -> Result<(), AnalyticsError> {
+        let mut line = serde_json::to_string(event).expect("QueryEvent always serializes");
+        line.push('\n');
+
+        // One write per line under the lock, so concurrent queries never
+        // interleave partial lines. tokio hands writes to a background task;
+        // flushing waits until the line is in the file, so it can be read
+        // back at once and a failed write is reported for this event.
+        let write_error = |e: std::io::Error| AnalyticsError::FileWrite {
+            path: self.path.display().to_string(),
+            error: e.to_string(),
+        };
+        let mut file = self.file.lock().await;
+        file.write_all(line.as_bytes()).await.map_err(write_error)?;
+        file.flush().await.map_err(write_error)
+    }
+
+    /// Append an event, logging rather than returning failures.
+    /// Analytics must never fail the query being recorded.
+    pub async fn record(&self, event: QueryEvent) {
+        if let Err(e) = self.append(&event).await {
+            tracing::warn!(error = %e, "Failed to record query analytics");
+        }
+    }
+}
+
+/// Read every event from a query log.
+///
+/// A missing file holds no events. Lines that do not parse (e.g. a partial
+/// line from a crash mid-write) are skipped with a warning.
+pub async fn read_events(path: impl AsRef<Path>) -> Result<Vec<QueryEvent>, AnalyticsError> {
+    let path = path.as_ref();
+    let contents = match fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AnalyticsError::FileRead {
+                path: path.display().to_string(),
+                error: e.to_string(),
+            })
+        }
+    };
+
+    Ok(parse_events(&contents, path))
+}
+
+/// Parse JSONL events, skipping malformed lines
+fn parse_events(contents: &str, path: &Path) -> Vec<QueryEvent> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match serde_json::from_str(line) {
+            Ok(event) => Some(event),
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    line = i + 1,
+                    error = %e,
+                    "Skipping malformed query log line"
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::types::QueryOutcome;
+    use crate::test_support::TempPath;
+    use chrono::NaiveDate;
+    use std::sync::Arc;
+
+    fn event(slug: &str) -> QueryEvent {
+        QueryEvent {
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            outcome: QueryOutcome::Matched {
+                slug: slug.to_string(),
+                score: 0.7,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_and_read_back() {
+        let path = TempPath::new("append.jsonl");
+        let log = QueryLog::open(&path).await.unwrap();
+        log.record(event("rust-learning")).await;
+        log.record(event("bitcoin-node-setup")).await;
+
+        let events = read_events(&path).await.unwrap();
+        assert_eq!(events, vec![event("rust-learning"), event("bitcoin-node-setup")]);
+    }
+
+    #[tokio::test]
+    async fn test_reopen_appends() {
+        let path = TempPath::new("reopen.jsonl");
+        QueryLog::open(&path).await.unwrap().record(event("rust-learning")).await;
+        QueryLog::open(&path).await.unwrap().record(event("nostr-development")).await;
+
+        assert_eq!(read_events(&path).await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_records_keep_whole_lines() {
+        let path = TempPath::new("concurrent.jsonl");
+        let log = Arc::new(QueryLog::open(&path).await.unwrap());
+
+        let tasks: Vec<_> = (0..50)
+            .map(|_| {
+                let log = Arc::clone(&log);
+                tokio::spawn(async move { log.record(event("rust-learning")).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 50);
+        assert_eq!(read_events(&path).await.unwrap().len(), 50);
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_has_no_events() {
+        let events = read_events(TempPath::new("missing.jsonl")).await.unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_malformed_lines_skipped() {
+        let good = serde_json::to_string(&event("rust-learning")).unwrap();
+        let contents = format!("{}\nnot json\n\n{{\"date\":\"2026-10-01\",\"outc", good);
+
+        let events = parse_events(&contents, Path::new("queries.jsonl"));
+        assert_eq!(events, vec![event("rust-learning")]);
+    }
+
+    #[tokio::test]
+    async fn test_open_fails_for_missing_directory() {
+        let path = std::env::temp_dir()
+            .join(uuid::Uuid::new_v4().to_string())
+            .join("queries.jsonl");
+        let result = QueryLog::open(&path).await;
+        assert!(matches!(result, Err(AnalyticsError::FileOpen { .. })));
+    }
+}
@@ -0,0 +1,273 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default number of unmatched queries in a stats report
+pub const DEFAULT_UNMATCHED_LIMIT: usize = 50;
+
+/// One get_sources query, as recorded in the query log.
+///
+/// Carries nothing that identifies the client: no session id, address or
+/// time of day. The text of a matched query is not kept either, only the
+/// category it matched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QueryEvent {
+    /// Day the query was made (UTC)
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub outcome: QueryOutcome,
+}
+
+impl QueryEvent {
+    /// An event dated today (UTC)
+    pub fn new(outcome: QueryOutcome) -> Self {
+        Self {
+            date: chrono::Utc::now().date_naive(),
+            outcome,
+        }
+    }
+}
+
+/// What the matcher made of a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "lowercase")]
+pub enum QueryOutcome {
+    /// The query matched a category
+    Matched { slug: String, score: f64 },
+    /// No category reached its threshold
+    Unmatched {
+        /// The query after normalization (lowercased, stop words removed,
+        /// synonyms and stemming applied)
+        query: String,
+        /// Best-scoring category
+        closest_slug: String,
+        closest_score: f64,
+    },
+}
+
+/// Query parameters for GET /admin/query-stats
+#[derive(Debug, Deserialize)]
+pub struct QueryStatsParams {
+    /// Only count queries made on or after this day (YYYY-MM-DD)
+    pub since: Option<String>,
+    /// Unmatched queries to list (default 50)
+    pub limit: Option<usize>,
+}
+
+/// Query counts for one matched category
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategoryStats {
+    pub slug: String,
+    /// Queries that matched this category
+    pub count: usize,
+    /// Mean match score; a low mean suggests the query patterns are weak
+    pub mean_score: f64,
+    /// Lowest match score
+    pub min_score: f64,
+}
+
+/// Occurrences of one unmatched normalized query
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnmatchedQueryStats {
+    pub query: String,
+    pub count: usize,
+    /// Category that came closest, in the attempt where it came closest
+    pub closest_slug: String,
+    pub closest_score: f64,
+    /// Most recent day the query was made
+    pub last_seen: NaiveDate,
+}
+
+/// Aggregated view of the query log for curators
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryStats {
+    /// Only events on or after this day are counted
+    pub since: Option<NaiveDate>,
+    /// Queries counted
+    pub total: usize,
+    pub matched: usize,
+    pub unmatched: usize,
+    /// Matched categories, most queried first
+    pub categories: Vec<CategoryStats>,
+    /// Most frequent unmatched queries first, at most `limit` of them
+    pub unmatched_queries: Vec<UnmatchedQueryStats>,
+}
+
+impl QueryStats {
+    /// Aggregate events made on or after `since`, keeping the `limit` most
+    /// frequent unmatched queries
+    pub fn from_events(events: &[QueryEvent], since: Option<NaiveDate>, limit: usize) -> Self {
+        let mut categories: HashMap<&str, CategoryStats> = HashMap::new();
+        let mut unmatched: HashMap<&str, UnmatchedQueryStats> = HashMap::new();
+        let mut total = 0;
+
+        let events = events
+            .iter()
+            .filter(|event| since.is_none_or(|since| event.date >= since));
+        for event in events {
+            total += 1;
+            match &event.outcome {
+                QueryOutcome::Matched { slug, score } => {
+                    let stats = categories.entry(slug).or_insert_with(|| CategoryStats {
+                        slug: slug.clone(),
+                        count: 0,
+                        mean_score: 0.0,
+                        min_score: *score,
+                    });
+                    stats.count += 1;
+                    // Running mean
+                    stats.mean_score += (score - stats.mean_score) / stats.count as f64;
+                    stats.min_score = stats.min_score.min(*score);
+                }
+                QueryOutcome::Unmatched {
+                    query,
+                    closest_slug,
+                    closest_score,
+                } => {
+                    let stats = unmatched.entry(query).or_insert_with(|| UnmatchedQueryStats {
+                        query: query.clone(),
+                        count: 0,
+                        closest_slug: closest_slug.clone(),
+                        closest_score: *closest_score,
+                        last_seen: event.date,
+                    });
+                    stats.count += 1;
+                    if *closest_score > stats.closest_score {
+                        stats.closest_slug = closest_slug.clone();
+                        stats.closest_score = *closest_score;
+                    }
+                    stats.last_seen = stats.last_seen.max(event.date);
+                }
+            }
+        }
+
+        let mut categories: Vec<CategoryStats> = categories.into_values().collect();
+        categories.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.slug.cmp(&b.slug)));
+
+        let mut unmatched_queries: Vec<UnmatchedQueryStats> = unmatched.into_values().collect();
+        let unmatched_total = unmatched_queries.iter().map(|q| q.count).sum();
+        unmatched_queries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.query.cmp(&b.query)));
+        unmatched_queries.truncate(limit);
+
+        Self {
+            since,
+            total,
+            matched: total - unmatched_total,
+            unmatched: unmatched_total,
+            categories,
+            unmatched_queries,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn matched(day: u32, slug: &str, score: f64) -> QueryEvent {
+        QueryEvent {
+            date: date(day),
+            outcome: QueryOutcome::Matched {
+                slug: slug.to_string(),
+                score,
+            },
+        }
+    }
+
+    fn unmatched(day: u32, query: &str, closest_slug: &str, closest_score: f64) -> QueryEvent {
+        QueryEvent {
+            date: date(day),
+            outcome: QueryOutcome::Unmatched {
+                query: query.to_string(),
+                closest_slug: closest_slug.to_string(),
+                closest_score,
+            },
+        }
+    }
+
+    #[test]
+    fn test_event_json_has_no_client_fields() {
+        let json = serde_json::to_value(matched(1, "rust-learning", 0.7)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "date": "2026-10-01",
+                "outcome": "matched",
+                "slug": "rust-learning",
+                "score": 0.7
+            })
+        );
+
+        let event = unmatched(2, "quantum physic", "rust-learning", 0.2);
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<QueryEvent>(&json).unwrap(), event);
+    }
+
+    #[test]
+    fn test_counts_and_category_scores() {
+        let events = vec![
+            matched(1, "rust-learning", 0.6),
+            matched(1, "rust-learning", 0.8),
+            matched(2, "bitcoin-node-setup", 0.9),
+            unmatched(2, "quantum physic", "rust-learning", 0.2),
+        ];
+        let stats = QueryStats::from_events(&events, None, DEFAULT_UNMATCHED_LIMIT);
+
+        assert_eq!((stats.total, stats.matched, stats.unmatched), (4, 3, 1));
+        assert_eq!(stats.categories[0].slug, "rust-learning");
+        assert_eq!(stats.categories[0].count, 2);
+        assert!((stats.categories[0].mean_score - 0.7).abs() < 1e-9);
+        assert_eq!(stats.categories[0].min_score, 0.6);
+        assert_eq!(stats.categories[1].slug, "bitcoin-node-setup");
+    }
+
+    #[test]
+    fn test_unmatched_queries_grouped_and_ranked() {
+        let events = vec![
+            unmatched(1, "sourdough bread", "home-automation-private", 0.2),
+            unmatched(3, "sourdough bread", "rust-learning", 0.3),
+            unmatched(2, "quantum physic", "rust-learning", 0.25),
+        ];
+        let stats = QueryStats::from_events(&events, None, DEFAULT_UNMATCHED_LIMIT);
+
+        let sourdough = &stats.unmatched_queries[0];
+        assert_eq!(sourdough.query, "sourdough bread");
+        assert_eq!(sourdough.count, 2);
+        assert_eq!(sourdough.closest_slug, "rust-learning");
+        assert_eq!(sourdough.closest_score, 0.3);
+        assert_eq!(sourdough.last_seen, date(3));
+        assert_eq!(stats.unmatched_queries[1].query, "quantum physic");
+    }
+
+    #[test]
+    fn test_limit_keeps_unmatched_total() {
+        let events = vec![
+            unmatched(1, "a", "rust-learning", 0.1),
+            unmatched(1, "a", "rust-learning", 0.1),
+            unmatched(1, "b", "rust-learning", 0.1),
+        ];
+        let stats = QueryStats::from_events(&events, None, 1);
+
+        assert_eq!(stats.unmatched_queries.len(), 1);
+        assert_eq!(stats.unmatched_queries[0].query, "a");
+        assert_eq!(stats.unmatched, 3);
+    }
+
+    #[test]
+    fn test_since_filters_older_events() {
+        let events = vec![
+            matched(1, "rust-learning", 0.6),
+            unmatched(5, "quantum physic", "rust-learning", 0.2),
+        ];
+        let stats = QueryStats::from_events(&events, Some(date(2)), DEFAULT_UNMATCHED_LIMIT);
+
+        assert_eq!(stats.total, 1);
+        assert_eq!(stats.matched, 0);
+        assert!(stats.categories.is_empty());
+        assert_eq!(stats.since, Some(date(2)));
+    }
+}
@@ -23,4 +23,12 @@ pub enum AuditError {
     /// Signature verification failed
     #[error("Signature verification failed for audit entry {id}")]
     SignatureVerificationFailed { id: Uuid },
+
+    /// previous_hash does not match the hash of the preceding entry
+    #[error("Hash chain broken at audit entry {id}: expected previous_hash {expected}, found {actual}")]
+    ChainBroken {
+        id: Uuid,
+        expected: String,
+        actual: String,
+    },
 }
@@ -1,11 +1,11 @@
 use super::error::AuditError;
-use super::types::{canonical_message, AuditEntry};
+use super::types::{canonical_message, hash_entry_json, AuditEntry, VerificationReport};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use std::path::Path;
 use tokio::fs;
 
 /// Load and verify the audit log from disk.
-/// Verifies Ed25519 signatures for every entry at load time.
+/// Verifies Ed25519 signatures and the previous_hash chain for every entry at load time.
 pub async fn load(path: impl AsRef<Path>) -> Result<Vec<AuditEntry>, AuditError> {
     let path = path.as_ref();
     let path_str = path.display().to_string();
@@ -23,14 +23,70 @@ pub async fn load(path: impl AsRef<Path>) -> Result<Vec<AuditEntry>, AuditError>
             error: e.to_string(),
         })?;
 
-    for entry in &entries {
-        verify_signature(entry)?;
-    }
+    verify_entries(&entries)?;
 
     tracing::info!(entries = entries.len(), "Audit log loaded successfully");
     Ok(entries)
 }
 
+/// Verify every entry's signature and its link to the preceding entry, in log order.
+/// The first entry must have a null previous_hash; each later entry must carry
+/// the `hash_entry_json` of the entry before it.
+pub fn verify_entries(entries: &[AuditEntry]) -> Result<(), AuditError> {
+    let mut expected_hash: Option<String> = None;
+
+    for entry in entries {
+        verify_signature(entry)?;
+
+        if entry.previous_hash != expected_hash {
+            return Err(AuditError::ChainBroken {
+                id: entry.id,
+                expected: expected_hash.unwrap_or_else(|| "null".to_string()),
+                actual: entry
+                    .previous_hash
+                    .clone()
+                    .unwrap_or_else(|| "null".to_string()),
+            });
+        }
+
+        expected_hash = Some(hash_entry_json(entry));
+    }
+
+    Ok(())
+}
+
+/// Build an integrity report for the given entries.
+/// Never fails: verification errors are reported in the returned struct.
+pub fn verification_report(entries: &[AuditEntry]) -> VerificationReport {
+    let head_hash = entries.last().map(hash_entry_json);
+
+    match verify_entries(entries) {
+        Ok(()) => VerificationReport {
+            valid: true,
+            entries: entries.len(),
+            head_hash,
+            failed_entry: None,
+            error: None,
+        },
+        Err(e) => {
+            let failed_entry = match &e {
+                AuditError::InvalidActorKey { id }
+                | AuditError::InvalidSignature { id }
+                | AuditError::SignatureVerificationFailed { id }
+                | AuditError::ChainBroken { id, .. } => Some(*id),
+                _ => None,
+            };
+            VerificationReport {
+                valid: false,
+                entries: entries.len(),
+                head_hash,
+                failed_entry,
+                error: Some(e.to_string()),
+            }
+        }
+    }
+}
+
 /// Verify an entry's Ed25519 signature against its canonical message.
 fn verify_signature(entry: &AuditEntry) -> Result<(), AuditError> {
     let pubkey_bytes =
@@ -63,19 +119,26 @@ mod tests {
     use uuid::Uuid;
 
     fn make_signed_entry() -> AuditEntry {
+        make_chained_entry(
+            Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            None,
+        )
+    }
+
+    fn make_chained_entry(id: Uuid, previous_hash: Option<String>) -> AuditEntry {
         let secret_bytes = [42u8; 32];
         let signing_key = SigningKey::from_bytes(&secret_bytes);
         let pubkey_hex = hex::encode(signing_key.verifying_key().to_bytes());
 
         let mut entry = AuditEntry {
-            id: Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap(),
+            id,
             timestamp: Utc.with_ymd_and_hms(2026, 2, 3, 0, 0, 0).unwrap(),
             action: AuditAction::SourceAdded,
             category: Some("rust-learning".to_string()),
             data: serde_json::json!({"name": "The Rust Book", "url": "https://doc.rust-lang.org/book/"}),
             actor: pubkey_hex,
             signature: String::new(),
-            previous_hash: None,
+            previous_hash,
         };
 
         let message = canonical_message(&entry);
@@ -119,6 +182,85 @@ mod tests {
         );
     }
 
+    fn make_chain(len: usize) -> Vec<AuditEntry> {
+        let mut entries: Vec<AuditEntry> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let previous_hash = entries.last().map(hash_entry_json);
+            entries.push(make_chained_entry(Uuid::new_v4(), previous_hash));
+        }
+        entries
+    }
+
+    #[test]
+    fn test_verify_entries_valid_chain() {
+        let entries = make_chain(3);
+        let result = verify_entries(&entries);
+        assert!(result.is_ok(), "Valid chain should verify: {:?}", result);
+    }
+
+    #[test]
+    fn test_verify_entries_empty_log() {
+        assert!(verify_entries(&[]).is_ok());
+    }
+
+    #[test]
+    fn test_verify_entries_rejects_deleted_entry() {
+        let mut entries = make_chain(3);
+        let removed = entries.remove(1);
+        let result = verify_entries(&entries);
+        match result {
+            Err(AuditError::ChainBroken { id, expected, actual }) => {
+                assert_eq!(id, entries[1].id);
+                assert_eq!(expected, hash_entry_json(&entries[0]));
+                assert_eq!(actual, hash_entry_json(&removed));
+            }
+            other => panic!("Expected ChainBroken, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verify_entries_rejects_reordered_entries() {
+        let mut entries = make_chain(3);
+        entries.swap(1, 2);
+        let result = verify_entries(&entries);
+        assert!(
+            matches!(result, Err(AuditError::ChainBroken { .. })),
+            "Expected ChainBroken, got: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_verify_entries_rejects_non_null_first_hash() {
+        let entry = make_chained_entry(Uuid::new_v4(), Some("ab".repeat(32)));
+        let result = verify_entries(&[entry]);
+        match result {
+            Err(AuditError::ChainBroken { expected, .. }) => assert_eq!(expected, "null"),
+            other => panic!("Expected ChainBroken, got: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_verification_report_valid() {
+        let entries = make_chain(2);
+        let report = verification_report(&entries);
+        assert!(report.valid);
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.head_hash, Some(hash_entry_json(&entries[1])));
+        assert!(report.failed_entry.is_none());
+        assert!(report.error.is_none());
+    }
+
+    #[test]
+    fn test_verification_report_names_failed_entry() {
+        let mut entries = make_chain(3);
+        entries.remove(1);
+        let report = verification_report(&entries);
+        assert!(!report.valid);
+        assert_eq!(report.failed_entry, Some(entries[1].id));
+        assert!(report.error.unwrap().contains("Hash chain broken"));
+    }
+
     #[tokio::test]
     async fn test_loader_accepts_valid_json() {
         let entry = make_signed_entry();
@@ -148,4 +290,23 @@ mod tests {
 
         let _ = tokio::fs::remove_file(&tmp).await;
     }
+
+    #[tokio::test]
+    async fn test_loader_rejects_broken_chain_json() {
+        let mut entries = make_chain(3);
+        entries.remove(1);
+        let json = serde_json::to_string_pretty(&entries).unwrap();
+
+        let tmp = std::env::temp_dir().join("test_audit_broken_chain.json");
+        tokio::fs::write(&tmp, &json).await.unwrap();
+
+        let result = load(&tmp).await;
+        assert!(
+            matches!(result, Err(AuditError::ChainBroken { .. })),
+            "Loader should reject broken chain, got: {:?}",
+            result
+        );
+
+        let _ = tokio::fs::remove_file(&tmp).await;
+    }
 }
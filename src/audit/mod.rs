@@ -3,5 +3,5 @@ pub mod loader;
 pub mod types;
 
 pub use error::AuditError;
-pub use loader::load;
-pub use types::{canonical_message, filter_entries, hash_entry_json, AuditAction, AuditEntry, AuditFilterParams};
+pub use loader::{load, verification_report, verify_entries};
+pub use types::{canonical_message, filter_entries, hash_entry_json, AuditAction, AuditEntry, AuditFilterParams, VerificationReport};
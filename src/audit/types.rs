@@ -47,6 +47,24 @@ pub enum AuditAction {
     VoteCast,
 }
 
+/// Result of verifying signatures and the previous_hash chain of an audit log.
+/// Served by `GET /audit/verify` so auditors can check integrity remotely.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationReport {
+    /// True when every signature verifies and the chain is unbroken
+    pub valid: bool,
+    /// Number of entries checked
+    pub entries: usize,
+    /// `hash_entry_json` of the last entry (None for an empty log)
+    pub head_hash: Option<String>,
+    /// Id of the first entry that failed verification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_entry: Option<Uuid>,
+    /// Human-readable description of the failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AuditFilterParams {
     pub since: Option<String>,
@@ -27,6 +27,36 @@ pub struct Config {
     /// If not set, server generates an ephemeral keypair on startup.
     /// Set via PKARR_SECRET_KEY environment variable for persistent identity.
     pub pkarr_secret_key: Option<String>,
+
+    /// Seconds between data file change checks for hot reload. Defaults to 10.
+    /// Set to 0 to disable file watching (SIGHUP still triggers a reload).
+    #[serde(default = "default_reload_poll_secs")]
+    pub reload_poll_secs: u64,
+
+    /// Seconds an idle MCP session survives before it expires. Defaults to 3600.
+    #[serde(default = "default_mcp_session_ttl_secs")]
+    pub mcp_session_ttl_secs: u64,
+
+    /// Seconds between peer registry refreshes. Defaults to 300.
+    /// A failing peer backs off from this interval, doubling with each failure.
+    #[serde(default = "default_peer_refresh_secs")]
+    pub peer_refresh_secs: u64,
+
+    /// Optional path to the query analytics log (JSONL, append-only).
+    /// If not set, get_sources queries are not recorded.
+    pub query_log_path: Option<PathBuf>,
+
+    /// Optional bearer token for /admin endpoints.
+    /// If not set or blank, /admin endpoints return 404.
+    pub admin_token: Option<String>,
+
+    /// Optional path to the link status file written by `check-links --stamp`.
+    /// If set, each source's `last_checked` is filled from it on load.
+    pub link_status_path: Option<PathBuf>,
+
+    /// Optional path to the peer cache file. If set, each peer's last good
+    /// registry is saved there and served from it after a restart.
+    pub peer_cache_path: Option<PathBuf>,
 }
 
 fn default_log_format() -> String {
@@ -37,6 +67,18 @@ fn default_port() -> u16 {
     3000
 }
 
+fn default_reload_poll_secs() -> u64 {
+    10
+}
+
+fn default_mcp_session_ttl_secs() -> u64 {
+    3600
+}
+
+fn default_peer_refresh_secs() -> u64 {
+    300
+}
+
 impl Config {
     pub fn load() -> Result<Self, anyhow::Error> {
         dotenvy::dotenv().ok(); // Load .env if present, ignore if missing
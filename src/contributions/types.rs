@@ -66,6 +66,18 @@ impl Default for Proposal {
     }
 }
 
+impl Proposal {
+    /// The proposal as JSON with its `id` added, as served by
+    /// `GET /proposals/{id}` and the `3gs://proposal/{id}` resource.
+    pub fn to_json_with_id(&self, id: &Uuid) -> Result<serde_json::Value, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("id".to_string(), serde_json::Value::String(id.to_string()));
+        }
+        Ok(value)
+    }
+}
+
 /// Summary view of a proposal for list endpoints.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProposalSummary {
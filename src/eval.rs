@@ -0,0 +1,748 @@
+//! Eval CLI subcommand — scores the matcher against labelled queries.
+//!
+//! Runs every query of a JSONL file through the same matching pipeline the
+//! server uses and reports how often it picks the labelled category, so
+//! `MATCH_THRESHOLD` and the weights can be tuned against data instead of
+//! by hand.
+//!
+//! Usage:
+//!   3gs eval --queries <file.jsonl> [--registry <path>] [--k <n>] [--mode <mode>] [--grid]
+
+use crate::matcher::{match_query_top_k, MatchConfig, MatchIndex, MatchMode};
+use serde::Deserialize;
+
+const USAGE: &str = "\
+Usage: 3gs eval --queries <file.jsonl> [OPTIONS]
+
+Required:
+  --queries <path>     JSONL file with one {\"query\": ..., \"expected_slug\": ...} per line.
+                       A null expected_slug marks a query that should match nothing.
+
+Optional:
+  --registry <path>    Registry to match against (default: REGISTRY_PATH or registry.json)
+  --k <n>              Rank cutoff for top-k accuracy, 1-10 (default: 3)
+  --mode <mode>        fuzzy, bm25 or hybrid (default: MATCH_MODE or fuzzy)
+  --grid               Grid-search the threshold and weights and print the best settings
+
+The threshold and weights come from MATCH_THRESHOLD, MATCH_FUZZY_WEIGHT,
+MATCH_KEYWORD_WEIGHT and MATCH_BM25_WEIGHT, as for the server.
+
+Examples:
+  3gs eval --queries eval/queries.jsonl
+  3gs eval --queries eval/queries.jsonl --mode hybrid --grid
+";
+
+/// Most matches the top-k cutoff may ask for (same cap as get_sources' limit)
+const MAX_K: usize = 10;
+
+/// One labelled query
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalCase {
+    pub query: String,
+    /// Category the query should match, or None if it should match nothing
+    pub expected_slug: Option<String>,
+}
+
+/// A query the matcher got wrong
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confusion {
+    pub query: String,
+    pub expected: Option<String>,
+    /// Category returned at the threshold, if any
+    pub predicted: Option<String>,
+    /// Best-scoring category and its score, whether or not it cleared the threshold
+    pub closest: Option<(String, f64)>,
+}
+
+/// Accuracy of one configuration over a labelled query set
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalReport {
+    /// Number of queries evaluated
+    pub cases: usize,
+    /// Queries with an expected slug
+    pub labelled: usize,
+    /// Queries the matcher returned a category for
+    pub predicted: usize,
+    /// Queries whose returned category is the expected one
+    pub correct: usize,
+    /// Rank cutoff used for `top_k_hits`
+    pub k: usize,
+    /// Labelled queries with the expected slug among the top k matches
+    pub top_k_hits: usize,
+    /// Every query whose returned category (or lack of one) was wrong
+    pub confusions: Vec<Confusion>,
+}
+
+impl EvalReport {
+    /// Share of returned categories that were correct (1.0 if none were returned)
+    pub fn precision(&self) -> f64 {
+        ratio(self.correct, self.predicted)
+    }
+
+    /// Share of labelled queries matched to their expected category
+    pub fn recall(&self) -> f64 {
+        ratio(self.correct, self.labelled)
+    }
+
+    /// Harmonic mean of precision and recall
+    pub fn f1(&self) -> f64 {
+        let (precision, recall) = (self.precision(), self.recall());
+        if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        }
+    }
+
+    /// Share of labelled queries with the expected slug among the top k matches
+    pub fn top_k_accuracy(&self) -> f64 {
+        ratio(self.top_k_hits, self.labelled)
+    }
+}
+
+/// `numerator / denominator`, or 1.0 when there is nothing to divide
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        1.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Best configuration found by [`grid_search`] and its report
+#[derive(Debug, Clone)]
+pub struct GridResult {
+    pub config: MatchConfig,
+    pub report: EvalReport,
+}
+
+/// Parse a JSONL query set, skipping blank lines
+pub fn parse_cases(jsonl: &str) -> Result<Vec<EvalCase>, String> {
+    jsonl
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| serde_json::from_str(line).map_err(|e| format!("Line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// One of a query's top-k matches
+#[derive(Debug, Clone)]
+struct Ranked {
+    slug: String,
+    score: f64,
+    /// The category has its own match_threshold, which the matcher has
+    /// already applied; the global threshold does not apply to it
+    own_threshold: bool,
+}
+
+/// Top-k matches of a query with no global threshold applied, best first.
+/// Empty when the query normalizes to nothing.
+type Ranking = Vec<Ranked>;
+
+/// Rank every case once; the global threshold is applied afterwards by
+/// [`score_rankings`], category thresholds by the matcher itself
+fn rank_cases(
+    cases: &[EvalCase],
+    index: &MatchIndex,
+    config: &MatchConfig,
+    k: usize,
+) -> Vec<Ranking> {
+    let config = MatchConfig {
+        match_threshold: 0.0,
+        ..config.clone()
+    };
+
+    cases
+        .iter()
+        .map(|case| {
+            match_query_top_k(&case.query, index, &config, k, None)
+                .map(|results| {
+                    results
+                        .into_iter()
+                        .map(|result| Ranked {
+                            own_threshold: result.category.match_threshold.is_some(),
+                            slug: result.slug,
+                            score: result.score,
+                        })
+                        .collect()
+                })
+                // Empty and all-stop-word queries match nothing
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Score precomputed rankings at one threshold
+fn score_rankings(
+    cases: &[EvalCase],
+    rankings: &[Ranking],
+    threshold: f64,
+    k: usize,
+) -> EvalReport {
+    let mut report = EvalReport {
+        cases: cases.len(),
+        labelled: 0,
+        predicted: 0,
+        correct: 0,
+        k,
+        top_k_hits: 0,
+        confusions: Vec::new(),
+    };
+
+    for (case, ranking) in cases.iter().zip(rankings) {
+        let matches: Vec<&str> = ranking
+            .iter()
+            .filter(|ranked| ranked.own_threshold || ranked.score >= threshold)
+            .map(|ranked| ranked.slug.as_str())
+            .collect();
+        let predicted = matches.first().copied();
+
+        if case.expected_slug.is_some() {
+            report.labelled += 1;
+        }
+        if predicted.is_some() {
+            report.predicted += 1;
+        }
+
+        if predicted.is_some() && predicted == case.expected_slug.as_deref() {
+            report.correct += 1;
+        } else if predicted != case.expected_slug.as_deref() {
+            report.confusions.push(Confusion {
+                query: case.query.clone(),
+                expected: case.expected_slug.clone(),
+                predicted: predicted.map(str::to_string),
+                closest: ranking
+                    .first()
+                    .map(|ranked| (ranked.slug.clone(), ranked.score)),
+            });
+        }
+
+        if case
+            .expected_slug
+            .as_deref()
+            .is_some_and(|expected| matches.contains(&expected))
+        {
+            report.top_k_hits += 1;
+        }
+    }
+
+    report
+}
+
+/// Evaluate one configuration against a labelled query set
+pub fn evaluate(
+    cases: &[EvalCase],
+    index: &MatchIndex,
+    config: &MatchConfig,
+    k: usize,
+) -> EvalReport {
+    let rankings = rank_cases(cases, index, config, k);
+    score_rankings(cases, &rankings, config.match_threshold, k)
+}
+
+/// Try every threshold and weight combination on a coarse grid and return
+/// the one with the best F1, then the best top-k accuracy.
+///
+/// Thresholds run from 0.20 to 0.80 in steps of 0.05 and weights from 0.0
+/// to 1.0 in steps of 0.1, with the keyword weight always 1 minus the fuzzy
+/// weight. Only the weights the mode uses are searched: none for bm25, the
+/// fuzzy/keyword split for fuzzy, and both that and the BM25 share for hybrid.
+/// Categories with their own `match_threshold` keep it at every grid point.
+pub fn grid_search(
+    cases: &[EvalCase],
+    index: &MatchIndex,
+    base: &MatchConfig,
+    k: usize,
+) -> GridResult {
+    let tenths: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+    let fuzzy_weights = match base.match_mode {
+        MatchMode::Bm25 => vec![base.match_fuzzy_weight],
+        MatchMode::Fuzzy | MatchMode::Hybrid => tenths.clone(),
+    };
+    let bm25_weights = match base.match_mode {
+        MatchMode::Hybrid => tenths,
+        MatchMode::Fuzzy | MatchMode::Bm25 => vec![base.match_bm25_weight],
+    };
+    // Highest first, so that among equally good settings the strictest
+    // threshold wins: it rejects more of the off-topic queries a labelled
+    // set cannot cover
+    let thresholds: Vec<f64> = (4..=16).rev().map(|i| i as f64 * 0.05).collect();
+
+    let mut best: Option<GridResult> = None;
+    for &fuzzy_weight in &fuzzy_weights {
+        for &bm25_weight in &bm25_weights {
+            let config = MatchConfig {
+                match_fuzzy_weight: fuzzy_weight,
+                match_keyword_weight: 1.0 - fuzzy_weight,
+                match_bm25_weight: bm25_weight,
+                ..base.clone()
+            };
+            let rankings = rank_cases(cases, index, &config, k);
+
+            for &threshold in &thresholds {
+                let report = score_rankings(cases, &rankings, threshold, k);
+                let better = best.as_ref().is_none_or(|best| {
+                    (report.f1(), report.top_k_accuracy())
+                        > (best.report.f1(), best.report.top_k_accuracy())
+                });
+                if better {
+                    best = Some(GridResult {
+                        config: MatchConfig {
+                            match_threshold: threshold,
+                            ..config.clone()
+                        },
+                        report,
+                    });
+                }
+            }
+        }
+    }
+
+    best.expect("grid has at least one point")
+}
+
+/// Parse a --mode value, spelled as in MATCH_MODE
+fn parse_mode(value: &str) -> Result<MatchMode, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).map_err(|_| {
+        format!(
+            "Unknown mode: {} (expected fuzzy, bm25 or hybrid)\n\n{}",
+            value, USAGE
+        )
+    })
+}
+
+/// Format a configuration on one line
+fn describe_config(config: &MatchConfig) -> String {
+    format!(
+        "mode {}, threshold {:.2}, fuzzy weight {:.2}, keyword weight {:.2}, bm25 weight {:.2}",
+        format!("{:?}", config.match_mode).to_lowercase(),
+        config.match_threshold,
+        config.match_fuzzy_weight,
+        config.match_keyword_weight,
+        config.match_bm25_weight
+    )
+}
+
+/// Format the metrics and confusion list of a report
+fn describe_report(report: &EvalReport) -> String {
+    let mut out = format!(
+        "  Precision:        {:.3} ({}/{} returned categories correct)\n\
+         \x20 Recall:           {:.3} ({}/{} labelled queries matched correctly)\n\
+         \x20 F1:               {:.3}\n\
+         \x20 Top-{} accuracy:   {:.3} ({}/{} labelled queries with the expected slug in the top {})\n",
+        report.precision(),
+        report.correct,
+        report.predicted,
+        report.recall(),
+        report.correct,
+        report.labelled,
+        report.f1(),
+        report.k,
+        report.top_k_accuracy(),
+        report.top_k_hits,
+        report.labelled,
+        report.k,
+    );
+
+    if report.confusions.is_empty() {
+        out.push_str("\n  No confusions\n");
+        return out;
+    }
+
+    out.push_str(&format!("\n  Confusions ({}):\n", report.confusions.len()));
+    for confusion in &report.confusions {
+        let closest = match &confusion.closest {
+            Some((slug, score)) => format!("closest {} at {:.3}", slug, score),
+            None => "query normalizes to nothing".to_string(),
+        };
+        out.push_str(&format!(
+            "    {:?}: expected {}, got {} ({})\n",
+            confusion.query,
+            confusion.expected.as_deref().unwrap_or("no match"),
+            confusion.predicted.as_deref().unwrap_or("no match"),
+            closest
+        ));
+    }
+    out
+}
+
+/// Entry point for the eval subcommand.
+///
+/// `args` is the full `std::env::args()` collection (index 0 = binary, index 1 = "eval").
+pub async fn run(args: Vec<String>) -> Result<(), String> {
+    let mut queries_path: Option<String> = None;
+    let mut registry_path: Option<String> = None;
+    let mut k: usize = 3;
+    let mut mode: Option<MatchMode> = None;
+    let mut grid = false;
+
+    let mut i = 2usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--queries" | "--registry" | "--k" | "--mode" => {
+                let flag = args[i].clone();
+                i += 1;
+                let Some(value) = args.get(i) else {
+                    return Err(format!("{} requires a value\n\n{}", flag, USAGE));
+                };
+                match flag.as_str() {
+                    "--queries" => queries_path = Some(value.clone()),
+                    "--registry" => registry_path = Some(value.clone()),
+                    "--k" => {
+                        k = value
+                            .parse()
+                            .ok()
+                            .filter(|k| (1..=MAX_K).contains(k))
+                            .ok_or_else(|| {
+                                format!("--k must be an integer from 1 to {}\n\n{}", MAX_K, USAGE)
+                            })?;
+                    }
+                    _ => mode = Some(parse_mode(value)?),
+                }
+            }
+            "--grid" => grid = true,
+            unknown => {
+                return Err(format!("Unknown flag: {}\n\n{}", unknown, USAGE));
+            }
+        }
+        i += 1;
+    }
+
+    let queries_path = queries_path.ok_or_else(|| format!("--queries is required\n\n{}", USAGE))?;
+
+    // Same sources of settings as the server
+    dotenvy::dotenv().ok();
+    let registry_path = registry_path
+        .or_else(|| std::env::var("REGISTRY_PATH").ok())
+        .unwrap_or_else(|| "registry.json".to_string());
+    let mut config = MatchConfig::load().map_err(|e| e.to_string())?;
+    if let Some(mode) = mode {
+        config.match_mode = mode;
+    }
+    config.validate().map_err(|e| e.to_string())?;
+
+    let registry = crate::registry::loader::load(&registry_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let jsonl = std::fs::read_to_string(&queries_path)
+        .map_err(|e| format!("Failed to read {}: {}", queries_path, e))?;
+    let cases = parse_cases(&jsonl).map_err(|e| format!("{}: {}", queries_path, e))?;
+    if cases.is_empty() {
+        return Err(format!("{} contains no queries", queries_path));
+    }
+
+    // A typo in a label would silently count as a miss
+    if let Some(case) = cases.iter().find(|case| {
+        case.expected_slug
+            .as_ref()
+            .is_some_and(|slug| !registry.categories.contains_key(slug))
+    }) {
+        return Err(format!(
+            "{}: query {:?} expects unknown category {}",
+            queries_path,
+            case.query,
+            case.expected_slug.as_deref().unwrap_or_default()
+        ));
+    }
+
+    let index = MatchIndex::new(&registry.categories, &registry.matching);
+
+    let report = evaluate(&cases, &index, &config, k);
+
+    println!(
+        "Evaluated {} queries against {} ({} categories)\n",
+        report.cases,
+        registry_path,
+        registry.categories.len()
+    );
+    println!("Current settings: {}\n", describe_config(&config));
+    print!("{}", describe_report(&report));
+
+    if grid {
+        let best = grid_search(&cases, &index, &config, k);
+        println!("\nBest settings: {}\n", describe_config(&best.config));
+        print!("{}", describe_report(&best.report));
+        println!(
+            "\nTo use them:\n  MATCH_THRESHOLD={:.2}\n  MATCH_FUZZY_WEIGHT={:.2}\n  MATCH_KEYWORD_WEIGHT={:.2}\n  MATCH_BM25_WEIGHT={:.2}",
+            best.config.match_threshold,
+            best.config.match_fuzzy_weight,
+            best.config.match_keyword_weight,
+            best.config.match_bm25_weight
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Registry;
+    use approx::assert_relative_eq;
+
+    fn load_test_index() -> MatchIndex {
+        let registry: Registry = serde_json::from_str(include_str!("../registry.json"))
+            .expect("Failed to parse registry.json");
+        MatchIndex::new(&registry.categories, &registry.matching)
+    }
+
+    fn default_config() -> MatchConfig {
+        MatchConfig {
+            match_threshold: 0.4,
+            match_fuzzy_weight: 0.7,
+            match_keyword_weight: 0.3,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
+        }
+    }
+
+    fn case(query: &str, expected: Option<&str>) -> EvalCase {
+        EvalCase {
+            query: query.to_string(),
+            expected_slug: expected.map(str::to_string),
+        }
+    }
+
+    fn make_args(extra: &[&str]) -> Vec<String> {
+        let mut args = vec!["3gs".to_string(), "eval".to_string()];
+        args.extend(extra.iter().map(|s| s.to_string()));
+        args
+    }
+
+    #[test]
+    fn test_parse_cases() {
+        let jsonl = "{\"query\": \"learn rust\", \"expected_slug\": \"rust-learning\"}\n\n\
+                     {\"query\": \"quantum physics\", \"expected_slug\": null}\n";
+        let cases = parse_cases(jsonl).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].expected_slug.as_deref(), Some("rust-learning"));
+        assert_eq!(cases[1].expected_slug, None);
+    }
+
+    #[test]
+    fn test_parse_cases_reports_line() {
+        let jsonl = "{\"query\": \"learn rust\", \"expected_slug\": \"rust-learning\"}\n\
+                     {\"query\": \"typo\", \"expected\": \"rust-learning\"}\n";
+        let err = parse_cases(jsonl).unwrap_err();
+        assert!(err.starts_with("Line 2:"), "{}", err);
+    }
+
+    #[test]
+    fn test_seed_query_set_is_matched_in_every_mode() {
+        let cases = parse_cases(include_str!("../eval/queries.jsonl")).unwrap();
+        let index = load_test_index();
+
+        for mode in [MatchMode::Fuzzy, MatchMode::Bm25, MatchMode::Hybrid] {
+            let config = MatchConfig {
+                match_mode: mode,
+                ..default_config()
+            };
+            let report = evaluate(&cases, &index, &config, 3);
+            assert!(
+                report.confusions.is_empty(),
+                "{:?} mode: {:?}",
+                mode,
+                report.confusions
+            );
+            assert_eq!(report.precision(), 1.0);
+            assert_eq!(report.recall(), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_metrics() {
+        let cases = [
+            case("learn rust", Some("rust-learning")),
+            // Labelled with the wrong category on purpose
+            case("bitcoin node", Some("linux-hardening")),
+            case("quantum physics supercollider", None),
+            // Matches even though it should not
+            case("password manager", None),
+        ];
+        let report = evaluate(&cases, &load_test_index(), &default_config(), 3);
+
+        assert_eq!(report.cases, 4);
+        assert_eq!(report.labelled, 2);
+        assert_eq!(report.predicted, 3);
+        assert_eq!(report.correct, 1);
+        assert_relative_eq!(report.precision(), 1.0 / 3.0);
+        assert_relative_eq!(report.recall(), 0.5);
+        assert_relative_eq!(report.f1(), 0.4);
+
+        let queries: Vec<&str> = report.confusions.iter().map(|c| c.query.as_str()).collect();
+        assert_eq!(queries, vec!["bitcoin node", "password manager"]);
+        assert_eq!(
+            report.confusions[0].predicted.as_deref(),
+            Some("bitcoin-node-setup")
+        );
+        assert_eq!(report.confusions[1].expected, None);
+    }
+
+    #[test]
+    fn test_missed_match_reports_closest() {
+        let cases = [case("rust", Some("rust-learning"))];
+        let config = MatchConfig {
+            match_threshold: 0.99,
+            ..default_config()
+        };
+        let report = evaluate(&cases, &load_test_index(), &config, 3);
+
+        assert_eq!(report.predicted, 0);
+        assert_eq!(report.precision(), 1.0);
+        assert_eq!(report.recall(), 0.0);
+        let confusion = &report.confusions[0];
+        assert_eq!(confusion.predicted, None);
+        let (slug, score) = confusion.closest.as_ref().unwrap();
+        assert_eq!(slug, "rust-learning");
+        assert!(*score < 0.99);
+    }
+
+    #[test]
+    fn test_stop_word_query_matches_nothing() {
+        let cases = [case("the and of", None)];
+        let report = evaluate(&cases, &load_test_index(), &default_config(), 3);
+        assert!(report.confusions.is_empty());
+        assert_eq!(report.predicted, 0);
+    }
+
+    #[test]
+    fn test_top_k_counts_lower_ranked_matches() {
+        // self-hosted-email wins "self hosted"; home-automation-private
+        // ranks third, just above 0.3
+        let cases = [case("self hosted", Some("home-automation-private"))];
+        let index = load_test_index();
+        let config = MatchConfig {
+            match_threshold: 0.3,
+            ..default_config()
+        };
+
+        assert_eq!(evaluate(&cases, &index, &config, 1).top_k_hits, 0);
+        let report = evaluate(&cases, &index, &config, 3);
+        assert_eq!(report.top_k_hits, 1);
+        assert_eq!(report.correct, 0);
+
+        // Matches below the threshold do not count
+        assert_eq!(evaluate(&cases, &index, &default_config(), 3).top_k_hits, 0);
+    }
+
+    #[test]
+    fn test_category_threshold_overrides_global() {
+        let mut registry: Registry =
+            serde_json::from_str(include_str!("../registry.json")).unwrap();
+        registry
+            .categories
+            .get_mut("rust-learning")
+            .unwrap()
+            .match_threshold = Some(0.95);
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let cases = [case("learn rust", Some("rust-learning"))];
+
+        for threshold in [0.0, 0.4] {
+            let config = MatchConfig {
+                match_threshold: threshold,
+                ..default_config()
+            };
+            assert_eq!(evaluate(&cases, &index, &config, 3).correct, 0);
+        }
+    }
+
+    #[test]
+    fn test_grid_search_never_worse_than_base() {
+        let cases = parse_cases(include_str!("../eval/queries.jsonl")).unwrap();
+        let index = load_test_index();
+        // A threshold high enough to reject most true matches, and above
+        // every threshold on the grid
+        let base = MatchConfig {
+            match_threshold: 0.95,
+            ..default_config()
+        };
+
+        let base_report = evaluate(&cases, &index, &base, 3);
+        let best = grid_search(&cases, &index, &base, 3);
+
+        assert!(best.report.f1() >= base_report.f1());
+        assert!(best.config.match_threshold < 0.95);
+        assert!(best.config.validate().is_ok());
+        assert_eq!(best.report, evaluate(&cases, &index, &best.config, 3));
+    }
+
+    #[test]
+    fn test_grid_search_keeps_bm25_weights_fixed_outside_hybrid() {
+        let cases = [case("learn rust", Some("rust-learning"))];
+        let base = MatchConfig {
+            match_mode: MatchMode::Bm25,
+            match_fuzzy_weight: 0.6,
+            match_keyword_weight: 0.4,
+            match_bm25_weight: 0.3,
+            ..default_config()
+        };
+        let best = grid_search(&cases, &load_test_index(), &base, 3);
+
+        assert_eq!(best.config.match_mode, MatchMode::Bm25);
+        assert_eq!(best.config.match_fuzzy_weight, 0.6);
+        assert_eq!(best.config.match_bm25_weight, 0.3);
+    }
+
+    #[tokio::test]
+    async fn test_missing_queries_flag() {
+        let result = run(make_args(&["--grid"])).await;
+        assert!(result.unwrap_err().contains("--queries is required"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_flag() {
+        let result = run(make_args(&["--queries", "q.jsonl", "--bogus"])).await;
+        assert!(result.unwrap_err().contains("Unknown flag"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_k_and_mode() {
+        let result = run(make_args(&["--queries", "q.jsonl", "--k", "0"])).await;
+        assert!(result.unwrap_err().contains("--k must be"));
+
+        let result = run(make_args(&["--queries", "q.jsonl", "--mode", "exact"])).await;
+        assert!(result.unwrap_err().contains("Unknown mode"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_expected_slug_rejected() {
+        let path = format!("/tmp/3gs-eval-unknown-{}.jsonl", std::process::id());
+        std::fs::write(
+            &path,
+            "{\"query\": \"learn rust\", \"expected_slug\": \"rust-learnin\"}\n",
+        )
+        .unwrap();
+
+        let result = run(make_args(&[
+            "--queries",
+            &path,
+            "--registry",
+            "registry.json",
+        ]))
+        .await;
+        std::fs::remove_file(&path).ok();
+
+        assert!(
+            result
+                .unwrap_err()
+                .contains("unknown category rust-learnin")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_runs_seed_query_set() {
+        let result = run(make_args(&[
+            "--queries",
+            "eval/queries.jsonl",
+            "--registry",
+            "registry.json",
+            "--k",
+            "1",
+            "--grid",
+        ]))
+        .await;
+        assert!(result.is_ok(), "eval::run failed: {:?}", result.err());
+    }
+}
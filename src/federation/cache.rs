@@ -1,9 +1,32 @@
-use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use chrono::{DateTime, Utc};
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tokio::task::JoinSet;
 
-use crate::registry::types::Endorsement;
-use super::types::{CachedPeer, PeerRegistry, PeerStatus};
+use crate::matcher::MatchIndex;
+use crate::registry::types::{Endorsement, SUPPORTED_SCHEMA_VERSIONS};
+use crate::registry::{verify_registry, SignatureError, PUBKEY_HEADER, SIGNATURE_HEADER};
+use reqwest::header::{HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use super::persist::{load_peers, save_peers, PersistedPeer, PersistedPeers};
+use super::types::{CachedPeer, PeerRegistry, PeerStatus, Validators};
+
+/// Seconds between peer refreshes (default), also the first backoff step
+const DEFAULT_REFRESH_SECS: u64 = 300;
+
+/// Peer registries fetched at once during a refresh
+const REFRESH_CONCURRENCY: usize = 8;
+
+/// Longest a failing peer waits before it is tried again
+const MAX_BACKOFF: Duration = Duration::from_secs(6 * 3600);
+
+/// Age of the last successful fetch after which a cached registry is Stale
+const STALE_AFTER: Duration = Duration::from_secs(3600);
 
 /// Snapshot of a cached peer for read-only consumers.
 /// Avoids holding the lock while callers process results.
@@ -13,8 +36,17 @@ pub struct CachedPeerSnapshot {
     pub url: String,
     pub name: Option<String>,
     pub registry: Option<PeerRegistry>,
+    pub match_index: Option<Arc<MatchIndex>>,
     pub stale: bool,
     pub status: PeerStatus,
+    /// Wall-clock time of the last successful fetch
+    pub last_success: Option<DateTime<Utc>>,
+    /// Why the last fetch failed, if it did
+    pub last_error: Option<String>,
+    /// Failed fetches since the last success
+    pub consecutive_failures: u32,
+    /// Earliest time a refresh will fetch the peer again, while backing off
+    pub next_attempt: Option<Instant>,
 }
 
 /// Cache of endorsed peer registries.
@@ -23,6 +55,12 @@ pub struct PeerCache {
     peers: RwLock<HashMap<String, CachedPeer>>,
     local_pubkey: String,
     client: reqwest::Client,
+    /// Backoff after a peer's first failure; doubles with each further one
+    backoff_base: Duration,
+    /// File the last good registry of each peer is saved to, if any
+    persist_path: Option<PathBuf>,
+    /// Serializes writes of the peer cache file
+    persist_lock: Mutex<()>,
 }
 
 impl PeerCache {
@@ -32,26 +70,11 @@ impl PeerCache {
         let mut peers = HashMap::new();
 
         for endorsement in endorsements {
-            if endorsement.pubkey == local_pubkey {
-                tracing::warn!(
-                    pubkey = %endorsement.pubkey,
-                    "Self-endorsement detected and filtered from peer cache"
-                );
+            if is_self_endorsement(&endorsement, &local_pubkey) {
                 continue;
             }
 
-            let cached = CachedPeer {
-                pubkey: endorsement.pubkey.clone(),
-                url: endorsement.url.clone(),
-                name: endorsement.name.clone(),
-                since: endorsement.since.clone(),
-                registry: None,
-                last_success: None,
-                last_attempt: None,
-                status: PeerStatus::Unreachable,
-            };
-
-            peers.insert(endorsement.pubkey.clone(), cached);
+            peers.insert(endorsement.pubkey.clone(), new_cached_peer(endorsement));
         }
 
         let client = reqwest::Client::builder()
@@ -63,6 +86,101 @@ impl PeerCache {
             peers: RwLock::new(peers),
             local_pubkey,
             client,
+            backoff_base: Duration::from_secs(DEFAULT_REFRESH_SECS),
+            persist_path: None,
+            persist_lock: Mutex::new(()),
+        }
+    }
+
+    /// Set the first backoff step for failing peers, normally the refresh
+    /// interval so a peer that fails once just waits for the next refresh
+    pub fn with_backoff_base(mut self, backoff_base: Duration) -> Self {
+        self.backoff_base = backoff_base;
+        self
+    }
+
+    /// Save each peer's last good registry to `path` after every fetch, and
+    /// let `restore` load them back on startup
+    pub fn with_persist_path(mut self, path: PathBuf) -> Self {
+        self.persist_path = Some(path);
+        self
+    }
+
+    /// Load the registries saved by a previous run, so endorsed peers serve
+    /// their last good data before the first refresh. A peer is Fresh or
+    /// Stale by the wall-clock age of its saved fetch. Entries for peers no
+    /// longer endorsed, or endorsed at another URL, are ignored. A missing
+    /// or unreadable cache file just leaves the cache empty.
+    pub async fn restore(&self) {
+        let Some(path) = &self.persist_path else {
+            return;
+        };
+        let persisted = match load_peers(path).await {
+            Ok(persisted) => persisted,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring peer cache file");
+                return;
+            }
+        };
+
+        let mut restored = 0usize;
+        let mut peers = self.peers.write().await;
+        for (pubkey, saved) in persisted {
+            let Some(peer) = peers.get_mut(&pubkey) else {
+                continue;
+            };
+            if peer.url != saved.url || peer.registry.is_some() {
+                continue;
+            }
+            if saved.registry.curator.pubkey != pubkey {
+                tracing::warn!(
+                    pubkey = %pubkey,
+                    "Ignoring saved peer registry with another curator pubkey"
+                );
+                continue;
+            }
+            peer.match_index = Some(Arc::new(MatchIndex::new(
+                &saved.registry.categories,
+                &saved.registry.matching,
+            )));
+            peer.registry = Some(saved.registry);
+            peer.validators = saved.validators;
+            peer.last_success = Some(saved.fetched_at);
+            peer.status = if is_stale(peer.last_success) {
+                PeerStatus::Stale
+            } else {
+                PeerStatus::Fresh
+            };
+            restored += 1;
+        }
+        tracing::info!(restored, path = %path.display(), "Peer cache restored from disk");
+    }
+
+    /// Save the last good registry of every trusted peer to the cache file.
+    /// Failures are logged; the in-memory cache is unaffected.
+    async fn persist(&self) {
+        let Some(path) = &self.persist_path else {
+            return;
+        };
+        let _guard = self.persist_lock.lock().await;
+        let persisted: PersistedPeers = {
+            let peers = self.peers.read().await;
+            peers
+                .values()
+                .filter(|peer| peer.status != PeerStatus::Untrusted)
+                .filter_map(|peer| {
+                    let saved = PersistedPeer {
+                        url: peer.url.clone(),
+                        fetched_at: peer.last_success?,
+                        validators: peer.validators.clone(),
+                        registry: peer.registry.clone()?,
+                    };
+                    Some((peer.pubkey.clone(), saved))
+                })
+                .collect()
+        };
+        if let Err(e) = save_peers(path, &persisted).await {
+            tracing::warn!(error = %e, "Failed to save peer cache");
         }
     }
 
@@ -71,98 +189,197 @@ impl PeerCache {
         self.peers.read().await.len()
     }
 
+    /// Reconcile the cache with a new endorsement list (e.g. after a registry reload).
+    /// New peers start Unreachable, peers no longer endorsed are dropped, and peers
+    /// whose URL changed lose their cached registry. Existing peers keep their state.
+    /// Returns the pubkeys of peers that need a fetch (added or re-pointed).
+    pub async fn sync_endorsements(&self, endorsements: Vec<Endorsement>) -> Vec<String> {
+        let mut peers = self.peers.write().await;
+        let mut endorsed: HashSet<String> = HashSet::new();
+        let mut needs_fetch = Vec::new();
+
+        for endorsement in endorsements {
+            if is_self_endorsement(&endorsement, &self.local_pubkey) {
+                continue;
+            }
+            endorsed.insert(endorsement.pubkey.clone());
+
+            match peers.get_mut(&endorsement.pubkey) {
+                Some(peer) => {
+                    if peer.url != endorsement.url {
+                        needs_fetch.push(endorsement.pubkey.clone());
+                        *peer = new_cached_peer(endorsement);
+                    } else {
+                        peer.name = endorsement.name;
+                        peer.since = endorsement.since;
+                    }
+                }
+                None => {
+                    needs_fetch.push(endorsement.pubkey.clone());
+                    peers.insert(endorsement.pubkey.clone(), new_cached_peer(endorsement));
+                }
+            }
+        }
+
+        let before = peers.len();
+        peers.retain(|pubkey, _| endorsed.contains(pubkey));
+
+        tracing::info!(
+            peers = peers.len(),
+            removed = before - peers.len(),
+            to_fetch = needs_fetch.len(),
+            "Peer cache synced with endorsements"
+        );
+
+        needs_fetch
+    }
+
     /// Returns the local node's pubkey
     pub fn local_pubkey(&self) -> &str {
         &self.local_pubkey
     }
 
-    /// Fetch the /registry endpoint from a single peer and update its cached state.
-    /// On success: sets status to Fresh, stores PeerRegistry, updates last_success.
-    /// On failure: logs WARN, keeps existing registry, marks Stale if >1hr since last success.
+    /// Fetch the /registry endpoint from a single peer and update its cached state,
+    /// regardless of any backoff in effect.
     pub async fn fetch_peer(&self, pubkey: &str) {
-        // Acquire read lock to get peer URL, then release before HTTP call
-        let peer_url = {
+        // Acquire read lock to get peer URL and validators, then release before HTTP call
+        let peer_target = {
             let peers = self.peers.read().await;
-            peers.get(pubkey).map(|p| p.url.clone())
+            peers.get(pubkey).map(|p| (p.url.clone(), conditional_validators(p)))
         };
 
-        let url = match peer_url {
-            Some(u) => u,
+        let (url, validators) = match peer_target {
+            Some(target) => target,
             None => {
                 tracing::warn!(pubkey = %pubkey, "fetch_peer called for unknown pubkey");
                 return;
             }
         };
 
-        let registry_url = format!("{}/registry", url.trim_end_matches('/'));
-
-        match self.client.get(&registry_url).send().await {
-            Ok(response) => {
-                match response.json::<PeerRegistry>().await {
-                    Ok(parsed) => {
-                        let mut peers = self.peers.write().await;
-                        if let Some(peer) = peers.get_mut(pubkey) {
-                            peer.registry = Some(parsed);
-                            peer.last_success = Some(Instant::now());
-                            peer.last_attempt = Some(Instant::now());
-                            peer.status = PeerStatus::Fresh;
-                            tracing::debug!(pubkey = %pubkey, url = %registry_url, "Peer registry fetched successfully");
-                        }
+        let result =
+            fetch_registry(self.client.clone(), url, pubkey.to_string(), validators).await;
+        self.record_fetch(pubkey, result).await;
+        self.persist().await;
+    }
+
+    /// Store the result of fetching a peer's registry.
+    /// On success: sets status to Fresh, stores PeerRegistry, its MatchIndex and validators,
+    /// updates last_success and clears the peer's backoff.
+    /// On 304 Not Modified: the same, keeping the cached registry.
+    /// On failed verification (see `read_peer_registry`): logs WARN and marks the peer Untrusted.
+    /// On failure: logs WARN, keeps existing registry, marks Stale if >1hr since last success.
+    /// Every failure counts towards the peer's backoff.
+    async fn record_fetch(&self, pubkey: &str, result: FetchResult) {
+        let registry_url = result.registry_url;
+        match result.outcome {
+            Ok(PeerFetch::Updated(parsed, validators)) => {
+                // Lax parsing keeps what this node understands of newer schemas
+                if let Some(version) = parsed
+                    .schema_version
+                    .filter(|v| !SUPPORTED_SCHEMA_VERSIONS.contains(v))
+                {
+                    tracing::info!(pubkey = %pubkey, schema_version = version, "Peer registry uses an unsupported schema version; unknown fields are ignored");
+                }
+                // Build the index before taking the write lock
+                let match_index = Arc::new(MatchIndex::new(&parsed.categories, &parsed.matching));
+                let mut peers = self.peers.write().await;
+                if let Some(peer) = peers.get_mut(pubkey) {
+                    peer.registry = Some(parsed);
+                    peer.match_index = Some(match_index);
+                    peer.validators = validators;
+                    record_success(peer);
+                    tracing::debug!(pubkey = %pubkey, url = %registry_url, "Peer registry fetched successfully");
+                }
+            }
+            Ok(PeerFetch::NotModified) => {
+                let mut peers = self.peers.write().await;
+                if let Some(peer) = peers.get_mut(pubkey) {
+                    // Only a trusted cached registry can be revalidated
+                    if peer.registry.is_some() && peer.status != PeerStatus::Untrusted {
+                        record_success(peer);
+                        tracing::debug!(pubkey = %pubkey, url = %registry_url, "Peer registry not modified");
+                    } else {
+                        tracing::warn!(pubkey = %pubkey, url = %registry_url, "Peer answered 304 with no trusted cached registry");
+                        peer.last_error =
+                            Some("304 Not Modified with no trusted cached registry".to_string());
+                        record_failure(peer, self.backoff_base);
+                    }
+                }
+            }
+            Err(error) => {
+                match &error {
+                    FetchError::Request(err) => {
+                        tracing::warn!(pubkey = %pubkey, url = %registry_url, error = %err, "Failed to fetch peer registry");
                     }
-                    Err(err) => {
+                    FetchError::Invalid(err) => {
                         tracing::warn!(pubkey = %pubkey, url = %registry_url, error = %err, "Failed to parse peer registry");
-                        let mut peers = self.peers.write().await;
-                        if let Some(peer) = peers.get_mut(pubkey) {
-                            peer.last_attempt = Some(Instant::now());
-                            let stale_threshold = Duration::from_secs(3600);
-                            let is_stale = peer.last_success
-                                .map(|t| t.elapsed() > stale_threshold)
-                                .unwrap_or(true);
-                            if is_stale {
-                                peer.status = if peer.registry.is_some() {
-                                    PeerStatus::Stale
-                                } else {
-                                    PeerStatus::Unreachable
-                                };
-                            }
-                        }
+                    }
+                    FetchError::Untrusted(reason) => {
+                        tracing::warn!(pubkey = %pubkey, url = %registry_url, reason = %reason, "Peer registry failed verification, peer marked untrusted");
                     }
                 }
-            }
-            Err(err) => {
-                tracing::warn!(pubkey = %pubkey, url = %registry_url, error = %err, "Failed to fetch peer registry");
                 let mut peers = self.peers.write().await;
                 if let Some(peer) = peers.get_mut(pubkey) {
-                    peer.last_attempt = Some(Instant::now());
-                    let stale_threshold = Duration::from_secs(3600);
-                    let is_stale = peer.last_success
-                        .map(|t| t.elapsed() > stale_threshold)
-                        .unwrap_or(true);
-                    if is_stale {
-                        peer.status = if peer.registry.is_some() {
-                            PeerStatus::Stale
-                        } else {
-                            PeerStatus::Unreachable
-                        };
+                    if matches!(error, FetchError::Untrusted(_)) {
+                        peer.status = PeerStatus::Untrusted;
                     }
+                    peer.last_error = Some(error.to_string());
+                    record_failure(peer, self.backoff_base);
+                    tracing::debug!(
+                        pubkey = %pubkey,
+                        failures = peer.consecutive_failures,
+                        "Peer backing off"
+                    );
                 }
             }
         }
     }
 
-    /// Refresh all peers sequentially by fetching their /registry endpoints.
+    /// Refresh every peer that is not backing off, fetching up to
+    /// REFRESH_CONCURRENCY /registry endpoints at once.
     pub async fn refresh_all(&self) {
-        // Collect pubkeys while holding the read lock, then release
-        let pubkeys: Vec<String> = {
+        // Collect due peers while holding the read lock, then release
+        let now = Instant::now();
+        let mut due = Vec::new();
+        let mut backing_off = 0usize;
+        {
             let peers = self.peers.read().await;
-            peers.keys().cloned().collect()
-        };
+            for peer in peers.values() {
+                if peer.next_attempt.is_some_and(|next| next > now) {
+                    backing_off += 1;
+                } else {
+                    due.push((peer.pubkey.clone(), peer.url.clone(), conditional_validators(peer)));
+                }
+            }
+        }
 
-        tracing::info!(count = pubkeys.len(), "Refreshing peer cache ({} peers)", pubkeys.len());
+        tracing::info!(
+            count = due.len(),
+            backing_off,
+            "Refreshing peer cache ({} peers)",
+            due.len()
+        );
 
-        for pubkey in pubkeys {
-            self.fetch_peer(&pubkey).await;
+        let permits = Arc::new(Semaphore::new(REFRESH_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (pubkey, url, validators) in due {
+            let client = self.client.clone();
+            let permits = Arc::clone(&permits);
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await.expect("semaphore is never closed");
+                let result = fetch_registry(client, url, pubkey.clone(), validators).await;
+                (pubkey, result)
+            });
         }
+
+        // Fetches run in parallel; their results are stored one at a time
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((pubkey, result)) => self.record_fetch(&pubkey, result).await,
+                Err(e) => tracing::error!(error = %e, "Peer refresh task failed"),
+            }
+        }
+        self.persist().await;
     }
 
     /// Returns a snapshot of all cached peers.
@@ -174,16 +391,255 @@ impl PeerCache {
             url: peer.url.clone(),
             name: peer.name.clone(),
             registry: peer.registry.clone(),
+            match_index: peer.match_index.clone(),
             stale: peer.status == PeerStatus::Stale,
             status: peer.status.clone(),
+            last_success: peer.last_success,
+            last_error: peer.last_error.clone(),
+            consecutive_failures: peer.consecutive_failures,
+            next_attempt: peer.next_attempt,
         }).collect()
     }
 }
 
+/// Why fetching a peer's registry failed
+enum FetchError {
+    /// The request failed or timed out
+    Request(String),
+    /// The body could not be read or parsed
+    Invalid(String),
+    /// The registry failed verification against the endorsed pubkey
+    Untrusted(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Request(err) => write!(f, "request failed: {}", err),
+            FetchError::Invalid(err) => write!(f, "invalid registry: {}", err),
+            FetchError::Untrusted(reason) => write!(f, "failed verification: {}", reason),
+        }
+    }
+}
+
+/// A peer registry fetched and verified
+enum PeerFetch {
+    /// A new copy of the registry, with its cache validators
+    Updated(PeerRegistry, Validators),
+    /// 304 Not Modified: the cached copy is current
+    NotModified,
+}
+
+/// Outcome of fetching one peer's registry
+struct FetchResult {
+    registry_url: String,
+    outcome: Result<PeerFetch, FetchError>,
+}
+
+/// Validators to make a peer's next fetch conditional on. Only a trusted
+/// cached registry is worth revalidating.
+fn conditional_validators(peer: &CachedPeer) -> Validators {
+    if peer.registry.is_some() && peer.status != PeerStatus::Untrusted {
+        peer.validators.clone()
+    } else {
+        Validators::default()
+    }
+}
+
+/// Fetch and verify a peer's registry, conditional on `validators` when set.
+/// Takes owned arguments so refreshes can run it on spawned tasks.
+async fn fetch_registry(
+    client: reqwest::Client,
+    url: String,
+    pubkey: String,
+    validators: Validators,
+) -> FetchResult {
+    let registry_url = format!("{}/registry", url.trim_end_matches('/'));
+    let mut request = client.get(&registry_url);
+    if let Some(etag) = &validators.etag {
+        request = request.header(IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let outcome = match request.send().await {
+        Ok(response) if response.status() == StatusCode::NOT_MODIFIED => {
+            Ok(PeerFetch::NotModified)
+        }
+        // An error page is an outage, not an unsigned registry
+        Ok(response) if !response.status().is_success() => {
+            Err(FetchError::Request(format!("HTTP {}", response.status())))
+        }
+        Ok(response) => {
+            let validators = response_validators(&response);
+            read_peer_registry(response, &pubkey)
+                .await
+                .map(|registry| PeerFetch::Updated(registry, validators))
+        }
+        Err(err) => Err(FetchError::Request(err.to_string())),
+    };
+    FetchResult {
+        registry_url,
+        outcome,
+    }
+}
+
+/// ETag and Last-Modified of a response
+fn response_validators(response: &reqwest::Response) -> Validators {
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    Validators {
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
+    }
+}
+
+/// Read a peer's /registry response and verify it against the endorsed
+/// pubkey: the registry must be signed by that key, and its curator pubkey
+/// must be that key.
+async fn read_peer_registry(
+    response: reqwest::Response,
+    pubkey: &str,
+) -> Result<PeerRegistry, FetchError> {
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let signature = header(SIGNATURE_HEADER);
+    let signer = header(PUBKEY_HEADER);
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| FetchError::Invalid(e.to_string()))?;
+
+    if let Some(signer) = signer.filter(|signer| signer != pubkey) {
+        return Err(FetchError::Untrusted(format!(
+            "registry is signed by {} instead of the endorsed pubkey",
+            signer
+        )));
+    }
+    let Some(signature) = signature else {
+        return Err(FetchError::Untrusted("registry is unsigned".to_string()));
+    };
+    let value = verify_registry(&body, &signature, pubkey).map_err(|e| match e {
+        SignatureError::JsonParse { .. } => FetchError::Invalid(e.to_string()),
+        _ => FetchError::Untrusted(e.to_string()),
+    })?;
+
+    let parsed: PeerRegistry =
+        serde_json::from_value(value).map_err(|e| FetchError::Invalid(e.to_string()))?;
+    if parsed.curator.pubkey != pubkey {
+        return Err(FetchError::Untrusted(format!(
+            "curator pubkey {} is not the endorsed pubkey",
+            parsed.curator.pubkey
+        )));
+    }
+    Ok(parsed)
+}
+
+/// Record a successful fetch: the peer is Fresh and its backoff cleared
+fn record_success(peer: &mut CachedPeer) {
+    peer.last_success = Some(Utc::now());
+    peer.last_attempt = Some(Instant::now());
+    peer.status = PeerStatus::Fresh;
+    peer.consecutive_failures = 0;
+    peer.next_attempt = None;
+    peer.last_error = None;
+}
+
+/// Record a failed fetch and schedule the next attempt with backoff.
+/// Once the last success is more than an hour old the peer turns Stale (or
+/// Unreachable with nothing cached). An untrusted peer stays untrusted until
+/// it serves a registry that verifies.
+fn record_failure(peer: &mut CachedPeer, backoff_base: Duration) {
+    let now = Instant::now();
+    peer.last_attempt = Some(now);
+    peer.consecutive_failures = peer.consecutive_failures.saturating_add(1);
+    peer.next_attempt =
+        Some(now + backoff_delay(backoff_base, peer.consecutive_failures, random_unit()));
+
+    if peer.status == PeerStatus::Untrusted {
+        return;
+    }
+    if is_stale(peer.last_success) {
+        peer.status = if peer.registry.is_some() {
+            PeerStatus::Stale
+        } else {
+            PeerStatus::Unreachable
+        };
+    }
+}
+
+/// Whether a registry last fetched at `last_success` is older than
+/// STALE_AFTER, by the wall clock. Nothing fetched counts as stale.
+fn is_stale(last_success: Option<DateTime<Utc>>) -> bool {
+    last_success.is_none_or(|t| (Utc::now() - t).to_std().is_ok_and(|age| age > STALE_AFTER))
+}
+
+/// Delay before retrying a peer after `failures` consecutive failures:
+/// `base` doubled for each failure after the first, capped at MAX_BACKOFF,
+/// then scaled into its upper half by `jitter` (0.0 to 1.0) so peers that
+/// failed together do not retry together.
+fn backoff_delay(base: Duration, failures: u32, jitter: f64) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    let delay = base.saturating_mul(1 << doublings).min(MAX_BACKOFF);
+    delay.mul_f64(0.5 + 0.5 * jitter.clamp(0.0, 1.0))
+}
+
+/// A random number in 0.0..1.0, taken from the randomly seeded std hasher
+fn random_unit() -> f64 {
+    let random = RandomState::new().build_hasher().finish();
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Returns true (and logs at WARN) if the endorsement points at the local node
+fn is_self_endorsement(endorsement: &Endorsement, local_pubkey: &str) -> bool {
+    if endorsement.pubkey == local_pubkey {
+        tracing::warn!(
+            pubkey = %endorsement.pubkey,
+            "Self-endorsement detected and filtered from peer cache"
+        );
+        return true;
+    }
+    false
+}
+
+/// Build an empty cache entry for a newly endorsed peer
+fn new_cached_peer(endorsement: Endorsement) -> CachedPeer {
+    CachedPeer {
+        pubkey: endorsement.pubkey,
+        url: endorsement.url,
+        name: endorsement.name,
+        since: endorsement.since,
+        registry: None,
+        match_index: None,
+        last_success: None,
+        last_attempt: None,
+        status: PeerStatus::Unreachable,
+        consecutive_failures: 0,
+        next_attempt: None,
+        validators: Validators::default(),
+        last_error: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::registry::types::Endorsement;
+    use crate::registry::types::{Endorsement, SUPPORTED_SCHEMA_VERSIONS};
+    use crate::registry::{sign_registry, Registry};
+    use crate::test_support::TempPath;
+
+    const REGISTRY_JSON: &str = include_str!("../../registry.json");
 
     fn make_endorsement(pubkey: &str, url: &str) -> Endorsement {
         Endorsement {
@@ -194,6 +650,44 @@ mod tests {
         }
     }
 
+    /// Serve `body` at /registry on a random local port with the given
+    /// response headers. Returns the peer's base URL.
+    async fn spawn_peer(body: String, headers: Vec<(&'static str, String)>) -> String {
+        let app = axum::Router::new().route(
+            "/registry",
+            axum::routing::get(move || {
+                let body = body.clone();
+                let mut header_map = axum::http::HeaderMap::new();
+                for (name, value) in &headers {
+                    header_map.insert(*name, value.parse().unwrap());
+                }
+                async move { (header_map, body) }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    /// The seed registry with its curator pubkey set to `keypair`'s,
+    /// pretty-printed, and its signature by `keypair`
+    fn signed_registry(keypair: &pkarr::Keypair) -> (String, String) {
+        let mut registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        registry.curator.pubkey = keypair.public_key().to_z32();
+        let signature = sign_registry(&registry, keypair);
+        (serde_json::to_string_pretty(&registry).unwrap(), signature)
+    }
+
+    /// Fetch a single peer served at `url` and return its snapshot
+    async fn fetch_from(pubkey: &str, url: &str) -> CachedPeerSnapshot {
+        let cache = PeerCache::new(vec![make_endorsement(pubkey, url)], "local-key".to_string());
+        cache.fetch_peer(pubkey).await;
+        cache.get_all_cached().await.remove(0)
+    }
+
     fn make_endorsement_with_name(pubkey: &str, url: &str, name: &str) -> Endorsement {
         Endorsement {
             pubkey: pubkey.to_string(),
@@ -271,6 +765,7 @@ mod tests {
             assert_eq!(snap.status, PeerStatus::Unreachable);
             assert!(!snap.stale); // Unreachable != Stale
             assert!(snap.registry.is_none());
+            assert!(snap.match_index.is_none());
         }
     }
 
@@ -306,4 +801,460 @@ mod tests {
         cache.refresh_all().await;
         assert!(cache.get_all_cached().await.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_sync_endorsements_adds_and_removes() {
+        let endorsements = vec![
+            make_endorsement("peer-a", "http://a.example.com"),
+            make_endorsement("peer-b", "http://b.example.com"),
+        ];
+        let cache = PeerCache::new(endorsements, "local-key".to_string());
+
+        let to_fetch = cache
+            .sync_endorsements(vec![
+                make_endorsement("peer-b", "http://b.example.com"),
+                make_endorsement("peer-c", "http://c.example.com"),
+                make_endorsement("local-key", "http://self.example.com"),
+            ])
+            .await;
+
+        assert_eq!(to_fetch, vec!["peer-c".to_string()]);
+        let mut pubkeys: Vec<String> = cache
+            .get_all_cached()
+            .await
+            .into_iter()
+            .map(|p| p.pubkey)
+            .collect();
+        pubkeys.sort();
+        assert_eq!(pubkeys, vec!["peer-b".to_string(), "peer-c".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_endorsements_keeps_existing_state() {
+        let cache = PeerCache::new(
+            vec![make_endorsement("peer-a", "http://a.example.com")],
+            "local-key".to_string(),
+        );
+        {
+            let mut peers = cache.peers.write().await;
+            peers.get_mut("peer-a").unwrap().status = PeerStatus::Fresh;
+        }
+
+        let to_fetch = cache
+            .sync_endorsements(vec![make_endorsement_with_name(
+                "peer-a",
+                "http://a.example.com",
+                "Alice",
+            )])
+            .await;
+
+        assert!(to_fetch.is_empty());
+        let snapshots = cache.get_all_cached().await;
+        assert_eq!(snapshots[0].status, PeerStatus::Fresh);
+        assert_eq!(snapshots[0].name, Some("Alice".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_sync_endorsements_url_change_resets_peer() {
+        let cache = PeerCache::new(
+            vec![make_endorsement("peer-a", "http://a.example.com")],
+            "local-key".to_string(),
+        );
+        {
+            let mut peers = cache.peers.write().await;
+            peers.get_mut("peer-a").unwrap().status = PeerStatus::Fresh;
+        }
+
+        let to_fetch = cache
+            .sync_endorsements(vec![make_endorsement("peer-a", "http://new.example.com")])
+            .await;
+
+        assert_eq!(to_fetch, vec!["peer-a".to_string()]);
+        let snapshots = cache.get_all_cached().await;
+        assert_eq!(snapshots[0].url, "http://new.example.com");
+        assert_eq!(snapshots[0].status, PeerStatus::Unreachable);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_peer_accepts_verified_registry() {
+        let keypair = pkarr::Keypair::from_secret_key(&[3; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let (body, signature) = signed_registry(&keypair);
+
+        let headers = vec![(SIGNATURE_HEADER, signature), (PUBKEY_HEADER, pubkey.clone())];
+        let url = spawn_peer(body, headers).await;
+        let snapshot = fetch_from(&pubkey, &url).await;
+        assert_eq!(snapshot.status, PeerStatus::Fresh);
+        assert_eq!(snapshot.registry.unwrap().curator.pubkey, pubkey);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_peer_marks_unverified_registry_untrusted() {
+        let keypair = pkarr::Keypair::from_secret_key(&[3; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let other = pkarr::Keypair::from_secret_key(&[4; 32]);
+        let (body, signature) = signed_registry(&keypair);
+
+        let unsigned = spawn_peer(body.clone(), vec![]).await;
+        // The original signature over altered content
+        let tampered = body.replace("https://doc.rust-lang.org/book/", "https://attacker.example/");
+        let tampered = spawn_peer(tampered, vec![(SIGNATURE_HEADER, signature.clone())]).await;
+        // Validly signed, but by a node announcing another key
+        let headers = vec![
+            (SIGNATURE_HEADER, signature.clone()),
+            (PUBKEY_HEADER, other.public_key().to_z32()),
+        ];
+        let other_signer = spawn_peer(body.clone(), headers).await;
+        // Signed by the endorsed key but naming another curator
+        let mut registry: Registry = serde_json::from_str(&body).unwrap();
+        registry.curator.pubkey = other.public_key().to_z32();
+        let headers = vec![(SIGNATURE_HEADER, sign_registry(&registry, &keypair))];
+        let other_curator =
+            spawn_peer(serde_json::to_string(&registry).unwrap(), headers).await;
+
+        for url in [unsigned, tampered, other_signer, other_curator] {
+            let snapshot = fetch_from(&pubkey, &url).await;
+            assert_eq!(snapshot.status, PeerStatus::Untrusted, "served from {}", url);
+            assert!(snapshot.registry.is_none());
+        }
+
+        // A valid registry endorsed under a different key
+        let (body, signature) = signed_registry(&other);
+        let url = spawn_peer(body, vec![(SIGNATURE_HEADER, signature)]).await;
+        assert_eq!(fetch_from(&pubkey, &url).await.status, PeerStatus::Untrusted);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_peer_unparseable_registry_is_not_untrusted() {
+        let keypair = pkarr::Keypair::from_secret_key(&[3; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let (_, signature) = signed_registry(&keypair);
+
+        let url = spawn_peer("<html>oops</html>".to_string(), vec![(SIGNATURE_HEADER, signature)])
+            .await;
+        assert_eq!(fetch_from(&pubkey, &url).await.status, PeerStatus::Unreachable);
+    }
+
+    #[tokio::test]
+    async fn test_untrusted_peer_stays_untrusted_on_fetch_failure() {
+        let cache = PeerCache::new(
+            vec![make_endorsement("peer-a", "http://127.0.0.1:1")],
+            "local-key".to_string(),
+        );
+        {
+            let mut peers = cache.peers.write().await;
+            peers.get_mut("peer-a").unwrap().status = PeerStatus::Untrusted;
+        }
+
+        cache.fetch_peer("peer-a").await;
+        assert_eq!(cache.get_all_cached().await[0].status, PeerStatus::Untrusted);
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_with_jitter_and_cap() {
+        let base = Duration::from_secs(300);
+        assert_eq!(backoff_delay(base, 1, 1.0), Duration::from_secs(300));
+        assert_eq!(backoff_delay(base, 1, 0.0), Duration::from_secs(150));
+        assert_eq!(backoff_delay(base, 3, 1.0), Duration::from_secs(1200));
+        assert_eq!(backoff_delay(base, 3, 0.5), Duration::from_secs(900));
+        assert_eq!(backoff_delay(base, 30, 1.0), MAX_BACKOFF);
+
+        for _ in 0..100 {
+            let jitter = random_unit();
+            assert!((0.0..1.0).contains(&jitter));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_peer_backs_off() {
+        let cache = PeerCache::new(
+            vec![make_endorsement("peer-a", "http://127.0.0.1:1")],
+            "local-key".to_string(),
+        )
+        .with_backoff_base(Duration::from_secs(60));
+
+        cache.fetch_peer("peer-a").await;
+        let snapshot = cache.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.consecutive_failures, 1);
+        let next_attempt = snapshot.next_attempt.expect("failure schedules a retry");
+        let wait = next_attempt.saturating_duration_since(Instant::now());
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(60));
+
+        // Backing off: a refresh leaves the peer alone
+        cache.refresh_all().await;
+        assert_eq!(cache.get_all_cached().await[0].consecutive_failures, 1);
+
+        // A direct fetch ignores the backoff and doubles it
+        cache.fetch_peer("peer-a").await;
+        let snapshot = cache.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.consecutive_failures, 2);
+        let wait = snapshot.next_attempt.unwrap().saturating_duration_since(Instant::now());
+        assert!(wait > Duration::from_secs(55) && wait <= Duration::from_secs(120));
+    }
+
+    #[tokio::test]
+    async fn test_success_resets_backoff() {
+        let keypair = pkarr::Keypair::from_secret_key(&[3; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let (body, signature) = signed_registry(&keypair);
+        let url = spawn_peer(body, vec![(SIGNATURE_HEADER, signature)]).await;
+
+        let cache = PeerCache::new(vec![make_endorsement(&pubkey, &url)], "local-key".to_string());
+        {
+            let mut peers = cache.peers.write().await;
+            let peer = peers.get_mut(&pubkey).unwrap();
+            peer.consecutive_failures = 3;
+            peer.next_attempt = Some(Instant::now());
+        }
+
+        cache.refresh_all().await;
+        let snapshot = cache.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.status, PeerStatus::Fresh);
+        assert_eq!(snapshot.consecutive_failures, 0);
+        assert!(snapshot.next_attempt.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_all_fetches_concurrently() {
+        let app = axum::Router::new().route(
+            "/registry",
+            axum::routing::get(|| async {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                "{}"
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let endorsements = ["peer-a", "peer-b", "peer-c", "peer-d"]
+            .iter()
+            .map(|pubkey| make_endorsement(pubkey, &url))
+            .collect();
+        let cache = PeerCache::new(endorsements, "local-key".to_string());
+
+        // Four 500ms responses would take 2s one after another
+        let started = Instant::now();
+        cache.refresh_all().await;
+        assert!(started.elapsed() < Duration::from_millis(1500));
+
+        let snapshots = cache.get_all_cached().await;
+        assert!(snapshots.iter().all(|peer| peer.consecutive_failures == 1));
+        assert!(snapshots.iter().all(|peer| peer.status == PeerStatus::Untrusted));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_peer_revalidates_with_etag() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let keypair = pkarr::Keypair::from_secret_key(&[4; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let (body, signature) = signed_registry(&keypair);
+        let etag = "\"v1\"";
+        let last_modified = "Sun, 01 Feb 2026 00:00:00 GMT";
+
+        // 304 when If-None-Match names the served ETag, counting each kind
+        let full = Arc::new(AtomicUsize::new(0));
+        let not_modified = Arc::new(AtomicUsize::new(0));
+        let (full_count, not_modified_count) = (Arc::clone(&full), Arc::clone(&not_modified));
+        let app = axum::Router::new().route(
+            "/registry",
+            axum::routing::get(move |headers: axum::http::HeaderMap| {
+                let (body, signature) = (body.clone(), signature.clone());
+                let (full, not_modified) = (full_count.clone(), not_modified_count.clone());
+                async move {
+                    use axum::response::IntoResponse;
+                    let matches = headers
+                        .get(IF_NONE_MATCH)
+                        .is_some_and(|value| value.as_bytes() == etag.as_bytes());
+                    if matches {
+                        not_modified.fetch_add(1, Ordering::SeqCst);
+                        return axum::http::StatusCode::NOT_MODIFIED.into_response();
+                    }
+                    full.fetch_add(1, Ordering::SeqCst);
+                    let mut header_map = axum::http::HeaderMap::new();
+                    header_map.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+                    header_map.insert(ETAG, etag.parse().unwrap());
+                    header_map.insert(LAST_MODIFIED, last_modified.parse().unwrap());
+                    (header_map, body).into_response()
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let cache = PeerCache::new(vec![make_endorsement(&pubkey, &url)], "local-key".to_string());
+        cache.fetch_peer(&pubkey).await;
+        {
+            let peers = cache.peers.read().await;
+            let validators = &peers[&pubkey].validators;
+            assert_eq!(validators.etag.as_deref(), Some(etag));
+            assert_eq!(validators.last_modified.as_deref(), Some(last_modified));
+        }
+
+        cache.fetch_peer(&pubkey).await;
+        assert_eq!(full.load(Ordering::SeqCst), 1);
+        assert_eq!(not_modified.load(Ordering::SeqCst), 1);
+        let snapshot = cache.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.status, PeerStatus::Fresh);
+        assert_eq!(snapshot.consecutive_failures, 0);
+        assert!(snapshot.registry.is_some(), "304 keeps the cached registry");
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_without_cached_registry_is_failure() {
+        let app = axum::Router::new().route(
+            "/registry",
+            axum::routing::get(|| async { axum::http::StatusCode::NOT_MODIFIED }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let snapshot = fetch_from("peer-a", &url).await;
+        assert_eq!(snapshot.status, PeerStatus::Unreachable);
+        assert_eq!(snapshot.consecutive_failures, 1);
+        assert!(snapshot.registry.is_none());
+    }
+
+    /// The seed registry as peer `pubkey` saved it `age_mins` ago from `url`
+    fn saved_peer(pubkey: &str, url: &str, age_mins: i64) -> (String, PersistedPeer) {
+        let mut registry: PeerRegistry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        registry.curator.pubkey = pubkey.to_string();
+        let saved = PersistedPeer {
+            url: url.to_string(),
+            fetched_at: Utc::now() - chrono::TimeDelta::minutes(age_mins),
+            validators: Validators::default(),
+            registry,
+        };
+        (pubkey.to_string(), saved)
+    }
+
+    #[tokio::test]
+    async fn test_restore_uses_wall_clock_staleness() {
+        let path = TempPath::new("peer-cache.json");
+        let mut wrong_curator = saved_peer("peer-e", "https://e.example", 1);
+        wrong_curator.1.registry.curator.pubkey = "peer-x".to_string();
+        let saved = PersistedPeers::from([
+            saved_peer("peer-a", "https://a.example", 10),
+            saved_peer("peer-b", "https://b.example", 2 * 24 * 60),
+            // Endorsed at another URL now
+            saved_peer("peer-c", "https://old.example", 1),
+            // No longer endorsed
+            saved_peer("peer-d", "https://d.example", 1),
+            wrong_curator,
+        ]);
+        save_peers(&path, &saved).await.unwrap();
+
+        let endorsements = vec![
+            make_endorsement("peer-a", "https://a.example"),
+            make_endorsement("peer-b", "https://b.example"),
+            make_endorsement("peer-c", "https://c.example"),
+            make_endorsement("peer-e", "https://e.example"),
+        ];
+        let cache = PeerCache::new(endorsements, "local-key".to_string())
+            .with_persist_path(path.to_path_buf());
+        cache.restore().await;
+
+        let peers = cache.peers.read().await;
+        assert_eq!(peers.len(), 4);
+        assert_eq!(peers["peer-a"].status, PeerStatus::Fresh);
+        assert!(peers["peer-a"].match_index.is_some());
+        assert_eq!(peers["peer-b"].status, PeerStatus::Stale);
+        assert!(peers["peer-b"].registry.is_some());
+        assert_eq!(peers["peer-c"].status, PeerStatus::Unreachable);
+        assert!(peers["peer-c"].registry.is_none());
+        assert!(peers["peer-e"].registry.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_fetched_registry_survives_restart() {
+        let keypair = pkarr::Keypair::from_secret_key(&[6; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let (body, signature) = signed_registry(&keypair);
+        let headers = vec![(SIGNATURE_HEADER, signature), ("etag", "\"v1\"".to_string())];
+        let url = spawn_peer(body, headers).await;
+        let path = TempPath::new("peer-cache.json");
+
+        let endorsements = vec![make_endorsement(&pubkey, &url)];
+        let cache = PeerCache::new(endorsements.clone(), "local-key".to_string())
+            .with_persist_path(path.to_path_buf());
+        cache.fetch_peer(&pubkey).await;
+        let saved = load_peers(&path).await.unwrap();
+        assert_eq!(saved[&pubkey].url, url);
+        assert_eq!(saved[&pubkey].validators.etag.as_deref(), Some("\"v1\""));
+
+        // After a restart the saved registry serves before any fetch
+        let restarted = PeerCache::new(endorsements, "local-key".to_string())
+            .with_persist_path(path.to_path_buf());
+        restarted.restore().await;
+        let snapshot = restarted.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.status, PeerStatus::Fresh);
+        assert_eq!(snapshot.registry.unwrap().curator.pubkey, pubkey);
+        assert!(snapshot.match_index.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_restored_peer_stays_usable_when_unreachable() {
+        let path = TempPath::new("peer-cache.json");
+        let url = "http://127.0.0.1:1";
+        let saved = PersistedPeers::from([saved_peer("peer-a", url, 3 * 60)]);
+        save_peers(&path, &saved).await.unwrap();
+
+        let cache = PeerCache::new(vec![make_endorsement("peer-a", url)], "local-key".to_string())
+            .with_persist_path(path.to_path_buf());
+        cache.restore().await;
+        cache.refresh_all().await;
+
+        let snapshot = cache.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.status, PeerStatus::Stale);
+        assert!(snapshot.stale);
+        assert!(snapshot.registry.is_some());
+        // The failed fetch does not wipe the saved registry
+        assert!(load_peers(&path).await.unwrap().contains_key("peer-a"));
+    }
+
+    #[tokio::test]
+    async fn test_http_error_is_not_untrusted() {
+        let app = axum::Router::new().route(
+            "/registry",
+            axum::routing::get(|| async { axum::http::StatusCode::INTERNAL_SERVER_ERROR }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let snapshot = fetch_from("peer-a", &url).await;
+        assert_eq!(snapshot.status, PeerStatus::Unreachable);
+        assert_eq!(
+            snapshot.last_error.as_deref(),
+            Some("request failed: HTTP 500 Internal Server Error")
+        );
+
+        // A peer with a cached registry goes Stale, keeping it
+        let keypair = pkarr::Keypair::from_secret_key(&[7; 32]);
+        let pubkey = keypair.public_key().to_z32();
+        let cache = PeerCache::new(vec![make_endorsement(&pubkey, &url)], "local-key".to_string());
+        {
+            let mut peers = cache.peers.write().await;
+            let peer = peers.get_mut(&pubkey).unwrap();
+            let (body, _) = signed_registry(&keypair);
+            peer.registry = Some(serde_json::from_str(&body).unwrap());
+            peer.last_success = Some(Utc::now() - chrono::TimeDelta::hours(2));
+            peer.status = PeerStatus::Fresh;
+        }
+        cache.fetch_peer(&pubkey).await;
+        let snapshot = cache.get_all_cached().await.remove(0);
+        assert_eq!(snapshot.status, PeerStatus::Stale);
+        assert!(snapshot.registry.is_some());
+    }
 }
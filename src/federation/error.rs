@@ -18,4 +18,16 @@ pub enum FederationError {
     /// Self-endorsement detected
     #[error("Self-endorsement detected for pubkey {pubkey}")]
     SelfEndorsement { pubkey: String },
+
+    /// Failed to read the peer cache file
+    #[error("Failed to read peer cache {path}: {error}")]
+    CacheRead { path: String, error: String },
+
+    /// Failed to parse the peer cache file
+    #[error("Failed to parse peer cache {path}: {error}")]
+    CacheParse { path: String, error: String },
+
+    /// Failed to write the peer cache file
+    #[error("Failed to write peer cache {path}: {error}")]
+    CacheWrite { path: String, error: String },
 }
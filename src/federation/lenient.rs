@@ -0,0 +1,220 @@
+//! Lenient counterparts of the registry's category types, for peer data.
+//!
+//! The local registry rejects unknown fields so curator typos are caught at
+//! load time. A peer may run a newer schema that adds fields, so these read
+//! the same documents but drop what they do not know, then convert to the
+//! strict types the matcher and tools use.
+
+use crate::registry::types::{
+    Category, LinkCheck, LinkCheckStatus, LocalizedCategory, MatchingRules, Source, SourceCost,
+    SourceFormat, SourcePolicy, SourceType,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Deserialize)]
+struct PeerCategory {
+    name: String,
+    description: String,
+    query_patterns: Vec<String>,
+    #[serde(default)]
+    match_threshold: Option<f64>,
+    #[serde(default)]
+    negative_patterns: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    related: Vec<String>,
+    sources: Vec<PeerSource>,
+    #[serde(default)]
+    localized: BTreeMap<String, PeerLocalizedCategory>,
+}
+
+#[derive(Deserialize)]
+struct PeerLocalizedCategory {
+    name: String,
+    description: String,
+    query_patterns: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PeerSource {
+    rank: u8,
+    name: String,
+    url: String,
+    #[serde(rename = "type")]
+    source_type: SourceType,
+    why: String,
+    #[serde(default)]
+    last_verified: Option<NaiveDate>,
+    #[serde(default)]
+    cost: Option<SourceCost>,
+    #[serde(default)]
+    license: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    format: Option<SourceFormat>,
+    #[serde(default)]
+    archived: bool,
+    #[serde(default)]
+    last_checked: Option<PeerLinkCheck>,
+}
+
+#[derive(Deserialize)]
+struct PeerLinkCheck {
+    date: NaiveDate,
+    status: LinkCheckStatus,
+    #[serde(default)]
+    http_status: Option<u16>,
+}
+
+#[derive(Deserialize)]
+struct PeerMatchingRules {
+    #[serde(default)]
+    stemming: bool,
+    #[serde(default)]
+    synonyms: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct PeerSourcePolicy {
+    #[serde(default = "default_primary_sources")]
+    primary_sources: usize,
+    #[serde(default)]
+    max_honourable_mentions: usize,
+}
+
+fn default_primary_sources() -> usize {
+    SourcePolicy::default().primary_sources
+}
+
+impl From<PeerCategory> for Category {
+    fn from(category: PeerCategory) -> Self {
+        Category {
+            name: category.name,
+            description: category.description,
+            query_patterns: category.query_patterns,
+            match_threshold: category.match_threshold,
+            negative_patterns: category.negative_patterns,
+            tags: category.tags,
+            parent: category.parent,
+            related: category.related,
+            sources: category.sources.into_iter().map(Source::from).collect(),
+            localized: category
+                .localized
+                .into_iter()
+                .map(|(lang, localized)| {
+                    let localized = LocalizedCategory {
+                        name: localized.name,
+                        description: localized.description,
+                        query_patterns: localized.query_patterns,
+                    };
+                    (lang, localized)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<PeerSource> for Source {
+    fn from(source: PeerSource) -> Self {
+        Source {
+            rank: source.rank,
+            name: source.name,
+            url: source.url,
+            source_type: source.source_type,
+            why: source.why,
+            last_verified: source.last_verified,
+            cost: source.cost,
+            license: source.license,
+            language: source.language,
+            format: source.format,
+            archived: source.archived,
+            last_checked: source.last_checked.map(|check| LinkCheck {
+                date: check.date,
+                status: check.status,
+                http_status: check.http_status,
+            }),
+        }
+    }
+}
+
+/// Read peer categories, ignoring fields this node does not know
+pub fn categories<'de, D>(deserializer: D) -> Result<HashMap<String, Category>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let categories = HashMap::<String, PeerCategory>::deserialize(deserializer)?;
+    Ok(categories
+        .into_iter()
+        .map(|(slug, category)| (slug, Category::from(category)))
+        .collect())
+}
+
+/// Read peer matching rules, ignoring fields this node does not know
+pub fn matching<'de, D>(deserializer: D) -> Result<MatchingRules, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let rules = PeerMatchingRules::deserialize(deserializer)?;
+    Ok(MatchingRules {
+        stemming: rules.stemming,
+        synonyms: rules.synonyms,
+    })
+}
+
+/// Read a peer source policy, ignoring fields this node does not know
+pub fn source_policy<'de, D>(deserializer: D) -> Result<Option<SourcePolicy>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let policy = Option::<PeerSourcePolicy>::deserialize(deserializer)?;
+    Ok(policy.map(|policy| SourcePolicy {
+        primary_sources: policy.primary_sources,
+        max_honourable_mentions: policy.max_honourable_mentions,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::federation::PeerRegistry;
+    use serde_json::json;
+
+    #[test]
+    fn test_peer_registry_ignores_unknown_nested_fields() {
+        let mut value: serde_json::Value =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        value["schema_version"] = json!(3);
+        value["mirrors"] = json!(["https://mirror.example"]);
+        value["matching"] = json!({"stemming": true, "transliterate": true});
+        value["source_policy"] = json!({"primary_sources": 3, "max_sponsored": 1});
+        let category = &mut value["categories"]["rust-learning"];
+        category["difficulty"] = json!("beginner");
+        category["sources"][0]["rating"] = json!(4.5);
+        category["sources"][0]["last_checked"] =
+            json!({"date": "2026-10-01", "status": "ok", "checked_by": "ci"});
+
+        let registry: PeerRegistry = serde_json::from_value(value).unwrap();
+        assert_eq!(registry.schema_version, Some(3));
+        assert!(registry.matching.stemming);
+        assert_eq!(registry.source_policy.unwrap().primary_sources, 3);
+        let source = &registry.categories["rust-learning"].sources[0];
+        assert_eq!(source.url, "https://doc.rust-lang.org/book/");
+        assert!(source.last_checked.is_some());
+        assert_eq!(registry.categories.len(), 10);
+    }
+
+    #[test]
+    fn test_persisted_peer_registry_round_trips() {
+        let registry: PeerRegistry =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        let json = serde_json::to_string(&registry).unwrap();
+        let reparsed: PeerRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(reparsed.categories.len(), registry.categories.len());
+        assert_eq!(reparsed.source_policy, registry.source_policy);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::matcher::{match_query, MatchConfig, MatchIndex};
+use crate::registry::types::{Registry, Source, SourcePolicy};
+
+use super::cache::PeerCache;
+use super::types::{FederatedMatch, PeerStatus, TrustLevel};
+
+/// Match a query against the local registry and every cached peer registry.
+///
+/// The local match (if any) comes first, tagged `TrustLevel::Direct`. Peer
+/// matches follow, tagged `TrustLevel::Endorsed` and ordered by peer pubkey.
+/// Unreachable and untrusted peers and peers without a cached registry are
+/// skipped; stale peers are included with `stale: true`. Match errors (no
+/// match, empty query, a registry without categories) simply produce no
+/// entry for that registry.
+///
+/// `index` must be the MatchIndex of `registry`; peers are matched with the
+/// index built when their registry was fetched.
+pub async fn match_federated(
+    query: &str,
+    registry: &Registry,
+    index: &MatchIndex,
+    config: &MatchConfig,
+    peer_cache: &PeerCache,
+) -> Vec<FederatedMatch> {
+    let mut matches = Vec::new();
+
+    if let Ok(result) = match_query(query, index, config) {
+        let (sources, honourable_mentions) = split_sources(registry.source_policy(), &result.category.sources);
+        matches.push(FederatedMatch {
+            curator_name: registry.curator.name.clone(),
+            curator_pubkey: registry.curator.pubkey.clone(),
+            peer_name: None,
+            source_url: None,
+            trust: TrustLevel::Direct,
+            stale: false,
+            slug: result.slug,
+            category_name: result.category.name,
+            category_description: result.category.description,
+            sources,
+            honourable_mentions,
+        });
+    }
+
+    let mut peers = peer_cache.get_all_cached().await;
+    peers.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
+
+    for peer in peers {
+        if matches!(peer.status, PeerStatus::Unreachable | PeerStatus::Untrusted) {
+            continue;
+        }
+        let (Some(peer_registry), Some(peer_index)) = (&peer.registry, &peer.match_index) else {
+            continue;
+        };
+
+        if let Ok(result) = match_query(query, peer_index, config) {
+            let policy = peer_registry.source_policy.unwrap_or_default();
+            let (sources, honourable_mentions) = split_sources(policy, &result.category.sources);
+            matches.push(FederatedMatch {
+                curator_name: peer_registry.curator.name.clone(),
+                curator_pubkey: peer.pubkey.clone(),
+                peer_name: peer.name.clone(),
+                source_url: Some(peer.url.clone()),
+                trust: TrustLevel::Endorsed,
+                stale: peer.stale,
+                slug: result.slug,
+                category_name: result.category.name,
+                category_description: result.category.description,
+                sources,
+                honourable_mentions,
+            });
+        }
+    }
+
+    matches
+}
+
+/// Owned primary sources and honourable mentions, each in rank order
+fn split_sources(policy: SourcePolicy, sources: &[Source]) -> (Vec<Source>, Vec<Source>) {
+    let (primary, mentions) = policy.split(sources);
+    (
+        primary.into_iter().cloned().collect(),
+        mentions.into_iter().cloned().collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::MatchMode;
+    use crate::registry::{sign_registry, SIGNATURE_HEADER};
+    use crate::registry::types::Endorsement;
+
+    const REGISTRY_JSON: &str = include_str!("../../registry.json");
+
+    fn test_config() -> MatchConfig {
+        MatchConfig {
+            match_threshold: 0.4,
+            match_fuzzy_weight: 0.7,
+            match_keyword_weight: 0.3,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
+        }
+    }
+
+    /// Key the test peer signs its registry with
+    fn peer_keypair() -> pkarr::Keypair {
+        pkarr::Keypair::from_secret_key(&[5; 32])
+    }
+
+    fn peer_pubkey() -> String {
+        peer_keypair().public_key().to_z32()
+    }
+
+    /// Serve a registry document at /registry on a random local port, with
+    /// its curator pubkey set to `signer`'s and signed by `signer`
+    async fn spawn_peer_stub(
+        mut registry_json: serde_json::Value,
+        signer: &pkarr::Keypair,
+    ) -> String {
+        registry_json["curator"]["pubkey"] = serde_json::json!(signer.public_key().to_z32());
+        let registry: Registry = serde_json::from_value(registry_json).unwrap();
+        let signature = sign_registry(&registry, signer);
+        let body = serde_json::to_string_pretty(&registry).unwrap();
+
+        let app = axum::Router::new().route(
+            "/registry",
+            axum::routing::get(move || {
+                let body = body.clone();
+                let signature = signature.clone();
+                async move {
+                    let content_type = "application/json".to_string();
+                    ([("content-type", content_type), (SIGNATURE_HEADER, signature)], body)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_local_match_only() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let peer_cache = PeerCache::new(vec![], "local-key".to_string());
+
+        let matches = match_federated("learn rust", &registry, &index, &test_config(), &peer_cache).await;
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].trust, TrustLevel::Direct);
+        assert_eq!(matches[0].slug, "rust-learning");
+        assert!(matches[0].source_url.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_peer_skipped() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let endorsement = Endorsement {
+            pubkey: "peer-a".to_string(),
+            url: "http://127.0.0.1:1".to_string(),
+            name: None,
+            since: "2026-04-03".to_string(),
+        };
+        let peer_cache = PeerCache::new(vec![endorsement], "local-key".to_string());
+
+        let matches = match_federated("learn rust", &registry, &index, &test_config(), &peer_cache).await;
+        assert_eq!(matches.len(), 1, "Never-fetched peer should be skipped");
+    }
+
+    #[tokio::test]
+    async fn test_fetched_peer_match_is_endorsed() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let mut peer_registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        peer_registry["curator"]["name"] = serde_json::json!("Peer Curator");
+        let url = spawn_peer_stub(peer_registry, &peer_keypair()).await;
+
+        let endorsement = Endorsement {
+            pubkey: peer_pubkey(),
+            url: url.clone(),
+            name: Some("Peer A".to_string()),
+            since: "2026-04-03".to_string(),
+        };
+        let peer_cache = PeerCache::new(vec![endorsement], "local-key".to_string());
+        peer_cache.fetch_peer(&peer_pubkey()).await;
+
+        let matches = match_federated("learn rust", &registry, &index, &test_config(), &peer_cache).await;
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].trust, TrustLevel::Direct);
+        let peer_match = &matches[1];
+        assert_eq!(peer_match.trust, TrustLevel::Endorsed);
+        assert_eq!(peer_match.curator_name, "Peer Curator");
+        assert_eq!(peer_match.curator_pubkey, peer_pubkey());
+        assert_eq!(peer_match.peer_name.as_deref(), Some("Peer A"));
+        assert_eq!(peer_match.source_url.as_deref(), Some(url.as_str()));
+        assert!(!peer_match.stale);
+        assert_eq!(peer_match.slug, "rust-learning");
+    }
+
+    #[tokio::test]
+    async fn test_peer_without_categories_matches_nothing() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let mut peer_registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        peer_registry["categories"] = serde_json::json!({});
+        let url = spawn_peer_stub(peer_registry, &peer_keypair()).await;
+
+        let endorsement = Endorsement {
+            pubkey: peer_pubkey(),
+            url,
+            name: None,
+            since: "2026-04-03".to_string(),
+        };
+        let peer_cache = PeerCache::new(vec![endorsement], "local-key".to_string());
+        peer_cache.fetch_peer(&peer_pubkey()).await;
+        assert_eq!(peer_cache.get_all_cached().await[0].status, PeerStatus::Fresh);
+
+        let matches = match_federated("learn rust", &registry, &index, &test_config(), &peer_cache).await;
+
+        assert_eq!(matches.len(), 1, "Peer without categories adds no match");
+        assert_eq!(matches[0].trust, TrustLevel::Direct);
+    }
+
+    #[tokio::test]
+    async fn test_peer_honourable_mentions_split_by_peer_policy() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let mut peer_registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        peer_registry["schema_version"] = serde_json::json!(3);
+        peer_registry["source_policy"] =
+            serde_json::json!({"primary_sources": 3, "max_honourable_mentions": 2});
+        peer_registry["categories"]["rust-learning"]["sources"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::json!({
+                "rank": 4,
+                "name": "Rust by Example",
+                "url": "https://doc.rust-lang.org/rust-by-example/",
+                "type": "documentation",
+                "why": "Interactive examples covering all Rust concepts"
+            }));
+        let url = spawn_peer_stub(peer_registry, &peer_keypair()).await;
+
+        let endorsement = Endorsement {
+            pubkey: peer_pubkey(),
+            url,
+            name: None,
+            since: "2026-04-03".to_string(),
+        };
+        let peer_cache = PeerCache::new(vec![endorsement], "local-key".to_string());
+        peer_cache.fetch_peer(&peer_pubkey()).await;
+
+        let matches = match_federated("learn rust", &registry, &index, &test_config(), &peer_cache).await;
+
+        assert_eq!(matches.len(), 2);
+        assert!(matches[0].honourable_mentions.is_empty());
+        let peer_match = &matches[1];
+        assert_eq!(peer_match.sources.len(), 3);
+        assert_eq!(peer_match.honourable_mentions.len(), 1);
+        assert_eq!(peer_match.honourable_mentions[0].rank, 4);
+    }
+
+    #[tokio::test]
+    async fn test_untrusted_peer_skipped() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        // Served by a node holding a different key than the endorsed one
+        let impostor = pkarr::Keypair::from_secret_key(&[6; 32]);
+        let peer_registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let url = spawn_peer_stub(peer_registry, &impostor).await;
+
+        let endorsement = Endorsement {
+            pubkey: peer_pubkey(),
+            url,
+            name: None,
+            since: "2026-04-03".to_string(),
+        };
+        let peer_cache = PeerCache::new(vec![endorsement], "local-key".to_string());
+        peer_cache.fetch_peer(&peer_pubkey()).await;
+        assert_eq!(peer_cache.get_all_cached().await[0].status, PeerStatus::Untrusted);
+
+        let matches = match_federated("learn rust", &registry, &index, &test_config(), &peer_cache).await;
+        assert_eq!(matches.len(), 1, "Untrusted peer should be skipped");
+    }
+}
@@ -1,9 +1,17 @@
 pub mod cache;
 pub mod error;
+mod lenient;
+pub mod matching;
+pub mod persist;
+pub mod report;
 pub mod types;
 
 pub use cache::{CachedPeerSnapshot, PeerCache};
 pub use error::FederationError;
+pub use matching::match_federated;
+pub use persist::{load_peers, save_peers, PersistedPeer, PersistedPeers};
+pub use report::{peer_reports, FederationSummary, PeerReport};
 pub use types::{
     CachedPeer, FederatedMatch, PeerCurator, PeerEndorsement, PeerRegistry, PeerStatus, TrustLevel,
+    Validators,
 };
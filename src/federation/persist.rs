@@ -0,0 +1,116 @@
+use super::error::FederationError;
+use super::types::{PeerRegistry, Validators};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A peer's last good registry, as saved in the peer cache file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistedPeer {
+    /// Base URL the registry was fetched from
+    pub url: String,
+    /// When the registry was last fetched or revalidated
+    pub fetched_at: DateTime<Utc>,
+    /// Cache validators of the registry, so the first fetch after a restart
+    /// can still be conditional
+    #[serde(default)]
+    pub validators: Validators,
+    pub registry: PeerRegistry,
+}
+
+/// Contents of the peer cache file, keyed by peer pubkey
+pub type PersistedPeers = BTreeMap<String, PersistedPeer>;
+
+/// Read the peer cache file. A missing file is an empty cache, since the
+/// node may not have fetched any peer yet.
+pub async fn load_peers(path: impl AsRef<Path>) -> Result<PersistedPeers, FederationError> {
+    let path = path.as_ref();
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(PersistedPeers::new()),
+        Err(e) => {
+            return Err(FederationError::CacheRead {
+                path: path.display().to_string(),
+                error: e.to_string(),
+            });
+        }
+    };
+
+    serde_json::from_str(&contents).map_err(|e| FederationError::CacheParse {
+        path: path.display().to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// Write the peer cache file, replacing any previous one. The file is
+/// written beside the target and renamed over it, so a crash mid-write
+/// never leaves a truncated cache.
+pub async fn save_peers(
+    path: impl AsRef<Path>,
+    peers: &PersistedPeers,
+) -> Result<(), FederationError> {
+    let path = path.as_ref();
+    let write_error = |e: std::io::Error| FederationError::CacheWrite {
+        path: path.display().to_string(),
+        error: e.to_string(),
+    };
+    let json = serde_json::to_string_pretty(peers).expect("PersistedPeers always serializes");
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    tokio::fs::write(&temp, json + "\n")
+        .await
+        .map_err(write_error)?;
+    tokio::fs::rename(&temp, path).await.map_err(write_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempPath;
+    use chrono::TimeZone;
+
+    #[tokio::test]
+    async fn test_peers_round_trip() {
+        let path = TempPath::new("peer-cache.json");
+        assert!(
+            load_peers(&path).await.unwrap().is_empty(),
+            "missing file is empty"
+        );
+
+        let registry: PeerRegistry =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        let peer = PersistedPeer {
+            url: "https://peer.example".to_string(),
+            fetched_at: Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap(),
+            validators: Validators {
+                etag: Some("\"abc\"".to_string()),
+                last_modified: None,
+            },
+            registry,
+        };
+        let peers = PersistedPeers::from([("peer-key".to_string(), peer)]);
+        save_peers(&path, &peers).await.unwrap();
+
+        let loaded = load_peers(&path).await.unwrap();
+        let loaded = &loaded["peer-key"];
+        assert_eq!(loaded.url, "https://peer.example");
+        assert_eq!(loaded.fetched_at, peers["peer-key"].fetched_at);
+        assert_eq!(loaded.validators, peers["peer-key"].validators);
+        assert_eq!(loaded.registry.categories.len(), 10);
+        assert_eq!(
+            loaded.registry.curator.pubkey,
+            peers["peer-key"].registry.curator.pubkey
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_cache_file_rejected() {
+        let path = TempPath::new("peer-cache.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(
+            load_peers(&path).await,
+            Err(FederationError::CacheParse { .. })
+        ));
+    }
+}
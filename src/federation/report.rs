@@ -0,0 +1,164 @@
+use super::cache::CachedPeerSnapshot;
+use super::types::PeerStatus;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Health of one endorsed peer, as served at /peers and by get_peer_status
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerReport {
+    pub pubkey: String,
+    /// Base URL of the peer node
+    pub url: String,
+    /// Endorsement display name
+    pub name: Option<String>,
+    pub status: PeerStatus,
+    /// Wall-clock time of the last successful fetch
+    pub last_success: Option<DateTime<Utc>>,
+    /// Whole seconds since the last successful fetch
+    pub seconds_since_success: Option<u64>,
+    /// Why the last fetch failed, if it did
+    pub last_error: Option<String>,
+    /// Failed fetches since the last success
+    pub consecutive_failures: u32,
+    /// Version of the cached registry
+    pub registry_version: Option<String>,
+    /// Categories in the cached registry
+    pub category_count: Option<usize>,
+}
+
+impl PeerReport {
+    /// Report on a cached peer as of `now`
+    pub fn new(peer: &CachedPeerSnapshot, now: DateTime<Utc>) -> Self {
+        Self {
+            pubkey: peer.pubkey.clone(),
+            url: peer.url.clone(),
+            name: peer.name.clone(),
+            status: peer.status.clone(),
+            last_success: peer.last_success,
+            seconds_since_success: peer
+                .last_success
+                .map(|t| (now - t).num_seconds().max(0) as u64),
+            last_error: peer.last_error.clone(),
+            consecutive_failures: peer.consecutive_failures,
+            registry_version: peer.registry.as_ref().map(|r| r.version.clone()),
+            category_count: peer.registry.as_ref().map(|r| r.categories.len()),
+        }
+    }
+}
+
+/// Count of endorsed peers by status, shown in /health
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FederationSummary {
+    pub peers: usize,
+    pub fresh: usize,
+    pub stale: usize,
+    pub unreachable: usize,
+    pub untrusted: usize,
+}
+
+impl FederationSummary {
+    pub fn new(reports: &[PeerReport]) -> Self {
+        let mut summary = FederationSummary {
+            peers: reports.len(),
+            ..FederationSummary::default()
+        };
+        for report in reports {
+            match report.status {
+                PeerStatus::Fresh => summary.fresh += 1,
+                PeerStatus::Stale => summary.stale += 1,
+                PeerStatus::Unreachable => summary.unreachable += 1,
+                PeerStatus::Untrusted => summary.untrusted += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// Report on every cached peer as of `now`, sorted by URL then pubkey
+pub fn peer_reports(peers: &[CachedPeerSnapshot], now: DateTime<Utc>) -> Vec<PeerReport> {
+    let mut reports: Vec<PeerReport> = peers
+        .iter()
+        .map(|peer| PeerReport::new(peer, now))
+        .collect();
+    reports.sort_by(|a, b| a.url.cmp(&b.url).then(a.pubkey.cmp(&b.pubkey)));
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::federation::PeerRegistry;
+    use chrono::TimeZone;
+
+    fn snapshot(pubkey: &str, status: PeerStatus) -> CachedPeerSnapshot {
+        CachedPeerSnapshot {
+            pubkey: pubkey.to_string(),
+            url: format!("https://{}.example", pubkey),
+            name: None,
+            registry: None,
+            match_index: None,
+            stale: status == PeerStatus::Stale,
+            status,
+            last_success: None,
+            last_error: None,
+            consecutive_failures: 0,
+            next_attempt: None,
+        }
+    }
+
+    #[test]
+    fn test_peer_report_fields() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+        let registry: PeerRegistry =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        let mut peer = snapshot("alice", PeerStatus::Stale);
+        peer.name = Some("Alice".to_string());
+        peer.last_success = Some(now - chrono::TimeDelta::minutes(90));
+        peer.last_error = Some("request failed: connection refused".to_string());
+        peer.consecutive_failures = 2;
+        peer.registry = Some(registry.clone());
+
+        let report = PeerReport::new(&peer, now);
+        assert_eq!(report.seconds_since_success, Some(5400));
+        assert_eq!(
+            report.registry_version.as_deref(),
+            Some(registry.version.as_str())
+        );
+        assert_eq!(report.category_count, Some(10));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "stale");
+        assert_eq!(json["name"], "Alice");
+        assert_eq!(json["last_success"], "2026-10-01T10:30:00Z");
+        assert_eq!(json["last_error"], "request failed: connection refused");
+
+        let never = PeerReport::new(&snapshot("bob", PeerStatus::Unreachable), now);
+        assert_eq!(never.seconds_since_success, None);
+        assert_eq!(never.category_count, None);
+    }
+
+    #[test]
+    fn test_reports_sorted_and_summarized() {
+        let now = Utc::now();
+        let peers = vec![
+            snapshot("carol", PeerStatus::Untrusted),
+            snapshot("alice", PeerStatus::Fresh),
+            snapshot("dave", PeerStatus::Unreachable),
+            snapshot("bob", PeerStatus::Fresh),
+        ];
+        let reports = peer_reports(&peers, now);
+        let order: Vec<&str> = reports.iter().map(|r| r.pubkey.as_str()).collect();
+        assert_eq!(order, ["alice", "bob", "carol", "dave"]);
+
+        assert_eq!(
+            FederationSummary::new(&reports),
+            FederationSummary {
+                peers: 4,
+                fresh: 2,
+                stale: 0,
+                unreachable: 1,
+                untrusted: 1,
+            }
+        );
+    }
+}
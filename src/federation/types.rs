@@ -1,30 +1,43 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
-use crate::registry::types::{Category, Source};
+use super::lenient;
+use crate::matcher::MatchIndex;
+use crate::registry::types::{Category, MatchingRules, Source, SourcePolicy};
 
 /// Lax peer registry for forward-compatible federation deserialization.
 /// No deny_unknown_fields — newer peers may add fields older nodes don't know about.
-#[derive(Debug, Clone, Deserialize)]
+/// Categories, matching rules and the source policy are read through the
+/// lenient types in `lenient`, so unknown fields nested in them are dropped too.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerRegistry {
+    /// Peers may run a newer schema; unknown versions are read as best we can
+    #[serde(default)]
+    pub schema_version: Option<u32>,
     pub version: String,
     pub updated: String,
     pub curator: PeerCurator,
     #[serde(default)]
     pub endorsements: Vec<PeerEndorsement>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "lenient::categories")]
     pub categories: HashMap<String, Category>,
+    #[serde(default, deserialize_with = "lenient::matching")]
+    pub matching: MatchingRules,
+    #[serde(default, deserialize_with = "lenient::source_policy")]
+    pub source_policy: Option<SourcePolicy>,
 }
 
 /// Lax curator type for peer data
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerCurator {
     pub name: String,
     pub pubkey: String,
 }
 
 /// Lax endorsement type for peer data (separate from local Endorsement per D-05)
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PeerEndorsement {
     pub pubkey: String,
     pub url: String,
@@ -37,13 +50,19 @@ pub struct PeerEndorsement {
 pub struct FederatedMatch {
     pub curator_name: String,
     pub curator_pubkey: String,
-    pub source_url: String,
+    /// Endorsement display name of the peer (None for the local registry)
+    pub peer_name: Option<String>,
+    /// Base URL of the peer node (None for the local registry)
+    pub source_url: Option<String>,
     pub trust: TrustLevel,
     pub stale: bool,
     pub slug: String,
     pub category_name: String,
     pub category_description: String,
+    /// Primary sources in rank order
     pub sources: Vec<Source>,
+    /// Honourable mentions in rank order, per the registry's source policy
+    pub honourable_mentions: Vec<Source>,
 }
 
 /// Trust level for federated results
@@ -56,7 +75,8 @@ pub enum TrustLevel {
 }
 
 /// Status of a cached peer
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PeerStatus {
     /// Successfully fetched within the last hour
     Fresh,
@@ -64,6 +84,9 @@ pub enum PeerStatus {
     Stale,
     /// Never successfully fetched or repeated failures
     Unreachable,
+    /// Last registry served failed signature or pubkey verification.
+    /// Excluded from federated results until a registry verifies again.
+    Untrusted,
 }
 
 /// A cached peer entry in the peer cache
@@ -74,7 +97,28 @@ pub struct CachedPeer {
     pub name: Option<String>,
     pub since: String,
     pub registry: Option<PeerRegistry>,
-    pub last_success: Option<Instant>,
+    /// Matching index for `registry`, built when the registry is fetched
+    pub match_index: Option<Arc<MatchIndex>>,
+    /// Wall-clock time of the last fetch that succeeded, so staleness
+    /// survives a restart when the registry is restored from disk
+    pub last_success: Option<DateTime<Utc>>,
     pub last_attempt: Option<Instant>,
     pub status: PeerStatus,
+    /// Failed fetches since the last success; drives the backoff
+    pub consecutive_failures: u32,
+    /// Earliest time a refresh will fetch the peer again, while backing off
+    pub next_attempt: Option<Instant>,
+    /// Cache validators of `registry`, sent to make the next fetch conditional
+    pub validators: Validators,
+    /// Why the last fetch failed; cleared by a successful one
+    pub last_error: Option<String>,
+}
+
+/// HTTP cache validators from a peer's /registry response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Validators {
+    /// ETag header, sent back as If-None-Match
+    pub etag: Option<String>,
+    /// Last-Modified header, sent back as If-Modified-Since
+    pub last_modified: Option<String>,
 }
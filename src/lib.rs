@@ -1,12 +1,19 @@
+pub mod analytics;
 pub mod audit;
 pub mod config;
 pub mod contributions;
 pub mod error;
+pub mod eval;
 pub mod federation;
 pub mod fork;
 pub mod identity;
+pub mod linkcheck;
 pub mod matcher;
 pub mod mcp;
 pub mod pubky;
 pub mod registry;
+pub mod reload;
 pub mod server;
+pub mod stdio;
+#[cfg(test)]
+mod test_support;
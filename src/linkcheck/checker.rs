@@ -0,0 +1,298 @@
+use super::error::LinkCheckError;
+use super::types::{LinkOutcome, LinkResult, Redirect, SourceRef, MAX_REDIRECTS};
+use crate::registry::Registry;
+use reqwest::header::LOCATION;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// How URLs are fetched
+#[derive(Debug, Clone, Copy)]
+pub struct CheckOptions {
+    /// URLs fetched at once
+    pub concurrency: usize,
+    /// Time allowed for each request, redirects counted separately
+    pub timeout: Duration,
+}
+
+/// Every distinct source URL in the registry with the sources linking to it
+pub fn collect_urls(registry: &Registry) -> BTreeMap<String, Vec<SourceRef>> {
+    let mut urls: BTreeMap<String, Vec<SourceRef>> = BTreeMap::new();
+    for (slug, category) in &registry.categories {
+        for source in &category.sources {
+            urls.entry(source.url.clone()).or_default().push(SourceRef {
+                category: slug.clone(),
+                rank: source.rank,
+                name: source.name.clone(),
+            });
+        }
+    }
+    for sources in urls.values_mut() {
+        sources.sort_by(|a, b| a.category.cmp(&b.category).then(a.rank.cmp(&b.rank)));
+    }
+    urls
+}
+
+/// Check every URL, at most `options.concurrency` at a time.
+/// Results are sorted by URL.
+pub async fn check_urls(
+    urls: BTreeMap<String, Vec<SourceRef>>,
+    options: CheckOptions,
+) -> Result<Vec<LinkResult>, LinkCheckError> {
+    // Redirects are followed by hand so each hop can be reported
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(options.timeout)
+        .user_agent(concat!("three-good-sources-link-checker/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| LinkCheckError::Client {
+            error: e.to_string(),
+        })?;
+
+    let permits = Arc::new(Semaphore::new(options.concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+    for (url, sources) in urls {
+        let client = client.clone();
+        let permits = Arc::clone(&permits);
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await.expect("semaphore is never closed");
+            check_url(&client, url, sources).await
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => tracing::error!(error = %e, "Link check task failed"),
+        }
+    }
+    results.sort_by(|a, b| a.url.cmp(&b.url));
+    Ok(results)
+}
+
+/// Fetch one URL, following up to MAX_REDIRECTS redirects
+async fn check_url(client: &reqwest::Client, url: String, sources: Vec<SourceRef>) -> LinkResult {
+    let mut redirects = Vec::new();
+    let mut current = url.clone();
+
+    let outcome = loop {
+        let response = match client.get(&current).send().await {
+            Ok(response) => response,
+            Err(e) => break classify_error(&e),
+        };
+        let status = response.status();
+
+        // A 3xx without a usable Location is the final answer
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| response.url().join(value).ok());
+        let Some(location) = location.filter(|_| status.is_redirection()) else {
+            break LinkOutcome::Response {
+                status: status.as_u16(),
+            };
+        };
+
+        if redirects.len() == MAX_REDIRECTS {
+            break LinkOutcome::TooManyRedirects;
+        }
+        current = location.to_string();
+        redirects.push(Redirect {
+            status: status.as_u16(),
+            location: current.clone(),
+        });
+    };
+
+    LinkResult {
+        url,
+        sources,
+        redirects,
+        outcome,
+    }
+}
+
+/// Sort a request error into timeout, TLS or connection failure.
+/// reqwest does not expose TLS errors as a kind, so the error chain's
+/// messages are searched for TLS terms instead.
+fn classify_error(error: &reqwest::Error) -> LinkOutcome {
+    if error.is_timeout() {
+        return LinkOutcome::Timeout;
+    }
+
+    let mut messages = vec![error.to_string()];
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        messages.push(cause.to_string());
+        source = cause.source();
+    }
+    let message = messages.join(": ");
+
+    let lower = message.to_lowercase();
+    if ["tls", "ssl", "certificate", "handshake"]
+        .iter()
+        .any(|term| lower.contains(term))
+    {
+        LinkOutcome::Tls { error: message }
+    } else {
+        LinkOutcome::Connection { error: message }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::types::LinkCheckStatus;
+    use axum::http::{header, StatusCode};
+    use axum::routing::get;
+
+    const REGISTRY_JSON: &str = include_str!("../../registry.json");
+
+    /// Serve canned responses on a random local port
+    async fn spawn_stub() -> String {
+        let app = axum::Router::new()
+            .route("/ok", get(|| async { "fine" }))
+            .route("/missing", get(|| async { StatusCode::NOT_FOUND }))
+            .route("/error", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+            .route(
+                "/moved",
+                get(|| async { (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, "/hop")]) }),
+            )
+            .route(
+                "/hop",
+                get(|| async { (StatusCode::FOUND, [(header::LOCATION, "/ok")]) }),
+            )
+            .route(
+                "/loop",
+                get(|| async { (StatusCode::FOUND, [(header::LOCATION, "/loop")]) }),
+            )
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "late"
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    fn options() -> CheckOptions {
+        CheckOptions {
+            concurrency: 2,
+            timeout: Duration::from_millis(500),
+        }
+    }
+
+    async fn check(url: String) -> LinkResult {
+        let urls = BTreeMap::from([(url, vec![])]);
+        check_urls(urls, options()).await.unwrap().remove(0)
+    }
+
+    #[tokio::test]
+    async fn test_ok_and_http_errors() {
+        let base = spawn_stub().await;
+
+        let result = check(format!("{}/ok", base)).await;
+        assert_eq!(result.outcome, LinkOutcome::Response { status: 200 });
+        assert_eq!(result.status(), LinkCheckStatus::Ok);
+
+        let result = check(format!("{}/missing", base)).await;
+        assert_eq!(result.outcome, LinkOutcome::Response { status: 404 });
+        assert_eq!(result.status(), LinkCheckStatus::Broken);
+
+        let result = check(format!("{}/error", base)).await;
+        assert_eq!(result.status(), LinkCheckStatus::Broken);
+        assert_eq!(result.http_status(), Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_redirects_are_tracked() {
+        let base = spawn_stub().await;
+
+        let result = check(format!("{}/moved", base)).await;
+        assert_eq!(result.status(), LinkCheckStatus::Redirected);
+        assert_eq!(
+            result.redirects,
+            vec![
+                Redirect {
+                    status: 301,
+                    location: format!("{}/hop", base),
+                },
+                Redirect {
+                    status: 302,
+                    location: format!("{}/ok", base),
+                },
+            ]
+        );
+        assert_eq!(result.final_url(), Some(format!("{}/ok", base).as_str()));
+
+        let result = check(format!("{}/loop", base)).await;
+        assert_eq!(result.outcome, LinkOutcome::TooManyRedirects);
+        assert_eq!(result.redirects.len(), MAX_REDIRECTS);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_connection_failures() {
+        let base = spawn_stub().await;
+
+        let result = check(format!("{}/slow", base)).await;
+        assert_eq!(result.outcome, LinkOutcome::Timeout);
+        assert_eq!(result.status(), LinkCheckStatus::Unreachable);
+
+        let result = check("http://127.0.0.1:1/".to_string()).await;
+        assert!(matches!(result.outcome, LinkOutcome::Connection { .. }));
+
+        // The stub speaks plain HTTP, so the TLS handshake fails
+        let result = check(base.replace("http://", "https://") + "/ok").await;
+        assert!(
+            matches!(result.outcome, LinkOutcome::Tls { .. }),
+            "expected TLS failure, got {:?}",
+            result.outcome
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checks_run_concurrently_and_sorted() {
+        let base = spawn_stub().await;
+        let urls: BTreeMap<String, Vec<SourceRef>> = ["d", "c", "b", "a"]
+            .iter()
+            .map(|query| (format!("{}/slow?{}", base, query), vec![]))
+            .collect();
+        let options = CheckOptions {
+            concurrency: 4,
+            ..options()
+        };
+
+        // Four 500ms timeouts would take 2s one after another
+        let started = std::time::Instant::now();
+        let results = check_urls(urls, options).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(1500));
+
+        let checked: Vec<&str> = results.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            checked,
+            ["a", "b", "c", "d"].map(|query| format!("{}/slow?{}", base, query))
+        );
+        assert!(results.iter().all(|r| r.outcome == LinkOutcome::Timeout));
+    }
+
+    #[test]
+    fn test_collect_urls() {
+        let registry: Registry = serde_json::from_str(REGISTRY_JSON).unwrap();
+        let urls = collect_urls(&registry);
+
+        let total: usize = urls.values().map(Vec::len).sum();
+        assert_eq!(total, 30, "every source is listed under its URL");
+        let rust_book = &urls["https://doc.rust-lang.org/book/"];
+        assert_eq!(rust_book[0].category, "rust-learning");
+        assert_eq!(rust_book[0].rank, 1);
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+/// Link checker errors
+#[derive(Debug, Error)]
+pub enum LinkCheckError {
+    /// Failed to build the HTTP client
+    #[error("Failed to build HTTP client: {error}")]
+    Client { error: String },
+
+    /// Failed to read the link status file
+    #[error("Failed to read link status file at {path}: {error}")]
+    FileRead { path: String, error: String },
+
+    /// Link status file is not valid JSON
+    #[error("Failed to parse link status file at {path}: {error}")]
+    JsonParse { path: String, error: String },
+
+    /// Failed to write the report or the link status file
+    #[error("Failed to write {path}: {error}")]
+    FileWrite { path: String, error: String },
+}
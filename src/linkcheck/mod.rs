@@ -0,0 +1,11 @@
+pub mod checker;
+pub mod error;
+pub mod report;
+pub mod status;
+pub mod types;
+
+pub use checker::{check_urls, collect_urls, CheckOptions};
+pub use error::LinkCheckError;
+pub use report::{format_report, run};
+pub use status::{apply_status, load_status, save_status};
+pub use types::{LinkOutcome, LinkReport, LinkResult, LinkStatus, LinkSummary, Redirect, SourceRef};
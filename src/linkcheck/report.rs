@@ -0,0 +1,286 @@
+use super::checker::{check_urls, collect_urls, CheckOptions};
+use super::error::LinkCheckError;
+use super::status::save_status;
+use super::types::{
+    LinkOutcome, LinkReport, LinkResult, DEFAULT_CONCURRENCY, DEFAULT_TIMEOUT_SECS,
+};
+use crate::registry::types::LinkCheckStatus;
+use std::fmt::Write;
+use std::time::Duration;
+
+const USAGE: &str = "\
+Usage: three-good-sources check-links [--registry <path>] [--output <path>] [--stamp <path>]
+                                      [--concurrency <n>] [--timeout <secs>] [--json]
+
+Fetch every source URL in the registry and report broken links and redirects.
+
+  --registry <path>   Registry to check (default: $REGISTRY_PATH)
+  --output <path>     Where to write the JSON report (default: link_report.json)
+  --stamp <path>      Also write each URL's status to this link status file;
+                      point LINK_STATUS_PATH at it to show it in /registry
+  --concurrency <n>   URLs fetched at once (default 8)
+  --timeout <secs>    Seconds to wait for each response (default 10)
+  --json              Print the report as JSON
+
+Exits with an error when any URL is broken or unreachable.";
+
+/// Default report path
+const DEFAULT_OUTPUT: &str = "link_report.json";
+
+/// One line naming the sources behind a URL, e.g. "rust-learning #1"
+fn describe_sources(result: &LinkResult) -> String {
+    result
+        .sources
+        .iter()
+        .map(|source| format!("{} #{}", source.category, source.rank))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Render a report as plain text for curators: the summary, then every
+/// URL that was not plainly OK
+pub fn format_report(report: &LinkReport) -> String {
+    let summary = &report.summary;
+    let mut out = String::new();
+    writeln!(
+        out,
+        "Checked {} URLs: {} ok, {} redirected, {} broken, {} unreachable",
+        summary.checked, summary.ok, summary.redirected, summary.broken, summary.unreachable
+    )
+    .unwrap();
+
+    let sections = [
+        (LinkCheckStatus::Broken, "Broken"),
+        (LinkCheckStatus::Unreachable, "Unreachable"),
+        (LinkCheckStatus::Redirected, "Redirected"),
+    ];
+    for (status, heading) in sections {
+        let results: Vec<&LinkResult> = report
+            .results
+            .iter()
+            .filter(|result| result.status() == status)
+            .collect();
+        if results.is_empty() {
+            continue;
+        }
+
+        writeln!(out, "\n{}:", heading).unwrap();
+        for result in results {
+            let detail = match (&result.outcome, result.final_url()) {
+                (_, Some(final_url)) if status == LinkCheckStatus::Redirected => {
+                    format!("-> {}", final_url)
+                }
+                (LinkOutcome::Response { status }, _) => format!("HTTP {}", status),
+                (LinkOutcome::Timeout, _) => "timed out".to_string(),
+                (LinkOutcome::TooManyRedirects, _) => "too many redirects".to_string(),
+                (LinkOutcome::Tls { error }, _) => format!("TLS error: {}", error),
+                (LinkOutcome::Connection { error }, _) => error.clone(),
+            };
+            writeln!(out, "  {} {} ({})", result.url, detail, describe_sources(result)).unwrap();
+        }
+    }
+
+    out
+}
+
+/// Write the JSON report
+async fn write_report(path: &str, report: &LinkReport) -> Result<(), LinkCheckError> {
+    let json = serde_json::to_string_pretty(report).expect("LinkReport always serializes");
+    tokio::fs::write(path, json + "\n")
+        .await
+        .map_err(|e| LinkCheckError::FileWrite {
+            path: path.to_string(),
+            error: e.to_string(),
+        })
+}
+
+/// Entry point for the `check-links` subcommand
+pub async fn run(args: Vec<String>) -> Result<(), String> {
+    let mut registry_path: Option<String> = None;
+    let mut output = DEFAULT_OUTPUT.to_string();
+    let mut stamp: Option<String> = None;
+    let mut concurrency = DEFAULT_CONCURRENCY;
+    let mut timeout_secs = DEFAULT_TIMEOUT_SECS;
+    let mut json = false;
+
+    let mut i = 2usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--registry" | "--output" | "--stamp" | "--concurrency" | "--timeout" => {
+                let flag = args[i].clone();
+                i += 1;
+                let Some(value) = args.get(i) else {
+                    return Err(format!("{} requires a value\n\n{}", flag, USAGE));
+                };
+                match flag.as_str() {
+                    "--registry" => registry_path = Some(value.clone()),
+                    "--output" => output = value.clone(),
+                    "--stamp" => stamp = Some(value.clone()),
+                    "--concurrency" => {
+                        concurrency = value.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                            format!("--concurrency must be a positive integer\n\n{}", USAGE)
+                        })?;
+                    }
+                    _ => {
+                        timeout_secs = value.parse().ok().filter(|n| *n > 0).ok_or_else(|| {
+                            format!("--timeout must be a positive number of seconds\n\n{}", USAGE)
+                        })?;
+                    }
+                }
+            }
+            "--json" => json = true,
+            unknown => {
+                return Err(format!("Unknown flag: {}\n\n{}", unknown, USAGE));
+            }
+        }
+        i += 1;
+    }
+
+    dotenvy::dotenv().ok();
+    let registry_path = registry_path
+        .or_else(|| std::env::var("REGISTRY_PATH").ok())
+        .ok_or_else(|| {
+            format!("--registry is required when REGISTRY_PATH is not set\n\n{}", USAGE)
+        })?;
+    let registry = crate::registry::load(&registry_path)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let options = CheckOptions {
+        concurrency,
+        timeout: Duration::from_secs(timeout_secs),
+    };
+    let results = check_urls(collect_urls(&registry), options)
+        .await
+        .map_err(|e| e.to_string())?;
+    let report = LinkReport::new(results, chrono::Utc::now());
+
+    write_report(&output, &report).await.map_err(|e| e.to_string())?;
+    if let Some(stamp) = &stamp {
+        save_status(stamp, &report.link_status())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("LinkReport always serializes")
+        );
+    } else {
+        print!("{}", format_report(&report));
+        println!("\nReport written to {}", output);
+    }
+
+    let failed = report.summary.broken + report.summary.unreachable;
+    if failed > 0 {
+        return Err(format!(
+            "{} of {} URLs are broken or unreachable",
+            failed, report.summary.checked
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linkcheck::load_status;
+    use crate::test_support::TempPath;
+    use axum::http::{header, StatusCode};
+    use axum::routing::get;
+
+    fn make_args(flags: &[&str]) -> Vec<String> {
+        ["three-good-sources", "check-links"]
+            .iter()
+            .chain(flags)
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    /// Registry whose rust-learning sources point at a local stub: one OK,
+    /// one redirected, one missing. Every other category is dropped.
+    async fn stub_registry() -> TempPath {
+        let app = axum::Router::new()
+            .route("/book", get(|| async { "book" }))
+            .route(
+                "/old-exercises",
+                get(|| async {
+                    (StatusCode::MOVED_PERMANENTLY, [(header::LOCATION, "/exercises")])
+                }),
+            )
+            .route("/exercises", get(|| async { "exercises" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let mut registry: serde_json::Value =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        let rust_learning = registry["categories"]["rust-learning"].clone();
+        registry["categories"] = serde_json::json!({"rust-learning": rust_learning});
+        let category = &mut registry["categories"]["rust-learning"];
+        category["related"] = serde_json::json!([]);
+        for (source, path) in ["book", "old-exercises", "gone"].iter().enumerate() {
+            let url = format!("http://{}/{}", addr, path);
+            category["sources"][source]["url"] = serde_json::json!(url);
+        }
+
+        let path = TempPath::new("registry.json");
+        std::fs::write(&path, registry.to_string()).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_run_arg_errors() {
+        let result = run(make_args(&["--bogus"])).await;
+        assert!(result.unwrap_err().contains("Unknown flag: --bogus"));
+
+        let result = run(make_args(&["--concurrency", "0"])).await;
+        assert!(result.unwrap_err().contains("--concurrency must be"));
+
+        let result = run(make_args(&["--timeout", "soon"])).await;
+        assert!(result.unwrap_err().contains("--timeout must be"));
+
+        let result = run(make_args(&["--stamp"])).await;
+        assert!(result.unwrap_err().contains("--stamp requires a value"));
+    }
+
+    #[tokio::test]
+    async fn test_run_writes_report_and_stamps_status() {
+        let registry = stub_registry().await;
+        let output = TempPath::new("report.json");
+        let stamp = TempPath::new("status.json");
+
+        let result = run(make_args(&[
+            "--registry",
+            &registry.display().to_string(),
+            "--output",
+            &output.display().to_string(),
+            "--stamp",
+            &stamp.display().to_string(),
+            "--timeout",
+            "5",
+        ]))
+        .await;
+        assert_eq!(result.unwrap_err(), "1 of 3 URLs are broken or unreachable");
+
+        let report: LinkReport =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(report.summary.ok, 1);
+        assert_eq!(report.summary.redirected, 1);
+        assert_eq!(report.summary.broken, 1);
+        let text = format_report(&report);
+        assert!(text.contains("Checked 3 URLs: 1 ok, 1 redirected, 1 broken, 0 unreachable"));
+        assert!(text.contains("/gone HTTP 404 (rust-learning #3)"));
+        assert!(text.contains("/old-exercises -> http://"));
+
+        let status = load_status(&stamp).await.unwrap();
+        let statuses: Vec<LinkCheckStatus> = status.values().map(|check| check.status).collect();
+        assert_eq!(
+            statuses,
+            vec![LinkCheckStatus::Ok, LinkCheckStatus::Broken, LinkCheckStatus::Redirected]
+        );
+    }
+}
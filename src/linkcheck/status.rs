@@ -0,0 +1,105 @@
+use super::error::LinkCheckError;
+use super::types::LinkStatus;
+use crate::registry::Registry;
+use std::path::Path;
+
+/// Read a link status file. A missing file is an empty status, since
+/// `check-links --stamp` may not have run yet.
+pub async fn load_status(path: impl AsRef<Path>) -> Result<LinkStatus, LinkCheckError> {
+    let path = path.as_ref();
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(LinkStatus::new()),
+        Err(e) => {
+            return Err(LinkCheckError::FileRead {
+                path: path.display().to_string(),
+                error: e.to_string(),
+            });
+        }
+    };
+
+    serde_json::from_str(&contents).map_err(|e| LinkCheckError::JsonParse {
+        path: path.display().to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// Write a link status file, replacing any previous one
+pub async fn save_status(path: impl AsRef<Path>, status: &LinkStatus) -> Result<(), LinkCheckError> {
+    let path = path.as_ref();
+    let json = serde_json::to_string_pretty(status).expect("LinkStatus always serializes");
+    tokio::fs::write(path, json + "\n")
+        .await
+        .map_err(|e| LinkCheckError::FileWrite {
+            path: path.display().to_string(),
+            error: e.to_string(),
+        })
+}
+
+/// Set each source's `last_checked` from the status of its URL. Sources
+/// whose URL was not checked keep the result they already had, so a partial
+/// check run does not wipe earlier ones.
+pub fn apply_status(registry: &mut Registry, status: &LinkStatus) {
+    for category in registry.categories.values_mut() {
+        for source in &mut category.sources {
+            if let Some(check) = status.get(&source.url) {
+                source.last_checked = Some(check.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::types::{LinkCheck, LinkCheckStatus};
+    use crate::test_support::TempPath;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn test_status_round_trip_and_apply() {
+        let path = TempPath::new("link-status.json");
+        assert!(load_status(&path).await.unwrap().is_empty(), "missing file is empty");
+
+        let check = LinkCheck {
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            status: LinkCheckStatus::Broken,
+            http_status: Some(404),
+        };
+        let url = "https://doc.rust-lang.org/book/".to_string();
+        let status = LinkStatus::from([(url, check.clone())]);
+        save_status(&path, &status).await.unwrap();
+        assert_eq!(load_status(&path).await.unwrap(), status);
+
+        let mut registry: Registry =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        let earlier = LinkCheck {
+            date: NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+            status: LinkCheckStatus::Ok,
+            http_status: Some(200),
+        };
+        let sources = &mut registry.categories.get_mut("rust-learning").unwrap().sources;
+        sources[1].last_checked = Some(earlier.clone());
+        apply_status(&mut registry, &status);
+        let sources = &registry.categories["rust-learning"].sources;
+        assert_eq!(sources[0].last_checked, Some(check));
+        assert_eq!(sources[1].last_checked, Some(earlier), "unchecked URL keeps its result");
+        assert_eq!(sources[2].last_checked, None);
+
+        let json = serde_json::to_value(&sources[0]).unwrap();
+        assert_eq!(
+            json["last_checked"],
+            serde_json::json!({"date": "2026-10-01", "status": "broken", "http_status": 404})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_status_file_rejected() {
+        let path = TempPath::new("link-status.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(matches!(
+            load_status(&path).await,
+            Err(LinkCheckError::JsonParse { .. })
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::registry::types::{LinkCheck, LinkCheckStatus};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// URLs fetched at once (default)
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Seconds to wait for each response (default)
+pub const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Redirects followed before a URL counts as unreachable
+pub const MAX_REDIRECTS: usize = 10;
+
+/// Latest check of each source URL, keyed by URL. Written by
+/// `check-links --stamp`, read by the server from LINK_STATUS_PATH.
+pub type LinkStatus = BTreeMap<String, LinkCheck>;
+
+/// A registry source pointing at a checked URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceRef {
+    /// Category slug
+    pub category: String,
+    /// Source rank within the category
+    pub rank: u8,
+    /// Source display name
+    pub name: String,
+}
+
+/// One redirect followed while checking a URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Redirect {
+    /// Redirect status code (301, 302, 307, 308, ...)
+    pub status: u16,
+    /// Absolute URL the redirect pointed to
+    pub location: String,
+}
+
+/// What happened when the last URL in the redirect chain was requested
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum LinkOutcome {
+    /// A response arrived with this status
+    Response { status: u16 },
+    /// No response within the timeout
+    Timeout,
+    /// TLS handshake or certificate failure
+    Tls { error: String },
+    /// DNS, connection or protocol failure
+    Connection { error: String },
+    /// More than MAX_REDIRECTS redirects
+    TooManyRedirects,
+}
+
+/// Result of checking one URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkResult {
+    /// URL as written in the registry
+    pub url: String,
+    /// Sources that link to it
+    pub sources: Vec<SourceRef>,
+    /// Redirects followed, in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<Redirect>,
+    #[serde(flatten)]
+    pub outcome: LinkOutcome,
+}
+
+impl LinkResult {
+    /// Summary status: 2xx and 3xx responses are fine, 4xx and 5xx broken
+    pub fn status(&self) -> LinkCheckStatus {
+        match self.outcome {
+            LinkOutcome::Response { status } if status < 400 => {
+                if self.redirects.is_empty() {
+                    LinkCheckStatus::Ok
+                } else {
+                    LinkCheckStatus::Redirected
+                }
+            }
+            LinkOutcome::Response { .. } => LinkCheckStatus::Broken,
+            _ => LinkCheckStatus::Unreachable,
+        }
+    }
+
+    /// URL the redirects ended at, if any were followed
+    pub fn final_url(&self) -> Option<&str> {
+        self.redirects.last().map(|redirect| redirect.location.as_str())
+    }
+
+    /// Status code of the final response, if one arrived
+    pub fn http_status(&self) -> Option<u16> {
+        match self.outcome {
+            LinkOutcome::Response { status } => Some(status),
+            _ => None,
+        }
+    }
+}
+
+/// Count of checked URLs by status
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkSummary {
+    pub checked: usize,
+    pub ok: usize,
+    pub redirected: usize,
+    pub broken: usize,
+    pub unreachable: usize,
+}
+
+/// Machine-readable output of `check-links`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkReport {
+    /// When the check finished
+    pub checked_at: DateTime<Utc>,
+    pub summary: LinkSummary,
+    /// One result per distinct URL, sorted by URL
+    pub results: Vec<LinkResult>,
+}
+
+impl LinkReport {
+    pub fn new(results: Vec<LinkResult>, checked_at: DateTime<Utc>) -> Self {
+        let mut summary = LinkSummary {
+            checked: results.len(),
+            ..LinkSummary::default()
+        };
+        for result in &results {
+            match result.status() {
+                LinkCheckStatus::Ok => summary.ok += 1,
+                LinkCheckStatus::Redirected => summary.redirected += 1,
+                LinkCheckStatus::Broken => summary.broken += 1,
+                LinkCheckStatus::Unreachable => summary.unreachable += 1,
+            }
+        }
+
+        Self {
+            checked_at,
+            summary,
+            results,
+        }
+    }
+
+    /// The link status of every checked URL, dated with the day of the check
+    pub fn link_status(&self) -> LinkStatus {
+        let date = self.checked_at.date_naive();
+        self.results
+            .iter()
+            .map(|result| {
+                let check = LinkCheck {
+                    date,
+                    status: result.status(),
+                    http_status: result.http_status(),
+                };
+                (result.url.clone(), check)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn result(url: &str, redirects: usize, outcome: LinkOutcome) -> LinkResult {
+        LinkResult {
+            url: url.to_string(),
+            sources: vec![],
+            redirects: (0..redirects)
+                .map(|i| Redirect {
+                    status: 301,
+                    location: format!("{}/{}", url, i),
+                })
+                .collect(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_status_classification() {
+        let ok = result("https://a.example", 0, LinkOutcome::Response { status: 200 });
+        assert_eq!(ok.status(), LinkCheckStatus::Ok);
+        assert_eq!(ok.final_url(), None);
+
+        let moved = result("https://b.example", 2, LinkOutcome::Response { status: 200 });
+        assert_eq!(moved.status(), LinkCheckStatus::Redirected);
+        assert_eq!(moved.final_url(), Some("https://b.example/1"));
+
+        let gone = result("https://c.example", 1, LinkOutcome::Response { status: 410 });
+        assert_eq!(gone.status(), LinkCheckStatus::Broken);
+        assert_eq!(gone.http_status(), Some(410));
+
+        let timeout = result("https://d.example", 0, LinkOutcome::Timeout);
+        assert_eq!(timeout.status(), LinkCheckStatus::Unreachable);
+        assert_eq!(timeout.http_status(), None);
+    }
+
+    #[test]
+    fn test_report_summary_and_link_status() {
+        let checked_at = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+        let report = LinkReport::new(
+            vec![
+                result("https://a.example", 0, LinkOutcome::Response { status: 200 }),
+                result("https://b.example", 1, LinkOutcome::Response { status: 200 }),
+                result("https://c.example", 0, LinkOutcome::Response { status: 500 }),
+                result("https://d.example", 0, LinkOutcome::TooManyRedirects),
+            ],
+            checked_at,
+        );
+
+        assert_eq!(
+            report.summary,
+            LinkSummary {
+                checked: 4,
+                ok: 1,
+                redirected: 1,
+                broken: 1,
+                unreachable: 1,
+            }
+        );
+
+        let status = report.link_status();
+        assert_eq!(status.len(), 4);
+        let broken = &status["https://c.example"];
+        assert_eq!(broken.date, NaiveDate::from_ymd_opt(2026, 10, 1).unwrap());
+        assert_eq!(broken.status, LinkCheckStatus::Broken);
+        assert_eq!(broken.http_status, Some(500));
+    }
+
+    #[test]
+    fn test_outcome_serialization() {
+        let json = serde_json::to_value(result(
+            "https://a.example",
+            1,
+            LinkOutcome::Response { status: 200 },
+        ))
+        .unwrap();
+        assert_eq!(json["outcome"], "response");
+        assert_eq!(json["status"], 200);
+        assert_eq!(json["redirects"][0]["location"], "https://a.example/0");
+
+        let json = serde_json::to_value(result("https://a.example", 0, LinkOutcome::Timeout)).unwrap();
+        assert_eq!(json["outcome"], "timeout");
+        assert!(json.get("redirects").is_none());
+    }
+}
@@ -1,63 +1,140 @@
+mod analytics;
 mod audit;
 mod config;
 mod contributions;
 mod error;
+mod eval;
 mod federation;
 mod fork;
 mod identity;
+mod linkcheck;
 mod matcher;
 mod mcp;
 mod pubky;
 mod registry;
+mod reload;
 mod server;
+mod stdio;
+#[cfg(test)]
+mod test_support;
 
 use config::Config;
 use std::sync::Arc;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::{
+    fmt, fmt::writer::BoxMakeWriter, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter,
+};
 
-fn init_logging(log_format: &str) {
+/// Initialize logging. In stdio mode logs go to stderr so stdout carries
+/// only MCP protocol messages.
+fn init_logging(log_format: &str, stdio: bool) {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let writer = if stdio {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
 
     match log_format {
         "json" => {
             tracing_subscriber::registry()
                 .with(env_filter)
-                .with(fmt::layer().json())
+                .with(fmt::layer().json().with_writer(writer))
                 .init();
         }
         _ => {
             // Default to pretty format (colored, human-readable)
             tracing_subscriber::registry()
                 .with(env_filter)
-                .with(fmt::layer().pretty())
+                .with(fmt::layer().pretty().with_writer(writer))
                 .init();
         }
     }
 }
 
+/// Parse `serve` arguments. Returns true for `serve --stdio`.
+/// No arguments (or bare `serve`) runs the HTTP server.
+fn parse_serve_args(args: &[String]) -> Result<bool, String> {
+    match args.get(1).map(String::as_str) {
+        None => Ok(false),
+        Some("serve") => {
+            let mut stdio = false;
+            for arg in &args[2..] {
+                match arg.as_str() {
+                    "--stdio" => stdio = true,
+                    other => return Err(format!("Unknown serve flag: {}", other)),
+                }
+            }
+            Ok(stdio)
+        }
+        Some(other) => Err(format!(
+            "Unknown subcommand: {}\n\nUsage: three-good-sources [serve [--stdio] | fork ... | eval ... | query-stats ... | check-links ...]",
+            other
+        )),
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Check for fork subcommand before loading config (no env vars needed)
-    {
-        let args: Vec<String> = std::env::args().collect();
-        if args.len() > 1 && args[1] == "fork" {
-            match crate::fork::run(args) {
-                Ok(()) => std::process::exit(0),
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    std::process::exit(1);
-                }
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() > 1 && args[1] == "fork" {
+        match crate::fork::run(args) {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Check for eval subcommand (reads match settings from the environment itself)
+    if args.len() > 1 && args[1] == "eval" {
+        match crate::eval::run(args).await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
             }
         }
     }
 
+    // Check for query-stats subcommand (reads QUERY_LOG_PATH from the environment itself)
+    if args.len() > 1 && args[1] == "query-stats" {
+        match crate::analytics::run(args).await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // Check for check-links subcommand (reads REGISTRY_PATH from the environment itself)
+    if args.len() > 1 && args[1] == "check-links" {
+        match crate::linkcheck::run(args).await {
+            Ok(()) => std::process::exit(0),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let stdio = match parse_serve_args(&args) {
+        Ok(stdio) => stdio,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
     // Load configuration from environment
     let config = Config::load()?;
 
     // Initialize logging based on configured format
-    init_logging(&config.log_format);
+    init_logging(&config.log_format, stdio);
 
-    tracing::info!("Starting 3GS server");
+    tracing::info!(transport = if stdio { "stdio" } else { "http" }, "Starting 3GS server");
 
     // Load and validate match configuration
     let match_config = matcher::MatchConfig::load()?;
@@ -79,44 +156,71 @@ async fn main() -> anyhow::Result<()> {
         "Server identity initialized"
     );
 
-    // Load and validate registry
-    let registry = Arc::new(registry::load(&config.registry_path).await?);
-
-    // Load audit log
-    let audit_log = Arc::new(crate::audit::load(&config.audit_log_path).await?);
-    tracing::info!(entries = audit_log.len(), "Audit log loaded");
-
-    // Load identities
-    let identities = Arc::new(crate::identity::load(&config.identities_path).await?);
-    tracing::info!(count = identities.len(), "Identities loaded");
+    // Load and validate registry, audit log, identities and contributions
+    let data_paths = reload::DataPaths::from_config(&config);
+    let snapshot = reload::load_snapshot(&data_paths).await?;
+    tracing::info!(entries = snapshot.audit_log.len(), "Audit log loaded");
+    tracing::info!(count = snapshot.identities.len(), "Identities loaded");
+    tracing::info!(count = snapshot.proposals.len(), "Contributions loaded");
+    reload::warn_on_foreign_curator(&snapshot.registry, &public_key.to_z32());
+    let endorsements = snapshot.registry.endorsements.clone();
+    let data = Arc::new(reload::DataStore::new(snapshot));
 
-    // Load contributions (validates voter pubkeys against identities)
-    let contributions = crate::contributions::load(&config.contributions_path, &identities).await?;
-    let proposals = Arc::new(contributions);
-    tracing::info!(count = proposals.len(), "Contributions loaded");
-
-    // Create peer cache from endorsements
-    let peer_cache = Arc::new(crate::federation::PeerCache::new(
-        registry.endorsements.clone(),
-        public_key.to_z32(),
-    ));
+    // Create peer cache from endorsements; failing peers back off from the refresh interval
+    let refresh_interval = std::time::Duration::from_secs(config.peer_refresh_secs.max(1));
+    let mut peer_cache = crate::federation::PeerCache::new(endorsements, public_key.to_z32())
+        .with_backoff_base(refresh_interval);
+    if let Some(path) = config.peer_cache_path.clone() {
+        peer_cache = peer_cache.with_persist_path(path);
+    }
+    let peer_cache = Arc::new(peer_cache);
     tracing::info!(peers = peer_cache.peer_count().await, "Peer cache initialized");
 
+    // Serve last good peer registries from disk until the first refresh lands
+    peer_cache.restore().await;
+
     // Create shutdown channel
     let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
 
     // Run initial refresh before server starts
     peer_cache.refresh_all().await;
 
-    // Spawn background refresh loop (every 5 minutes)
+    // Spawn background reload task (file changes and SIGHUP)
+    let poll_interval = if config.reload_poll_secs > 0 {
+        Some(std::time::Duration::from_secs(config.reload_poll_secs))
+    } else {
+        None
+    };
+    let reload_handle = reload::spawn_reload_task(
+        data_paths,
+        Arc::clone(&data),
+        Arc::clone(&peer_cache),
+        poll_interval,
+        shutdown_rx.clone(),
+    );
+
+    // Spawn background refresh loop (every PEER_REFRESH_SECS). The first
+    // tick waits a full interval, since the startup refresh just ran.
     let refresh_cache = Arc::clone(&peer_cache);
     let refresh_handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+        let first_tick = tokio::time::Instant::now() + refresh_interval;
+        let mut interval = tokio::time::interval_at(first_tick, refresh_interval);
         let mut shutdown_rx = shutdown_rx;
         loop {
             tokio::select! {
                 _ = interval.tick() => {
                     refresh_cache.refresh_all().await;
+                    let now = std::time::Instant::now();
+                    for peer in refresh_cache.get_all_cached().await {
+                        if let Some(next_attempt) = peer.next_attempt {
+                            tracing::debug!(
+                                pubkey = %peer.pubkey,
+                                failures = peer.consecutive_failures,
+                                retry_in_secs = next_attempt.saturating_duration_since(now).as_secs(),
+                                "Peer is backing off"
+                            );
+                        }
+                    }
                 }
                 _ = shutdown_rx.changed() => {
                     tracing::info!("Peer cache refresh loop shutting down");
@@ -126,42 +230,60 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
+    // Open the query analytics log if configured
+    let query_log = match &config.query_log_path {
+        Some(path) => {
+            let query_log = Arc::new(analytics::QueryLog::open(path).await?);
+            tracing::info!(path = %query_log.path().display(), "Query analytics enabled");
+            Some(query_log)
+        }
+        None => None,
+    };
+
     // Create MCP handler with shared registry and match config
     let pubkey_z32 = public_key.to_z32();
-    let mcp_handler = mcp::McpHandler::new(
-        Arc::clone(&registry),
+    let mut mcp_handler = mcp::McpHandler::new(
+        Arc::clone(&data),
         match_config,
         pubkey_z32,
-        Arc::clone(&audit_log),
-        Arc::clone(&identities),
-        Arc::clone(&proposals),
         Arc::clone(&peer_cache),
-    );
+    )
+    .with_session_ttl(std::time::Duration::from_secs(config.mcp_session_ttl_secs));
+    if let Some(query_log) = &query_log {
+        mcp_handler = mcp_handler.with_query_log(Arc::clone(query_log));
+    }
 
-    // Build application state
-    let app_state = Arc::new(server::AppState {
-        mcp_handler,
-        registry,
-        pubkey: public_key,
-        audit_log,
-        identities,
-        proposals,
-        peer_cache,
-    });
+    if stdio {
+        // Serve MCP on stdin/stdout until the client closes stdin
+        stdio::serve_stdio(&mcp_handler).await?;
+    } else {
+        // Build application state
+        let app_state = Arc::new(server::AppState {
+            mcp_handler,
+            data,
+            pubkey: public_key,
+            keypair,
+            peer_cache,
+            // A blank ADMIN_TOKEN= line leaves /admin disabled
+            admin_token: config.admin_token.filter(|token| !token.trim().is_empty()),
+            query_log,
+        });
 
-    // Build router with routes and middleware
-    let app = server::build_router(app_state);
+        // Build router with routes and middleware
+        let app = server::build_router(app_state);
 
-    // Bind to configured address and start server
-    let addr = format!("0.0.0.0:{}", config.port);
-    tracing::info!(port = config.port, "Server listening on {}", addr);
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+        // Bind to configured address and start server
+        let addr = format!("0.0.0.0:{}", config.port);
+        tracing::info!(port = config.port, "Server listening on {}", addr);
+        let listener = tokio::net::TcpListener::bind(&addr).await?;
+        axum::serve(listener, app).await?;
+    }
 
     // Signal background tasks to stop
     let _ = shutdown_tx.send(true);
-    // Wait for refresh loop to finish (clean shutdown)
+    // Wait for background tasks to finish (clean shutdown)
     let _ = refresh_handle.await;
+    let _ = reload_handle.await;
 
     Ok(())
 }
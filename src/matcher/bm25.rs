@@ -0,0 +1,206 @@
+use super::index::IndexedCategory;
+use std::collections::{HashMap, HashSet};
+
+/// Term frequency saturation parameter
+const K1: f64 = 1.2;
+
+/// Document length normalization parameter
+const B: f64 = 0.75;
+
+/// Token bag for one category
+#[derive(Debug)]
+struct Document {
+    term_freqs: HashMap<String, usize>,
+    length: usize,
+}
+
+/// BM25 statistics over every category in a registry.
+///
+/// Each category is one document built from its name, description, slug
+/// terms and query patterns (translated and English, for a translated
+/// view), normalized with the same pipeline (including the registry's
+/// synonyms and stemming) as queries.
+/// Inverse document frequencies are computed across the registry's
+/// categories, so terms shared by many categories ("self", "guide") count
+/// for less than distinctive ones ("bitcoin", "nostr").
+#[derive(Debug)]
+pub struct Bm25Index {
+    documents: HashMap<String, Document>,
+    doc_freqs: HashMap<String, usize>,
+    avg_length: f64,
+}
+
+impl Bm25Index {
+    /// Build the statistics from indexed categories
+    pub(crate) fn new(categories: &[IndexedCategory]) -> Self {
+        let documents: HashMap<String, Document> = categories
+            .iter()
+            .map(|entry| (entry.slug.clone(), build_document(entry)))
+            .collect();
+
+        let mut doc_freqs: HashMap<String, usize> = HashMap::new();
+        for document in documents.values() {
+            for term in document.term_freqs.keys() {
+                *doc_freqs.entry(term.clone()).or_insert(0) += 1;
+            }
+        }
+
+        let total_length: usize = documents.values().map(|d| d.length).sum();
+        let avg_length = if documents.is_empty() {
+            0.0
+        } else {
+            total_length as f64 / documents.len() as f64
+        };
+
+        Self {
+            documents,
+            doc_freqs,
+            avg_length,
+        }
+    }
+
+    /// Score a normalized query against one category, scaled to 0.0-1.0.
+    ///
+    /// The raw BM25 sum is divided by the sum of the query terms' IDFs, i.e.
+    /// the score a category would get by containing every query term once at
+    /// average document length. Query terms no category contains still count
+    /// in the denominator, so unmatched words lower the score. Repeated query
+    /// terms are counted once. Returns 0.0 for unknown slugs.
+    pub fn score(&self, query: &str, slug: &str) -> f64 {
+        let Some(document) = self.documents.get(slug) else {
+            return 0.0;
+        };
+
+        let mut seen = HashSet::new();
+        let mut raw = 0.0;
+        let mut max = 0.0;
+
+        for term in query.split_whitespace().filter(|term| seen.insert(*term)) {
+            let idf = self.idf(term);
+            max += idf;
+
+            let tf = document.term_freqs.get(term).copied().unwrap_or(0) as f64;
+            if tf > 0.0 {
+                let length_norm = if self.avg_length > 0.0 {
+                    1.0 - B + B * document.length as f64 / self.avg_length
+                } else {
+                    1.0
+                };
+                raw += idf * (tf * (K1 + 1.0)) / (tf + K1 * length_norm);
+            }
+        }
+
+        if max == 0.0 {
+            return 0.0;
+        }
+        (raw / max).min(1.0)
+    }
+
+    /// Inverse document frequency (BM25 variant that never goes negative)
+    fn idf(&self, term: &str) -> f64 {
+        let n = self.documents.len() as f64;
+        let df = self.doc_freqs.get(term).copied().unwrap_or(0) as f64;
+        (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+    }
+}
+
+/// Collect the normalized tokens of every text field of a category
+fn build_document(entry: &IndexedCategory) -> Document {
+    let mut tokens: Vec<String> = entry.slug_terms.clone();
+    tokens.extend(entry.text_terms.iter().cloned());
+    for pattern in &entry.patterns {
+        tokens.extend(pattern.normalized.split_whitespace().map(|t| t.to_string()));
+    }
+
+    let mut term_freqs = HashMap::new();
+    for token in &tokens {
+        *term_freqs.entry(token.clone()).or_insert(0) += 1;
+    }
+
+    Document {
+        term_freqs,
+        length: tokens.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bm25Index;
+    use crate::matcher::index::MatchIndex;
+    use crate::matcher::language::Language;
+    use crate::registry::Registry;
+
+    fn load_test_registry() -> Registry {
+        let json = include_str!("../../registry.json");
+        serde_json::from_str(json).expect("Failed to parse registry.json")
+    }
+
+    fn load_test_index() -> MatchIndex {
+        let registry = load_test_registry();
+        MatchIndex::new(&registry.categories, &registry.matching)
+    }
+
+    fn english_bm25(index: &MatchIndex) -> &Bm25Index {
+        &index.language(Language::English).bm25
+    }
+
+    #[test]
+    fn test_matching_terms_score_higher() {
+        let index = load_test_index();
+        let index = english_bm25(&index);
+
+        let bitcoin = index.score("bitcoin node", "bitcoin-node-setup");
+        let rust = index.score("bitcoin node", "rust-learning");
+
+        assert!(bitcoin > 0.0);
+        assert!(bitcoin > rust, "bitcoin {} should beat rust {}", bitcoin, rust);
+    }
+
+    #[test]
+    fn test_no_shared_terms_scores_zero() {
+        let index = load_test_index();
+        let index = english_bm25(&index);
+        assert_eq!(index.score("quantum supercollider", "rust-learning"), 0.0);
+    }
+
+    #[test]
+    fn test_unknown_slug_scores_zero() {
+        let index = load_test_index();
+        let index = english_bm25(&index);
+        assert_eq!(index.score("learn rust", "no-such-category"), 0.0);
+    }
+
+    #[test]
+    fn test_unmatched_terms_lower_score() {
+        let index = load_test_index();
+        let index = english_bm25(&index);
+
+        let focused = index.score("rust", "rust-learning");
+        let diluted = index.score("rust supercollider", "rust-learning");
+
+        assert!(diluted < focused);
+    }
+
+    #[test]
+    fn test_repeated_terms_counted_once() {
+        let index = load_test_index();
+        let index = english_bm25(&index);
+
+        let once = index.score("nostr", "nostr-development");
+        let twice = index.score("nostr nostr", "nostr-development");
+
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn test_score_in_range() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let index = english_bm25(&index);
+
+        for slug in registry.categories.keys() {
+            let score = index.score("self hosted email server setup guide", slug);
+            assert!((0.0..=1.0).contains(&score), "{} scored {}", slug, score);
+        }
+    }
+}
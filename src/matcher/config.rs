@@ -1,4 +1,17 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+/// Which scorer produces a category's match score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    /// Levenshtein similarity blended with slug keyword boosting
+    #[default]
+    Fuzzy,
+    /// BM25 over category name, description, slug and query patterns
+    Bm25,
+    /// Fuzzy and BM25 scores blended by `match_bm25_weight`
+    Hybrid,
+}
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct MatchConfig {
@@ -13,6 +26,14 @@ pub struct MatchConfig {
     /// Weight for keyword boost score (default: 0.3)
     #[serde(default = "default_keyword_weight")]
     pub match_keyword_weight: f64,
+
+    /// Scorer to use: fuzzy, bm25, or hybrid (default: fuzzy)
+    #[serde(default)]
+    pub match_mode: MatchMode,
+
+    /// Share of the BM25 score in hybrid mode; the fuzzy score gets the rest (default: 0.5)
+    #[serde(default = "default_bm25_weight")]
+    pub match_bm25_weight: f64,
 }
 
 fn default_threshold() -> f64 {
@@ -27,6 +48,10 @@ fn default_keyword_weight() -> f64 {
     0.3
 }
 
+fn default_bm25_weight() -> f64 {
+    0.5
+}
+
 impl MatchConfig {
     pub fn load() -> Result<Self, anyhow::Error> {
         envy::from_env::<MatchConfig>().map_err(|e| {
@@ -46,6 +71,9 @@ impl MatchConfig {
                 self.match_fuzzy_weight + self.match_keyword_weight
             );
         }
+        if self.match_bm25_weight < 0.0 || self.match_bm25_weight > 1.0 {
+            anyhow::bail!("MATCH_BM25_WEIGHT must be between 0.0 and 1.0");
+        }
         Ok(())
     }
 }
@@ -8,13 +8,20 @@ pub enum MatchError {
     #[error("Query contains only stop words and has no searchable content")]
     QueryAllStopWords,
 
+    #[error("Registry has no categories to match against")]
+    NoCategories,
+
     #[error(
-        "No category matches query (threshold: {threshold:.2}). Closest: {closest_slug} ({closest_score:.2}). Available: {all_slugs:?}. Request a new category at https://github.com/johnzilla/3goodsources"
+        "No category matches query (threshold: {threshold:.2}). Closest: {closest_slug} ({closest_score:.2}). See also: {suggestions:?}. Request a new category at https://github.com/johnzilla/3goodsources"
     )]
     BelowThreshold {
         threshold: f64,
         closest_slug: String,
         closest_score: f64,
-        all_slugs: Vec<String>,
+        /// Categories to try instead: those the closest category links to
+        /// as related or parent, else the next closest by score
+        suggestions: Vec<String>,
+        /// The query after normalization, for analytics on unmatched queries
+        normalized_query: String,
     },
 }
@@ -0,0 +1,336 @@
+use super::bm25::Bm25Index;
+use super::language::Language;
+use super::normalize::Normalizer;
+use crate::registry::types::{Category, MatchingRules};
+use std::collections::{BTreeSet, HashMap};
+
+/// A query pattern with its normalized form
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedPattern {
+    /// The pattern as written in the registry
+    pub pattern: String,
+    /// The pattern after normalization, synonyms and stemming
+    pub normalized: String,
+}
+
+/// A category with every match surface precomputed for one language
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedCategory {
+    pub slug: String,
+    /// The category, with name and description translated when it has them
+    /// in the index's language
+    pub category: Category,
+    /// Slug with hyphens replaced by spaces, rewritten by the normalizer
+    pub slug_text: String,
+    /// Words of `slug_text`
+    pub slug_terms: Vec<String>,
+    /// Category name (translated if available) lowercased, rewritten by the normalizer
+    pub name_lower: String,
+    /// Query patterns that normalize to something: translated patterns
+    /// first, then the English ones, each in registry order
+    pub patterns: Vec<IndexedPattern>,
+    /// Normalized words of the name and description, translated and English
+    pub text_terms: Vec<String>,
+    /// Negative patterns that normalize to something, in registry order
+    pub negative_patterns: Vec<IndexedPattern>,
+}
+
+impl IndexedCategory {
+    pub(crate) fn new(
+        slug: &str,
+        category: &Category,
+        normalizer: &Normalizer,
+        language: Language,
+    ) -> Self {
+        let translation = match language {
+            Language::English => None,
+            _ => category.localized.get(language.code()),
+        };
+
+        let mut indexed = category.clone();
+        let mut patterns = Vec::new();
+        let mut text_terms = Vec::new();
+        let name_lower = match translation {
+            Some(translation) => {
+                indexed.name = translation.name.clone();
+                indexed.description = translation.description.clone();
+                patterns.extend(index_patterns(&translation.query_patterns, normalizer, language));
+                text_terms.extend(normalize_terms(
+                    [&translation.name, &translation.description],
+                    normalizer,
+                    language,
+                ));
+                normalizer.rewrite(&translation.name.to_lowercase(), language)
+            }
+            None => normalizer.rewrite(&category.name.to_lowercase(), Language::English),
+        };
+        patterns.extend(index_patterns(&category.query_patterns, normalizer, Language::English));
+        text_terms.extend(normalize_terms(
+            [&category.name, &category.description],
+            normalizer,
+            Language::English,
+        ));
+
+        let slug_text = normalizer.rewrite(&slug.replace('-', " "), Language::English);
+
+        Self {
+            slug: slug.to_string(),
+            category: indexed,
+            slug_terms: slug_text.split_whitespace().map(|term| term.to_string()).collect(),
+            slug_text,
+            name_lower,
+            patterns,
+            text_terms,
+            negative_patterns: index_patterns(
+                &category.negative_patterns,
+                normalizer,
+                Language::English,
+            ),
+        }
+    }
+}
+
+/// Normalize query patterns written in `language`
+fn index_patterns(
+    patterns: &[String],
+    normalizer: &Normalizer,
+    language: Language,
+) -> Vec<IndexedPattern> {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            // Patterns that normalize to nothing can never match
+            let normalized = normalizer.normalize(pattern, language).ok()?;
+            Some(IndexedPattern {
+                pattern: pattern.clone(),
+                normalized,
+            })
+        })
+        .collect()
+}
+
+/// Normalized words of free-text fields written in `language`
+fn normalize_terms(texts: [&str; 2], normalizer: &Normalizer, language: Language) -> Vec<String> {
+    texts
+        .iter()
+        // Fields that normalize to nothing contribute no words
+        .filter_map(|text| normalizer.normalize(text, language).ok())
+        .flat_map(|normalized| {
+            normalized
+                .split_whitespace()
+                .map(|term| term.to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Categories and BM25 statistics for queries in one language
+#[derive(Debug)]
+pub(crate) struct LanguageIndex {
+    /// Categories sorted by slug
+    pub categories: Vec<IndexedCategory>,
+    pub bm25: Bm25Index,
+}
+
+impl LanguageIndex {
+    fn new(
+        categories: &HashMap<String, Category>,
+        normalizer: &Normalizer,
+        language: Language,
+    ) -> Self {
+        let mut indexed: Vec<IndexedCategory> = categories
+            .iter()
+            .map(|(slug, category)| IndexedCategory::new(slug, category, normalizer, language))
+            .collect();
+        indexed.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        Self {
+            bm25: Bm25Index::new(&indexed),
+            categories: indexed,
+        }
+    }
+}
+
+/// Precomputed matching data for a set of categories.
+///
+/// Built once when a registry is loaded (or a peer registry is fetched) so
+/// that each query only normalizes the query itself. Holds one view per
+/// language the categories are written in (English, plus every supported
+/// language some category is translated into) and the registry's
+/// normalizer, so matching needs nothing else.
+#[derive(Debug)]
+pub struct MatchIndex {
+    languages: HashMap<Language, LanguageIndex>,
+    /// Normalizer for queries, built from the registry's matching rules
+    pub(crate) normalizer: Normalizer,
+}
+
+impl MatchIndex {
+    /// Build the index for a registry's categories and matching rules
+    pub fn new(categories: &HashMap<String, Category>, rules: &MatchingRules) -> Self {
+        let normalizer = Normalizer::new(rules);
+
+        // Translations in unsupported languages (possible in unvalidated
+        // peer registries) are ignored
+        let mut languages: BTreeSet<Language> = categories
+            .values()
+            .flat_map(|category| category.localized.keys())
+            .filter_map(|code| Language::from_code(code))
+            .collect();
+        languages.insert(Language::English);
+
+        let languages = languages
+            .into_iter()
+            .map(|language| (language, LanguageIndex::new(categories, &normalizer, language)))
+            .collect();
+
+        Self {
+            languages,
+            normalizer,
+        }
+    }
+
+    /// The view for queries in `language`, or the English view if no
+    /// category is translated into it
+    pub(crate) fn language(&self, language: Language) -> &LanguageIndex {
+        self.languages
+            .get(&language)
+            .unwrap_or_else(|| &self.languages[&Language::English])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::Registry;
+
+    fn load_test_registry() -> Registry {
+        let json = include_str!("../../registry.json");
+        serde_json::from_str(json).expect("Failed to parse registry.json")
+    }
+
+    fn find<'a>(view: &'a LanguageIndex, slug: &str) -> &'a IndexedCategory {
+        view.categories.iter().find(|c| c.slug == slug).unwrap()
+    }
+
+    #[test]
+    fn test_indexes_every_category_sorted() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+
+        for language in Language::ALL {
+            let view = index.language(language);
+            assert_eq!(view.categories.len(), registry.categories.len());
+            let slugs: Vec<&str> = view.categories.iter().map(|c| c.slug.as_str()).collect();
+            let mut sorted = slugs.clone();
+            sorted.sort();
+            assert_eq!(slugs, sorted);
+        }
+    }
+
+    #[test]
+    fn test_precomputes_surfaces() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+
+        let entry = find(index.language(Language::English), "bitcoin-node-setup");
+        assert_eq!(entry.slug_text, "bitcoin node setup");
+        assert_eq!(entry.slug_terms, vec!["bitcoin", "node", "setup"]);
+        assert_eq!(entry.name_lower, "bitcoin node setup");
+        assert_eq!(entry.patterns.len(), entry.category.query_patterns.len());
+        for pattern in &entry.patterns {
+            assert_eq!(
+                pattern.normalized,
+                index
+                    .normalizer
+                    .normalize(&pattern.pattern, Language::English)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_index() {
+        let index = MatchIndex::new(&HashMap::new(), &MatchingRules::default());
+        assert!(index.language(Language::English).categories.is_empty());
+    }
+
+    #[test]
+    fn test_matching_rules_applied_to_surfaces() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+
+        let entry = find(index.language(Language::English), "self-hosted-email");
+        // "hosted" is stemmed; "mail" is rewritten to its canonical "email"
+        assert_eq!(entry.slug_terms, vec!["self", "host", "email"]);
+        let pattern = entry
+            .patterns
+            .iter()
+            .find(|p| p.pattern == "run my own mail server")
+            .unwrap();
+        assert_eq!(pattern.normalized, "run email server");
+    }
+
+    #[test]
+    fn test_default_rules_leave_surfaces_unchanged() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &MatchingRules::default());
+
+        let entry = find(index.language(Language::English), "self-hosted-email");
+        assert_eq!(entry.slug_terms, vec!["self", "hosted", "email"]);
+        assert_eq!(entry.name_lower, "self-hosted email");
+    }
+
+    #[test]
+    fn test_negative_patterns_normalized() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+
+        for language in Language::ALL {
+            let entry = find(index.language(language), "rust-learning");
+            let pattern = entry
+                .negative_patterns
+                .iter()
+                .find(|p| p.pattern == "remove rust")
+                .unwrap();
+            assert_eq!(pattern.normalized, "remov rust");
+        }
+    }
+
+    #[test]
+    fn test_translated_view_uses_translation() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let category = &registry.categories["rust-learning"];
+        let translation = &category.localized["es"];
+
+        let entry = find(index.language(Language::Spanish), "rust-learning");
+        assert_eq!(entry.category.name, translation.name);
+        assert_eq!(entry.category.description, translation.description);
+
+        // Translated patterns come first, then the English ones
+        let patterns: Vec<&str> = entry.patterns.iter().map(|p| p.pattern.as_str()).collect();
+        let expected: Vec<&str> = translation
+            .query_patterns
+            .iter()
+            .chain(&category.query_patterns)
+            .map(|p| p.as_str())
+            .collect();
+        assert_eq!(patterns, expected);
+
+        // Slugs stay English
+        assert_eq!(entry.slug_terms, vec!["rust", "learn"]);
+    }
+
+    #[test]
+    fn test_untranslated_language_falls_back_to_english() {
+        let mut registry = load_test_registry();
+        for category in registry.categories.values_mut() {
+            category.localized.clear();
+        }
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+
+        let entry = find(index.language(Language::German), "rust-learning");
+        assert_eq!(entry.category.name, "Rust Learning");
+    }
+}
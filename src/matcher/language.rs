@@ -0,0 +1,66 @@
+/// A language queries and category text can be matched in.
+///
+/// Each language normalizes with its own NLTK stop-word list. English is
+/// the language of a category's own name, description and query patterns;
+/// the others come from a category's `localized` section.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+    German,
+}
+
+impl Language {
+    /// Every supported language, English first
+    pub const ALL: [Language; 3] = [Language::English, Language::Spanish, Language::German];
+
+    /// ISO 639-1 code (e.g., "es")
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Spanish => "es",
+            Language::German => "de",
+        }
+    }
+
+    /// Look up a language by ISO 639-1 code, ignoring case
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    /// Comma-separated codes of every supported language, for error messages
+    pub fn supported_codes() -> String {
+        Self::ALL
+            .iter()
+            .map(|language| language.code())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_round_trip() {
+        for language in Language::ALL {
+            assert_eq!(Language::from_code(language.code()), Some(language));
+        }
+    }
+
+    #[test]
+    fn test_from_code_ignores_case() {
+        assert_eq!(Language::from_code("ES"), Some(Language::Spanish));
+        assert_eq!(Language::from_code(" de "), Some(Language::German));
+    }
+
+    #[test]
+    fn test_unknown_code() {
+        assert_eq!(Language::from_code("xx"), None);
+        assert_eq!(Language::from_code(""), None);
+    }
+}
@@ -1,8 +1,16 @@
+pub mod bm25;
 pub mod config;
 pub mod error;
+pub mod index;
+pub mod language;
 pub mod normalize;
 pub mod scorer;
+pub mod stem;
 
-pub use config::MatchConfig;
+pub use config::{MatchConfig, MatchMode};
 pub use error::MatchError;
-pub use scorer::match_query;
+pub use index::MatchIndex;
+pub use language::Language;
+pub use scorer::{
+    match_query, match_query_top_k, MatchResult, ScoreBreakdown, NEGATIVE_PATTERN_PENALTY,
+};
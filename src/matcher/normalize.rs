@@ -1,15 +1,45 @@
 use super::error::MatchError;
+use super::language::Language;
+use super::stem;
+use crate::registry::types::MatchingRules;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// Matches anything that is not a word character or whitespace
+static PUNCT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^\w\s]").unwrap());
+
+/// Matches runs of whitespace
+static WS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+
+/// Stop words of every supported language, built on first use
+static STOP_WORDS: LazyLock<HashMap<Language, HashSet<String>>> = LazyLock::new(|| {
+    Language::ALL
+        .into_iter()
+        .map(|language| {
+            let words = stop_words::get(language.code())
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            (language, words)
+        })
+        .collect()
+});
+
+/// Normalize English text for matching (see [`normalize_text_in`])
+pub fn normalize_text(text: &str) -> Result<String, MatchError> {
+    normalize_text_in(text, Language::English)
+}
 
 /// Normalize text for matching through a 4-stage pipeline:
 /// 1. Lowercase
 /// 2. Strip punctuation
-/// 3. Remove stop words
+/// 3. Remove stop words of `language`
 /// 4. Normalize whitespace
 ///
 /// Returns an error if the input is empty or contains only stop words.
-pub fn normalize_text(text: &str) -> Result<String, MatchError> {
+/// The regexes and stop-word sets are compiled once and shared across calls.
+pub fn normalize_text_in(text: &str, language: Language) -> Result<String, MatchError> {
     // Pre-check: empty query
     if text.trim().is_empty() {
         return Err(MatchError::EmptyQuery);
@@ -19,15 +49,10 @@ pub fn normalize_text(text: &str) -> Result<String, MatchError> {
     let mut normalized = text.to_lowercase();
 
     // Stage 2: Strip punctuation (keep only word characters and whitespace)
-    let punct_re = Regex::new(r"[^\w\s]").unwrap();
-    normalized = punct_re.replace_all(&normalized, "").to_string();
+    normalized = PUNCT_RE.replace_all(&normalized, "").to_string();
 
     // Stage 3: Remove stop words
-    let stop_words: HashSet<String> = stop_words::get(stop_words::LANGUAGE::English)
-        .iter()
-        .map(|s| s.to_string())
-        .collect();
-
+    let stop_words = &STOP_WORDS[&language];
     normalized = normalized
         .split_whitespace()
         .filter(|word| !stop_words.contains(*word))
@@ -35,8 +60,7 @@ pub fn normalize_text(text: &str) -> Result<String, MatchError> {
         .join(" ");
 
     // Stage 4: Normalize whitespace (trim + collapse multiple spaces)
-    let ws_re = Regex::new(r"\s+").unwrap();
-    normalized = ws_re.replace_all(normalized.trim(), " ").to_string();
+    normalized = WS_RE.replace_all(normalized.trim(), " ").to_string();
 
     // Post-check: all stop words removed
     if normalized.is_empty() {
@@ -46,9 +70,147 @@ pub fn normalize_text(text: &str) -> Result<String, MatchError> {
     Ok(normalized)
 }
 
+/// Guess the language of a query from its stop words.
+///
+/// Counts the words of the query that are stop words in each supported
+/// language and picks the language with the most. Queries without any stop
+/// words ("learn rust") and ties resolve to English.
+pub fn detect_language(text: &str) -> Language {
+    let lowered = text.to_lowercase();
+    let stripped = PUNCT_RE.replace_all(&lowered, "");
+    let words: Vec<&str> = stripped.split_whitespace().collect();
+
+    let mut best = Language::English;
+    let mut best_count = 0;
+    for language in Language::ALL {
+        let stop_words = &STOP_WORDS[&language];
+        let count = words.iter().filter(|word| stop_words.contains(**word)).count();
+        if count > best_count {
+            best = language;
+            best_count = count;
+        }
+    }
+    best
+}
+
+/// Lookup key for a synonym alias, or `None` if the alias does not
+/// normalize to exactly one word. With stemming on the key is stemmed, so
+/// an alias also catches its inflections ("credential" catches "credentials").
+pub fn synonym_key(alias: &str, stemming: bool) -> Option<String> {
+    let normalized = normalize_text(alias).ok()?;
+    if normalized.contains(' ') {
+        return None;
+    }
+    Some(if stemming {
+        stem::stem(&normalized)
+    } else {
+        normalized
+    })
+}
+
+/// True if every word of `words` is a word of `text`, in any order.
+/// Both must already be normalized.
+pub fn contains_words(text: &str, words: &str) -> bool {
+    let text: HashSet<&str> = text.split_whitespace().collect();
+    words.split_whitespace().all(|word| text.contains(word))
+}
+
+/// Registry-specific rewriting applied after [`normalize_text`].
+///
+/// Built from a registry's `matching` rules. Every text matched against
+/// (query patterns, names, descriptions, slugs) and every query go through
+/// the same normalizer, so an alias in either one lines up with its
+/// canonical term in the other. With default rules it changes nothing.
+#[derive(Debug, Clone, Default)]
+pub struct Normalizer {
+    stemming: bool,
+    /// Alias lookup key (see [`synonym_key`]) -> canonical term, normalized
+    /// and stemmed like everything else
+    synonyms: HashMap<String, String>,
+}
+
+impl Normalizer {
+    /// Build a normalizer from a registry's matching rules.
+    ///
+    /// Invalid entries (aliases that are not a single word, canonical terms
+    /// that are only stop words) are skipped; the registry loader rejects
+    /// them for the local registry, but peer registries are not validated.
+    pub fn new(rules: &MatchingRules) -> Self {
+        let mut synonyms = HashMap::new();
+        for (canonical, aliases) in &rules.synonyms {
+            let Ok(mut canonical) = normalize_text(canonical) else {
+                continue;
+            };
+            if rules.stemming {
+                canonical = stem::stem_text(&canonical);
+            }
+            for alias in aliases {
+                if let Some(key) = synonym_key(alias, rules.stemming) {
+                    synonyms.insert(key, canonical.clone());
+                }
+            }
+        }
+
+        Self {
+            stemming: rules.stemming,
+            synonyms,
+        }
+    }
+
+    /// Normalize text in `language` and apply this registry's synonyms and stemming
+    pub fn normalize(&self, text: &str, language: Language) -> Result<String, MatchError> {
+        Ok(self.rewrite(&normalize_text_in(text, language)?, language))
+    }
+
+    /// Apply stemming and synonyms to already-normalized text, word by word:
+    /// each word is stemmed (if stemming is enabled), then replaced by its
+    /// canonical term if it is an alias.
+    ///
+    /// The stemmer is English-only, so words of other languages are kept
+    /// as written; they are still stemmed to look up synonyms, so an alias
+    /// catches its plural ("correos" for "correo") in any language.
+    pub fn rewrite(&self, text: &str, language: Language) -> String {
+        if !self.stemming && self.synonyms.is_empty() {
+            return text.to_string();
+        }
+
+        text.split_whitespace()
+            .map(|word| {
+                let key = if self.stemming {
+                    stem::stem(word)
+                } else {
+                    word.to_string()
+                };
+                match self.synonyms.get(&key) {
+                    Some(canonical) => canonical.clone(),
+                    None if language == Language::English => key,
+                    None => word.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::BTreeMap;
+
+    fn rules(stemming: bool, synonyms: &[(&str, &[&str])]) -> MatchingRules {
+        MatchingRules {
+            stemming,
+            synonyms: synonyms
+                .iter()
+                .map(|(canonical, aliases)| {
+                    (
+                        canonical.to_string(),
+                        aliases.iter().map(|a| a.to_string()).collect(),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
 
     #[test]
     fn test_basic_normalization() {
@@ -129,4 +291,112 @@ mod tests {
         // All of these are stop words: "how", "to", "do", "it"
         assert!(matches!(result, Err(MatchError::QueryAllStopWords)));
     }
+
+    #[test]
+    fn test_default_normalizer_matches_normalize_text() {
+        let normalizer = Normalizer::default();
+        let result = normalizer.normalize("How do I run a Bitcoin node?", Language::English).unwrap();
+        assert_eq!(result, normalize_text("How do I run a Bitcoin node?").unwrap());
+    }
+
+    #[test]
+    fn test_stemming_stage() {
+        let normalizer = Normalizer::new(&rules(true, &[]));
+        let result = normalizer.normalize("running nodes", Language::English).unwrap();
+        assert_eq!(result, normalizer.normalize("run node", Language::English).unwrap());
+    }
+
+    #[test]
+    fn test_synonym_expansion() {
+        let normalizer = Normalizer::new(&rules(false, &[("email", &["mail"])]));
+        assert_eq!(normalizer.normalize("mail server", Language::English).unwrap(), "email server");
+    }
+
+    #[test]
+    fn test_multi_word_canonical() {
+        let normalizer = Normalizer::new(&rules(false, &[("self hosted", &["selfhosted"])]));
+        // "self-hosted" loses its hyphen during normalization
+        assert_eq!(
+            normalizer.normalize("self-hosted mail", Language::English).unwrap(),
+            "self hosted mail"
+        );
+    }
+
+    #[test]
+    fn test_stemmed_alias_catches_inflections() {
+        let normalizer = Normalizer::new(&rules(true, &[("password", &["credential"])]));
+        assert_eq!(normalizer.normalize("credentials", Language::English).unwrap(), "password");
+    }
+
+    #[test]
+    fn test_multi_word_alias_ignored() {
+        let normalizer = Normalizer::new(&rules(false, &[("email", &["electronic mail"])]));
+        assert_eq!(
+            normalizer.normalize("electronic mail", Language::English).unwrap(),
+            "electronic mail"
+        );
+    }
+
+    #[test]
+    fn test_synonym_key() {
+        assert_eq!(synonym_key("E-Mail", false).as_deref(), Some("email"));
+        assert_eq!(synonym_key("credentials", true).as_deref(), Some("credenti"));
+        assert_eq!(synonym_key("electronic mail", false), None);
+        assert_eq!(synonym_key("the", false), None);
+    }
+
+    #[test]
+    fn test_spanish_stop_words() {
+        let result = normalize_text_in("¿Cómo configuro un nodo de Bitcoin en casa?", Language::Spanish).unwrap();
+        // "un", "de" and "en" are Spanish stop words; accents survive
+        assert_eq!(result, "cómo configuro nodo bitcoin casa");
+    }
+
+    #[test]
+    fn test_german_stop_words() {
+        let result = normalize_text_in("Wie betreibe ich einen Bitcoin-Knoten?", Language::German).unwrap();
+        // "wie", "ich" and "einen" are German stop words
+        assert_eq!(result, "betreibe bitcoinknoten");
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(detect_language("how do I run a bitcoin node"), Language::English);
+        assert_eq!(detect_language("servidor de correo para mi casa"), Language::Spanish);
+        assert_eq!(detect_language("Wie lerne ich Rust?"), Language::German);
+    }
+
+    #[test]
+    fn test_detect_language_defaults_to_english() {
+        // No stop words in any language
+        assert_eq!(detect_language("learn rust"), Language::English);
+        assert_eq!(detect_language("bitcoin"), Language::English);
+    }
+
+    #[test]
+    fn test_stemming_keeps_other_languages_as_written() {
+        let normalizer = Normalizer::new(&rules(true, &[]));
+        assert_eq!(
+            normalizer.normalize("nodos bitcoin", Language::Spanish).unwrap(),
+            "nodos bitcoin"
+        );
+    }
+
+    #[test]
+    fn test_synonyms_apply_in_every_language() {
+        let normalizer = Normalizer::new(&rules(true, &[("email", &["correo"])]));
+        assert_eq!(
+            normalizer.normalize("servidor de correos", Language::Spanish).unwrap(),
+            "servidor email"
+        );
+    }
+
+    #[test]
+    fn test_contains_words() {
+        assert!(contains_words("remov rust metal", "remov rust"));
+        assert!(contains_words("rust remov", "remov rust"));
+        assert!(!contains_words("learn rust", "remov rust"));
+        // Whole words only
+        assert!(!contains_words("rusty belt", "rust belt"));
+    }
 }
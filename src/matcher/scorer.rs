@@ -1,148 +1,312 @@
-use super::config::MatchConfig;
+use super::config::{MatchConfig, MatchMode};
 use super::error::MatchError;
+use super::index::{IndexedCategory, MatchIndex};
+use super::language::Language;
 use super::normalize;
-use crate::registry::types::{Category, Registry};
+use crate::registry::types::Category;
 
 /// Result of a successful query match
 ///
-/// Contains the slug, score, and full category data for the best matching category.
+/// Contains the slug, score, and full category data for a matching category,
+/// plus the per-surface scores that produced the final score.
 #[derive(Debug, Clone)]
 pub struct MatchResult {
     /// The matched category slug
     pub slug: String,
     /// The match score (0.0 to 1.0)
-    /// Used in tests and available for debugging/logging
-    #[allow(dead_code)]
     pub score: f64,
     /// Clone of the matched category
     pub category: Category,
+    /// Per-surface scores behind `score`
+    pub breakdown: ScoreBreakdown,
+    /// Language the query was matched in
+    pub language: Language,
 }
 
-/// Match a query against the registry and return the best match
+/// Per-surface scores for one category against one query
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    /// Fuzzy similarity against the slug (hyphens as spaces)
+    pub slug: f64,
+    /// Fuzzy similarity against the lowercased category name
+    pub name: f64,
+    /// Fuzzy similarity against each normalized query pattern, in registry order.
+    /// Patterns that normalize to nothing are omitted.
+    pub patterns: Vec<PatternScore>,
+    /// Best fuzzy similarity across slug, name, and patterns
+    pub fuzzy: f64,
+    /// Fraction of slug terms found in the query
+    pub keyword: f64,
+    /// BM25 score (0.0 to 1.0); `None` in fuzzy mode, where it is not computed
+    pub bm25: Option<f64>,
+    /// First negative pattern (as written in the registry) whose every word
+    /// is in the query; the score is lowered by [`NEGATIVE_PATTERN_PENALTY`]
+    pub negative_pattern: Option<String>,
+}
+
+/// Fuzzy similarity of the query against one query pattern
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternScore {
+    /// The pattern as written in the registry
+    pub pattern: String,
+    /// Normalized Levenshtein similarity (0.0 to 1.0)
+    pub score: f64,
+}
+
+/// Amount subtracted from a category's score when the query contains one of
+/// its negative patterns. Large enough that only a near-perfect match still
+/// clears the default threshold.
+pub const NEGATIVE_PATTERN_PENALTY: f64 = 0.5;
+
+/// Runners-up suggested when no category reaches its threshold and the
+/// closest one has no related or parent links
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Match a query against an indexed registry and return the best match
 ///
 /// # Arguments
 /// * `query` - User query string (will be normalized)
-/// * `registry` - Registry containing categories to match against
+/// * `index` - MatchIndex built from the registry's categories
 /// * `config` - Match configuration (threshold, weights)
 ///
 /// # Returns
 /// * `Ok(MatchResult)` - Best match if score >= threshold
 /// * `Err(MatchError::EmptyQuery)` - Query is empty
 /// * `Err(MatchError::QueryAllStopWords)` - Query contains only stop words
-/// * `Err(MatchError::BelowThreshold)` - Best score < threshold
+/// * `Err(MatchError::NoCategories)` - The index has no categories
+/// * `Err(MatchError::BelowThreshold)` - No category reached its threshold
 ///
 /// # Scoring
-/// Combines fuzzy similarity (normalized Levenshtein) and keyword boosting:
+/// In fuzzy mode (the default), combines fuzzy similarity (normalized
+/// Levenshtein) and keyword boosting:
 /// - Fuzzy: Best match across query_patterns, slug, and name
 /// - Keyword: Fraction of slug terms found in query
 /// - Final: `(fuzzy_weight * fuzzy) + (keyword_weight * keyword)`
+///
+/// In bm25 mode the final score is the BM25 token score over name,
+/// description, slug and query_patterns. Hybrid mode blends the two:
+/// `(1 - bm25_weight) * fuzzy_final + bm25_weight * bm25`.
+///
+/// In every mode, a category whose negative pattern is contained in the
+/// query loses [`NEGATIVE_PATTERN_PENALTY`], and a category's own
+/// `match_threshold` replaces `config.match_threshold` for it.
+///
+/// The query's language is detected from its stop words; see
+/// [`match_query_top_k`].
 pub fn match_query(
     query: &str,
-    registry: &Registry,
+    index: &MatchIndex,
     config: &MatchConfig,
 ) -> Result<MatchResult, MatchError> {
-    // Stage 1: Normalize query (propagates EmptyQuery/QueryAllStopWords errors)
-    let normalized_query = normalize::normalize_text(query)?;
+    let mut results = match_query_top_k(query, index, config, 1, None)?;
+    Ok(results.remove(0))
+}
+
+/// Match a query against an indexed registry and return up to `k` matches
+///
+/// Results are sorted by score descending (ties broken by slug) and only
+/// include categories scoring at or above their threshold (their own
+/// `match_threshold`, or `config.match_threshold`). Returns the same
+/// errors as [`match_query`]; `BelowThreshold` means no category reached
+/// its threshold. A `k` of 0 is treated as 1.
+///
+/// The query is normalized with the stop words of `language`, or of the
+/// language detected from its stop words when `None`, and scored against
+/// the categories' translations in that language plus their English text.
+pub fn match_query_top_k(
+    query: &str,
+    index: &MatchIndex,
+    config: &MatchConfig,
+    k: usize,
+    language: Option<Language>,
+) -> Result<Vec<MatchResult>, MatchError> {
+    // Stage 1: Normalize query in its language with the registry's synonyms
+    // and stemming (propagates EmptyQuery/QueryAllStopWords errors)
+    let language = language.unwrap_or_else(|| normalize::detect_language(query));
+    let normalized_query = index.normalizer.normalize(query, language)?;
+    let view = index.language(language);
 
     // Stage 2: Score all categories
-    let mut scores: Vec<(String, f64, Category)> = registry
+    let use_bm25 = config.match_mode != MatchMode::Fuzzy;
+    let mut scored: Vec<(&IndexedCategory, ScoreBreakdown, f64)> = view
         .categories
         .iter()
-        .map(|(slug, category)| {
-            let score = calculate_score(&normalized_query, slug, category, config);
-            (slug.clone(), score, category.clone())
+        .map(|entry| {
+            let mut breakdown = calculate_breakdown(&normalized_query, entry);
+            if use_bm25 {
+                breakdown.bm25 = Some(view.bm25.score(&normalized_query, &entry.slug));
+            }
+            let score = combine(&breakdown, config);
+            (entry, breakdown, score)
         })
         .collect();
 
-    // Stage 3: Sort by score descending
-    scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-    // Get best match
-    let (best_slug, best_score, best_category) = scores
-        .first()
-        .expect("Registry should have at least one category");
+    // Stage 3: Sort by score descending, then slug for a stable order
+    scored.sort_by(|a, b| {
+        b.2.partial_cmp(&a.2)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.0.slug.cmp(&b.0.slug))
+    });
+
+    // Stage 4: Threshold check, per category where the registry sets one
+    let threshold = |entry: &IndexedCategory| {
+        entry
+            .category
+            .match_threshold
+            .unwrap_or(config.match_threshold)
+    };
+    if !scored
+        .iter()
+        .any(|(entry, _, score)| *score >= threshold(entry))
+    {
+        // Get best match; a registry without categories has none
+        let Some((best, _, best_score)) = scored.first() else {
+            return Err(MatchError::NoCategories);
+        };
 
-    // Stage 4: Threshold check
-    if *best_score >= config.match_threshold {
-        Ok(MatchResult {
-            slug: best_slug.clone(),
-            score: *best_score,
-            category: best_category.clone(),
-        })
-    } else {
-        // Collect all slugs for error message
-        let all_slugs: Vec<String> = registry.categories.keys().cloned().collect();
+        // Suggest the closest category's curated links, or failing that
+        // the runners-up
+        let mut suggestions: Vec<String> = best
+            .category
+            .related
+            .iter()
+            .chain(&best.category.parent)
+            .cloned()
+            .collect();
+        if suggestions.is_empty() {
+            suggestions = scored
+                .iter()
+                .skip(1)
+                .take(MAX_SUGGESTIONS)
+                .map(|(entry, _, _)| entry.slug.clone())
+                .collect();
+        }
 
-        Err(MatchError::BelowThreshold {
-            threshold: config.match_threshold,
-            closest_slug: best_slug.clone(),
+        return Err(MatchError::BelowThreshold {
+            threshold: threshold(best),
+            closest_slug: best.slug.clone(),
             closest_score: *best_score,
-            all_slugs,
-        })
+            suggestions,
+            normalized_query,
+        });
     }
-}
 
-/// Calculate fuzzy similarity score across all match surfaces
-fn calculate_fuzzy_score(query: &str, slug: &str, category: &Category) -> f64 {
-    let mut max_score: f64 = 0.0;
+    Ok(scored
+        .into_iter()
+        .filter(|(entry, _, score)| *score >= threshold(entry))
+        .take(k.max(1))
+        .map(|(entry, breakdown, score)| MatchResult {
+            slug: entry.slug.clone(),
+            score,
+            category: entry.category.clone(),
+            breakdown,
+            language,
+        })
+        .collect())
+}
 
+/// Score every match surface of a category against a normalized query
+fn calculate_breakdown(query: &str, entry: &IndexedCategory) -> ScoreBreakdown {
     // Surface 1: Compare against slug with hyphens replaced by spaces
-    let slug_as_text = slug.replace('-', " ");
-    max_score = max_score.max(strsim::normalized_levenshtein(query, &slug_as_text));
+    let slug_score = strsim::normalized_levenshtein(query, &entry.slug_text);
 
     // Surface 2: Compare against category name lowercased
-    let name_lower = category.name.to_lowercase();
-    max_score = max_score.max(strsim::normalized_levenshtein(query, &name_lower));
-
-    // Surface 3: Compare against each query pattern (normalized)
-    for pattern in &category.query_patterns {
-        // Normalize pattern before comparison
-        if let Ok(normalized_pattern) = normalize::normalize_text(pattern) {
-            let score = strsim::normalized_levenshtein(query, &normalized_pattern);
-            max_score = max_score.max(score);
-        }
-    }
+    let name_score = strsim::normalized_levenshtein(query, &entry.name_lower);
 
-    max_score
+    // Surface 3: Compare against each query pattern (normalized at index time)
+    let patterns: Vec<PatternScore> = entry
+        .patterns
+        .iter()
+        .map(|pattern| PatternScore {
+            pattern: pattern.pattern.clone(),
+            score: strsim::normalized_levenshtein(query, &pattern.normalized),
+        })
+        .collect();
+
+    let fuzzy = patterns
+        .iter()
+        .map(|p| p.score)
+        .fold(slug_score.max(name_score), f64::max);
+
+    // Penalty: the first negative pattern whose words are all in the query
+    let negative_pattern = entry
+        .negative_patterns
+        .iter()
+        .find(|pattern| normalize::contains_words(query, &pattern.normalized))
+        .map(|pattern| pattern.pattern.clone());
+
+    ScoreBreakdown {
+        slug: slug_score,
+        name: name_score,
+        patterns,
+        fuzzy,
+        keyword: calculate_keyword_score(query, &entry.slug_terms),
+        bm25: None,
+        negative_pattern,
+    }
 }
 
 /// Calculate keyword boost score based on slug term presence in query
-fn calculate_keyword_score(query: &str, slug: &str) -> f64 {
-    // Split slug on hyphens to get slug terms
-    let slug_terms: Vec<&str> = slug.split('-').collect();
+fn calculate_keyword_score(query: &str, slug_terms: &[String]) -> f64 {
     let total_terms = slug_terms.len() as f64;
 
     // Count how many slug terms appear in the query
     let matches = slug_terms
         .iter()
-        .filter(|term| query.contains(*term))
+        .filter(|term| query.contains(term.as_str()))
         .count() as f64;
 
     // Return fraction of slug terms found in query
     matches / total_terms
 }
 
+/// Combine surface scores according to the configured mode and weights
+fn combine(breakdown: &ScoreBreakdown, config: &MatchConfig) -> f64 {
+    // Weighted sum combination
+    let fuzzy = (config.match_fuzzy_weight * breakdown.fuzzy)
+        + (config.match_keyword_weight * breakdown.keyword);
+
+    // Clamp to [0.0, 1.0] range
+    let fuzzy = fuzzy.min(1.0);
+    let bm25 = breakdown.bm25.unwrap_or(0.0);
+
+    let score = match config.match_mode {
+        MatchMode::Fuzzy => fuzzy,
+        MatchMode::Bm25 => bm25,
+        MatchMode::Hybrid => {
+            (1.0 - config.match_bm25_weight) * fuzzy + config.match_bm25_weight * bm25
+        }
+    };
+
+    if breakdown.negative_pattern.is_some() {
+        (score - NEGATIVE_PATTERN_PENALTY).max(0.0)
+    } else {
+        score
+    }
+}
+
 /// Calculate combined score using weighted sum
+#[cfg(test)]
 fn calculate_score(
     query: &str,
     slug: &str,
     category: &Category,
     config: &MatchConfig,
 ) -> f64 {
-    let fuzzy_score = calculate_fuzzy_score(query, slug, category);
-    let keyword_score = calculate_keyword_score(query, slug);
-
-    // Weighted sum combination
-    let combined = (config.match_fuzzy_weight * fuzzy_score)
-        + (config.match_keyword_weight * keyword_score);
-
-    // Clamp to [0.0, 1.0] range
-    combined.min(1.0)
+    let entry = IndexedCategory::new(
+        slug,
+        category,
+        &normalize::Normalizer::default(),
+        Language::English,
+    );
+    combine(&calculate_breakdown(query, &entry), config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::registry::types::MatchingRules;
+    use crate::registry::Registry;
     use approx::assert_relative_eq;
 
     fn load_test_registry() -> Registry {
@@ -150,20 +314,27 @@ mod tests {
         serde_json::from_str(json_content).expect("Failed to parse registry.json")
     }
 
+    fn load_test_index() -> MatchIndex {
+        let registry = load_test_registry();
+        MatchIndex::new(&registry.categories, &registry.matching)
+    }
+
     fn default_config() -> MatchConfig {
         MatchConfig {
             match_threshold: 0.4,
             match_fuzzy_weight: 0.7,
             match_keyword_weight: 0.3,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
         }
     }
 
     #[test]
     fn test_learn_rust_matches_rust_learning() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
-        let result = match_query("learn rust", &registry, &config).unwrap();
+        let result = match_query("learn rust", &index, &config).unwrap();
 
         assert_eq!(result.slug, "rust-learning");
         assert!(result.score > 0.4, "Score should be above threshold");
@@ -172,10 +343,10 @@ mod tests {
 
     #[test]
     fn test_bitcoin_node_matches_bitcoin_node_setup() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
-        let result = match_query("bitcoin node", &registry, &config).unwrap();
+        let result = match_query("bitcoin node", &index, &config).unwrap();
 
         assert_eq!(result.slug, "bitcoin-node-setup");
         assert!(result.score > 0.4, "Score should be above threshold");
@@ -184,10 +355,10 @@ mod tests {
 
     #[test]
     fn test_email_server_matches_self_hosted_email() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
-        let result = match_query("email server", &registry, &config).unwrap();
+        let result = match_query("email server", &index, &config).unwrap();
 
         assert_eq!(result.slug, "self-hosted-email");
         assert!(result.score > 0.4, "Score should be above threshold");
@@ -196,22 +367,26 @@ mod tests {
 
     #[test]
     fn test_below_threshold_returns_error() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
-        let result = match_query("quantum physics supercollider", &registry, &config);
+        let result = match_query("quantum physics supercollider", &index, &config);
 
         match result {
             Err(MatchError::BelowThreshold {
                 threshold,
                 closest_slug,
                 closest_score,
-                all_slugs,
+                suggestions,
+                normalized_query,
             }) => {
                 assert_relative_eq!(threshold, 0.4, epsilon = 1e-6);
                 assert!(!closest_slug.is_empty());
                 assert!(closest_score < 0.4, "Closest score should be below threshold");
-                assert_eq!(all_slugs.len(), 10, "Should return all 10 category slugs");
+                assert!(!suggestions.is_empty(), "Should suggest other categories");
+                assert!(suggestions.len() < 10, "Should not list every category");
+                assert!(!suggestions.contains(&closest_slug));
+                assert!(normalized_query.starts_with("quantum "));
             }
             _ => panic!("Expected BelowThreshold error"),
         }
@@ -231,6 +406,8 @@ mod tests {
             match_threshold: 0.4,
             match_fuzzy_weight: 0.7,
             match_keyword_weight: 0.3,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
         };
 
         // Config without keyword boosting (fuzzy only)
@@ -238,6 +415,8 @@ mod tests {
             match_threshold: 0.4,
             match_fuzzy_weight: 0.7,
             match_keyword_weight: 0.0,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
         };
 
         // Calculate scores directly to verify keyword boost effect
@@ -264,32 +443,32 @@ mod tests {
 
     #[test]
     fn test_empty_query_returns_error() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
-        let result = match_query("", &registry, &config);
+        let result = match_query("", &index, &config);
 
         assert!(matches!(result, Err(MatchError::EmptyQuery)));
     }
 
     #[test]
     fn test_all_stop_words_returns_error() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
-        let result = match_query("the a an", &registry, &config);
+        let result = match_query("the a an", &index, &config);
 
         assert!(matches!(result, Err(MatchError::QueryAllStopWords)));
     }
 
     #[test]
     fn test_best_match_wins() {
-        let registry = load_test_registry();
+        let index = load_test_index();
         let config = default_config();
 
         // This query could potentially match multiple categories,
         // but we should get exactly one result: the highest scorer
-        let result = match_query("rust programming", &registry, &config).unwrap();
+        let result = match_query("rust programming", &index, &config).unwrap();
 
         // We should get a single result with the highest score
         assert_eq!(result.slug, "rust-learning");
@@ -297,4 +476,428 @@ mod tests {
         // Verify we got exactly one result (not a list)
         assert!(!result.category.sources.is_empty());
     }
+
+    #[test]
+    fn test_top_k_returns_sorted_matches() {
+        let index = load_test_index();
+        let config = MatchConfig {
+            match_threshold: 0.1,
+            ..default_config()
+        };
+
+        let results = match_query_top_k("self host", &index, &config, 3, None).unwrap();
+
+        assert!(results.len() > 1, "Ambiguous query should return several matches");
+        assert!(results.len() <= 3);
+        for pair in results.windows(2) {
+            assert!(pair[0].score >= pair[1].score, "Results should be sorted by score");
+        }
+    }
+
+    #[test]
+    fn test_top_k_first_result_matches_match_query() {
+        let index = load_test_index();
+        let config = default_config();
+
+        let best = match_query("learn rust", &index, &config).unwrap();
+        let top = match_query_top_k("learn rust", &index, &config, 5, None).unwrap();
+
+        assert_eq!(top[0].slug, best.slug);
+        assert_relative_eq!(top[0].score, best.score, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_top_k_excludes_below_threshold() {
+        let index = load_test_index();
+        let config = default_config();
+
+        let results = match_query_top_k("learn rust", &index, &config, 10, None).unwrap();
+
+        assert!(results.iter().all(|r| r.score >= config.match_threshold));
+    }
+
+    #[test]
+    fn test_top_k_zero_treated_as_one() {
+        let index = load_test_index();
+        let config = default_config();
+
+        let results = match_query_top_k("learn rust", &index, &config, 0, None).unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_top_k_below_threshold_error() {
+        let index = load_test_index();
+        let config = default_config();
+
+        let result = match_query_top_k("quantum physics supercollider", &index, &config, 3, None);
+        assert!(matches!(result, Err(MatchError::BelowThreshold { .. })));
+    }
+
+    #[test]
+    fn test_empty_index_has_no_categories() {
+        let index = MatchIndex::new(&std::collections::HashMap::new(), &MatchingRules::default());
+
+        let result = match_query("learn rust", &index, &default_config());
+        assert!(matches!(result, Err(MatchError::NoCategories)));
+    }
+
+    #[test]
+    fn test_breakdown_explains_score() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        let config = default_config();
+
+        let result = match_query("bitcoin node", &index, &config).unwrap();
+        let breakdown = &result.breakdown;
+        let category = registry.categories.get("bitcoin-node-setup").unwrap();
+
+        // One pattern score per (non-empty) registry pattern
+        assert_eq!(breakdown.patterns.len(), category.query_patterns.len());
+
+        // Fuzzy is the best surface
+        let best_pattern = breakdown.patterns.iter().map(|p| p.score).fold(0.0, f64::max);
+        assert_relative_eq!(
+            breakdown.fuzzy,
+            best_pattern.max(breakdown.slug).max(breakdown.name),
+            epsilon = 1e-12
+        );
+
+        // "bitcoin" and "node" are 2 of the 3 slug terms
+        assert_relative_eq!(breakdown.keyword, 2.0 / 3.0, epsilon = 1e-12);
+
+        // Final score is the weighted combination
+        assert_relative_eq!(
+            result.score,
+            (0.7 * breakdown.fuzzy + 0.3 * breakdown.keyword).min(1.0),
+            epsilon = 1e-12
+        );
+    }
+
+    fn bm25_config(mode: MatchMode) -> MatchConfig {
+        MatchConfig {
+            match_mode: mode,
+            ..default_config()
+        }
+    }
+
+    #[test]
+    fn test_bm25_mode_matches_expected_categories() {
+        let index = load_test_index();
+        let config = bm25_config(MatchMode::Bm25);
+
+        for (query, expected) in [
+            ("learn rust", "rust-learning"),
+            ("bitcoin node", "bitcoin-node-setup"),
+            ("email server", "self-hosted-email"),
+        ] {
+            let result = match_query(query, &index, &config).unwrap();
+            assert_eq!(result.slug, expected, "query {:?}", query);
+        }
+    }
+
+    #[test]
+    fn test_bm25_mode_below_threshold() {
+        let index = load_test_index();
+        let config = bm25_config(MatchMode::Bm25);
+
+        let result = match_query("quantum physics supercollider", &index, &config);
+        assert!(matches!(result, Err(MatchError::BelowThreshold { .. })));
+    }
+
+    #[test]
+    fn test_bm25_scores_long_queries_higher() {
+        let index = load_test_index();
+        let query = "recommended resources for learning the rust programming language as a beginner";
+
+        let fuzzy = match_query(query, &index, &default_config()).unwrap();
+        let bm25 = match_query(query, &index, &bm25_config(MatchMode::Bm25)).unwrap();
+
+        assert_eq!(fuzzy.slug, "rust-learning");
+        assert_eq!(bm25.slug, "rust-learning");
+        assert!(
+            bm25.score > fuzzy.score,
+            "BM25 {} should beat fuzzy {} on a long query",
+            bm25.score,
+            fuzzy.score
+        );
+    }
+
+    #[test]
+    fn test_hybrid_mode_blends_scores() {
+        let index = load_test_index();
+        let config = MatchConfig {
+            match_bm25_weight: 0.25,
+            ..bm25_config(MatchMode::Hybrid)
+        };
+
+        let result = match_query("email server", &index, &config).unwrap();
+        let breakdown = &result.breakdown;
+        let fuzzy = (0.7 * breakdown.fuzzy + 0.3 * breakdown.keyword).min(1.0);
+        let bm25 = breakdown.bm25.expect("Hybrid mode computes BM25");
+
+        assert_relative_eq!(result.score, 0.75 * fuzzy + 0.25 * bm25, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_fuzzy_mode_skips_bm25() {
+        let index = load_test_index();
+
+        let result = match_query("learn rust", &index, &default_config()).unwrap();
+        assert!(result.breakdown.bm25.is_none());
+    }
+
+    #[test]
+    fn test_synonyms_and_stemming_match_registry_categories() {
+        let index = load_test_index();
+
+        // Inflections and aliases that only match through registry.json's
+        // matching rules ("mail" -> "email", "btc" -> "bitcoin", ...)
+        let cases = [
+            ("mail server", "self-hosted-email"),
+            ("passwords", "password-management"),
+            ("btc node", "bitcoin-node-setup"),
+            ("ubuntu hardening", "linux-hardening"),
+            ("storing credentials", "password-management"),
+            ("learning rust", "rust-learning"),
+        ];
+
+        for mode in [MatchMode::Fuzzy, MatchMode::Bm25, MatchMode::Hybrid] {
+            let config = bm25_config(mode);
+            for (query, expected) in cases {
+                let result = match_query(query, &index, &config).unwrap();
+                assert_eq!(result.slug, expected, "{:?} mode, query {:?}", mode, query);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stemmed_query_matches_in_token_modes() {
+        let index = load_test_index();
+
+        // "running nodes" stems to "run node"; BM25 sees both tokens
+        for mode in [MatchMode::Bm25, MatchMode::Hybrid] {
+            let result = match_query("running nodes", &index, &bm25_config(mode)).unwrap();
+            assert_eq!(result.slug, "bitcoin-node-setup", "{:?} mode", mode);
+        }
+    }
+
+    #[test]
+    fn test_inflected_query_misses_without_rules() {
+        let registry = load_test_registry();
+        let index = MatchIndex::new(&registry.categories, &MatchingRules::default());
+
+        // Without stemming "passwords" shares no token with any category
+        let result = match_query("passwords", &index, &bm25_config(MatchMode::Bm25));
+        assert!(matches!(result, Err(MatchError::BelowThreshold { .. })));
+    }
+
+    #[test]
+    fn test_detected_language_uses_translations() {
+        let index = load_test_index();
+        let config = default_config();
+
+        let result = match_query("¿Cómo configuro un nodo de Bitcoin?", &index, &config).unwrap();
+        assert_eq!(result.slug, "bitcoin-node-setup");
+        assert_eq!(result.language, Language::Spanish);
+        assert_eq!(result.category.name, "Configuración de un nodo Bitcoin");
+
+        let result = match_query("Wie lerne ich Rust?", &index, &config).unwrap();
+        assert_eq!(result.slug, "rust-learning");
+        assert_eq!(result.language, Language::German);
+        assert_eq!(result.category.name, "Rust lernen");
+    }
+
+    #[test]
+    fn test_localized_queries_match_in_every_mode() {
+        let index = load_test_index();
+
+        let cases = [
+            ("mi propio servidor de correo", "self-hosted-email"),
+            ("gestor de contraseñas", "password-management"),
+            ("montar un relay de nostr", "nostr-development"),
+            ("Passwortmanager für die Familie", "password-management"),
+        ];
+
+        for mode in [MatchMode::Fuzzy, MatchMode::Bm25, MatchMode::Hybrid] {
+            for (query, expected) in cases {
+                let result = match_query(query, &index, &bm25_config(mode)).unwrap();
+                assert_eq!(result.slug, expected, "{:?} mode, query {:?}", mode, query);
+            }
+        }
+    }
+
+    #[test]
+    fn test_explicit_language_overrides_detection() {
+        let index = load_test_index();
+        let config = default_config();
+
+        // No German stop words, so detection falls back to English and misses
+        let query = "Passwörter sicher speichern";
+        assert!(match_query(query, &index, &config).is_err());
+
+        let results =
+            match_query_top_k(query, &index, &config, 1, Some(Language::German)).unwrap();
+        assert_eq!(results[0].slug, "password-management");
+        assert_eq!(results[0].language, Language::German);
+        assert_eq!(results[0].category.name, "Passwortverwaltung");
+    }
+
+    #[test]
+    fn test_english_queries_keep_english_text() {
+        let index = load_test_index();
+
+        let result = match_query("learn rust", &index, &default_config()).unwrap();
+        assert_eq!(result.language, Language::English);
+        assert_eq!(result.category.name, "Rust Learning");
+    }
+
+    #[test]
+    fn test_unrelated_localized_queries_rejected() {
+        let index = load_test_index();
+        let config = default_config();
+
+        for query in ["física cuántica de partículas", "Quantenphysik und Teilchenbeschleuniger"] {
+            let result = match_query(query, &index, &config);
+            assert!(
+                matches!(result, Err(MatchError::BelowThreshold { .. })),
+                "{:?} should not match",
+                query
+            );
+        }
+    }
+
+    fn index_with_thresholds(thresholds: &[(&str, f64)]) -> MatchIndex {
+        let mut registry = load_test_registry();
+        for (slug, threshold) in thresholds {
+            registry.categories.get_mut(*slug).unwrap().match_threshold = Some(*threshold);
+        }
+        MatchIndex::new(&registry.categories, &registry.matching)
+    }
+
+    #[test]
+    fn test_negative_patterns_penalize_in_every_mode() {
+        // Seed registry: rust-learning has negative patterns "remove rust",
+        // "rust stain" and "rust belt" (the docs/SCHEMA.md example)
+        let index = load_test_index();
+
+        for mode in [MatchMode::Fuzzy, MatchMode::Bm25, MatchMode::Hybrid] {
+            let config = bm25_config(mode);
+            for query in ["how do I remove rust from my bike", "rust removal", "rust belt"] {
+                let result = match_query(query, &index, &config);
+                assert!(
+                    matches!(result, Err(MatchError::BelowThreshold { .. })),
+                    "{:?} mode should reject {:?}",
+                    mode,
+                    query
+                );
+            }
+
+            // Queries without every word of a negative pattern are unaffected
+            let result = match_query("learn rust", &index, &config).unwrap();
+            assert_eq!(result.slug, "rust-learning");
+            assert_eq!(result.breakdown.negative_pattern, None);
+        }
+    }
+
+    #[test]
+    fn test_negative_pattern_subtracts_penalty() {
+        let index = load_test_index();
+        let config = MatchConfig {
+            match_threshold: 0.0,
+            ..default_config()
+        };
+
+        let results = match_query_top_k("rust belt", &index, &config, 10, None).unwrap();
+        let rust = results.iter().find(|r| r.slug == "rust-learning").unwrap();
+        assert_eq!(rust.breakdown.negative_pattern.as_deref(), Some("rust belt"));
+
+        let fuzzy = (0.7 * rust.breakdown.fuzzy + 0.3 * rust.breakdown.keyword).min(1.0);
+        assert_relative_eq!(
+            rust.score,
+            (fuzzy - NEGATIVE_PATTERN_PENALTY).max(0.0),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_category_threshold_raises_bar() {
+        // docs/SCHEMA.md example: rust-learning with match_threshold 0.5
+        let index = index_with_thresholds(&[("rust-learning", 0.5)]);
+        let config = default_config();
+
+        // "rust" alone scores about 0.43: enough globally, not for rust-learning
+        assert!(match_query("rust", &load_test_index(), &config).is_ok());
+        match match_query("rust", &index, &config) {
+            Err(MatchError::BelowThreshold {
+                threshold,
+                closest_slug,
+                ..
+            }) => {
+                assert_eq!(closest_slug, "rust-learning");
+                assert_eq!(threshold, 0.5);
+            }
+            other => panic!("expected BelowThreshold, got {:?}", other),
+        }
+
+        assert_eq!(match_query("learn rust", &index, &config).unwrap().slug, "rust-learning");
+    }
+
+    #[test]
+    fn test_below_threshold_suggests_related_categories() {
+        let index = index_with_thresholds(&[("rust-learning", 0.5)]);
+        match match_query("rust", &index, &default_config()) {
+            Err(MatchError::BelowThreshold { suggestions, .. }) => {
+                assert_eq!(suggestions, vec!["pubky-development", "mcp-development"]);
+            }
+            other => panic!("expected BelowThreshold, got {:?}", other),
+        }
+
+        // Without links, the next closest categories are suggested instead
+        let mut registry = load_test_registry();
+        for category in registry.categories.values_mut() {
+            category.related.clear();
+            category.match_threshold = Some(0.5);
+        }
+        let index = MatchIndex::new(&registry.categories, &registry.matching);
+        match match_query("rust", &index, &default_config()) {
+            Err(MatchError::BelowThreshold {
+                closest_slug,
+                suggestions,
+                ..
+            }) => {
+                assert_eq!(closest_slug, "rust-learning");
+                assert_eq!(suggestions.len(), MAX_SUGGESTIONS);
+                assert!(!suggestions.contains(&closest_slug));
+            }
+            other => panic!("expected BelowThreshold, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_category_threshold_lowers_bar() {
+        // docs/SCHEMA.md example: home-automation-private with match_threshold 0.3
+        let config = bm25_config(MatchMode::Hybrid);
+        let query = "smart thermostat";
+
+        assert!(match_query(query, &load_test_index(), &config).is_err());
+
+        let index = index_with_thresholds(&[("home-automation-private", 0.3)]);
+        let result = match_query(query, &index, &config).unwrap();
+        assert_eq!(result.slug, "home-automation-private");
+        assert!(result.score < config.match_threshold);
+    }
+
+    #[test]
+    fn test_category_threshold_applies_to_alternatives() {
+        // In hybrid mode "secure server" ranks linux-hardening,
+        // self-hosted-email, mcp-development and password-management, all
+        // above 0.4 but below 0.9
+        let config = bm25_config(MatchMode::Hybrid);
+        let index = index_with_thresholds(&[("self-hosted-email", 0.9)]);
+
+        let results = match_query_top_k("secure server", &index, &config, 3, None).unwrap();
+        let slugs: Vec<&str> = results.iter().map(|r| r.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["linux-hardening", "mcp-development", "password-management"]);
+    }
 }
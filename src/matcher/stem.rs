@@ -0,0 +1,288 @@
+//! Porter stemmer (M.F. Porter, "An algorithm for suffix stripping", 1980).
+//!
+//! Reduces English words to a common stem so that "running", "runs" and
+//! "run" compare equal. Only lowercase ASCII words are stemmed; anything
+//! else (digits, non-ASCII, words of two letters or fewer) is returned as is.
+
+/// Stem a single lowercase word
+pub fn stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+
+    let mut w = word.as_bytes().to_vec();
+    step1a(&mut w);
+    step1b(&mut w);
+    step1c(&mut w);
+    step2(&mut w);
+    step3(&mut w);
+    step4(&mut w);
+    step5(&mut w);
+
+    // Only ASCII lowercase bytes were ever written
+    String::from_utf8(w).expect("stemmer output is ASCII")
+}
+
+/// Stem every whitespace-separated word of already-normalized text
+pub fn stem_text(text: &str) -> String {
+    text.split_whitespace().map(stem).collect::<Vec<_>>().join(" ")
+}
+
+/// True if w[i] is a consonant
+fn is_consonant(w: &[u8], i: usize) -> bool {
+    match w[i] {
+        b'a' | b'e' | b'i' | b'o' | b'u' => false,
+        b'y' => i == 0 || !is_consonant(w, i - 1),
+        _ => true,
+    }
+}
+
+/// Number of VC sequences in w[..len], i.e. m in [C](VC)^m[V]
+fn measure(w: &[u8], len: usize) -> usize {
+    let mut m = 0;
+    let mut i = 0;
+
+    // Skip the optional leading consonants
+    while i < len && is_consonant(w, i) {
+        i += 1;
+    }
+    while i < len {
+        // Vowel run
+        while i < len && !is_consonant(w, i) {
+            i += 1;
+        }
+        if i >= len {
+            break;
+        }
+        // Consonant run closes one VC
+        while i < len && is_consonant(w, i) {
+            i += 1;
+        }
+        m += 1;
+    }
+    m
+}
+
+/// True if w[..len] contains a vowel
+fn has_vowel(w: &[u8], len: usize) -> bool {
+    (0..len).any(|i| !is_consonant(w, i))
+}
+
+/// True if w[..len] ends with a double consonant
+fn ends_double_consonant(w: &[u8], len: usize) -> bool {
+    len >= 2 && w[len - 1] == w[len - 2] && is_consonant(w, len - 1)
+}
+
+/// True if w[..len] ends consonant-vowel-consonant, the last not w, x or y
+fn ends_cvc(w: &[u8], len: usize) -> bool {
+    len >= 3
+        && is_consonant(w, len - 3)
+        && !is_consonant(w, len - 2)
+        && is_consonant(w, len - 1)
+        && !matches!(w[len - 1], b'w' | b'x' | b'y')
+}
+
+/// Length of the stem left after removing `suffix`, if w ends with it
+fn stem_len(w: &[u8], suffix: &str) -> Option<usize> {
+    w.ends_with(suffix.as_bytes()).then(|| w.len() - suffix.len())
+}
+
+/// Cut w back to its first `stem` bytes and append `replacement`
+fn replace_suffix(w: &mut Vec<u8>, stem: usize, replacement: &str) {
+    w.truncate(stem);
+    w.extend_from_slice(replacement.as_bytes());
+}
+
+/// Apply the first rule whose suffix matches, if its stem has m > `min_measure`.
+/// Later rules are not tried once a suffix matches, even if its condition fails.
+fn apply_rules(w: &mut Vec<u8>, rules: &[(&str, &str)], min_measure: usize) {
+    for (suffix, replacement) in rules {
+        if let Some(stem) = stem_len(w, suffix) {
+            if measure(w, stem) > min_measure {
+                replace_suffix(w, stem, replacement);
+            }
+            return;
+        }
+    }
+}
+
+fn step1a(w: &mut Vec<u8>) {
+    if let Some(stem) = stem_len(w, "sses") {
+        replace_suffix(w, stem, "ss");
+    } else if let Some(stem) = stem_len(w, "ies") {
+        replace_suffix(w, stem, "i");
+    } else if w.ends_with(b"s") && !w.ends_with(b"ss") {
+        w.pop();
+    }
+}
+
+fn step1b(w: &mut Vec<u8>) {
+    if let Some(stem) = stem_len(w, "eed") {
+        if measure(w, stem) > 0 {
+            replace_suffix(w, stem, "ee");
+        }
+        return;
+    }
+
+    let stem = match stem_len(w, "ed").or_else(|| stem_len(w, "ing")) {
+        Some(stem) if has_vowel(w, stem) => stem,
+        _ => return,
+    };
+    w.truncate(stem);
+
+    if w.ends_with(b"at") || w.ends_with(b"bl") || w.ends_with(b"iz") {
+        w.push(b'e');
+    } else if ends_double_consonant(w, w.len()) && !matches!(w[w.len() - 1], b'l' | b's' | b'z') {
+        w.pop();
+    } else if measure(w, w.len()) == 1 && ends_cvc(w, w.len()) {
+        w.push(b'e');
+    }
+}
+
+fn step1c(w: &mut [u8]) {
+    let len = w.len();
+    if w.ends_with(b"y") && has_vowel(w, len - 1) {
+        w[len - 1] = b'i';
+    }
+}
+
+fn step2(w: &mut Vec<u8>) {
+    const RULES: [(&str, &str); 20] = [
+        ("ational", "ate"),
+        ("tional", "tion"),
+        ("enci", "ence"),
+        ("anci", "ance"),
+        ("izer", "ize"),
+        ("abli", "able"),
+        ("alli", "al"),
+        ("entli", "ent"),
+        ("eli", "e"),
+        ("ousli", "ous"),
+        ("ization", "ize"),
+        ("ation", "ate"),
+        ("ator", "ate"),
+        ("alism", "al"),
+        ("iveness", "ive"),
+        ("fulness", "ful"),
+        ("ousness", "ous"),
+        ("aliti", "al"),
+        ("iviti", "ive"),
+        ("biliti", "ble"),
+    ];
+    apply_rules(w, &RULES, 0);
+}
+
+fn step3(w: &mut Vec<u8>) {
+    const RULES: [(&str, &str); 7] = [
+        ("icate", "ic"),
+        ("ative", ""),
+        ("alize", "al"),
+        ("iciti", "ic"),
+        ("ical", "ic"),
+        ("ful", ""),
+        ("ness", ""),
+    ];
+    apply_rules(w, &RULES, 0);
+}
+
+fn step4(w: &mut Vec<u8>) {
+    const SUFFIXES: [&str; 19] = [
+        "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion",
+        "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+    ];
+
+    // Pick the longest matching suffix
+    let Some(suffix) = SUFFIXES
+        .iter()
+        .filter(|suffix| w.ends_with(suffix.as_bytes()))
+        .max_by_key(|suffix| suffix.len())
+    else {
+        return;
+    };
+    let stem = w.len() - suffix.len();
+
+    // "ion" is only removed after s or t
+    if *suffix == "ion" && !(stem > 0 && matches!(w[stem - 1], b's' | b't')) {
+        return;
+    }
+    if measure(w, stem) > 1 {
+        w.truncate(stem);
+    }
+}
+
+fn step5(w: &mut Vec<u8>) {
+    // Step 5a: remove a final e
+    if let Some(stem) = stem_len(w, "e") {
+        let m = measure(w, stem);
+        if m > 1 || (m == 1 && !ends_cvc(w, stem)) {
+            w.truncate(stem);
+        }
+    }
+
+    // Step 5b: -ll to -l when m > 1
+    let len = w.len();
+    if measure(w, len) > 1 && ends_double_consonant(w, len) && w[len - 1] == b'l' {
+        w.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_porter_examples() {
+        // Examples from Porter's paper, run through the whole algorithm
+        let cases = [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("cats", "cat"),
+            ("feed", "feed"),
+            ("agreed", "agre"),
+            ("plastered", "plaster"),
+            ("motoring", "motor"),
+            ("sing", "sing"),
+            ("hopping", "hop"),
+            ("falling", "fall"),
+            ("filing", "file"),
+            ("happy", "happi"),
+            ("relational", "relat"),
+            ("conditional", "condit"),
+            ("hopeful", "hope"),
+            ("goodness", "good"),
+            ("adjustment", "adjust"),
+            ("generalizations", "gener"),
+            ("controll", "control"),
+        ];
+
+        for (word, expected) in cases {
+            assert_eq!(stem(word), expected, "stem({:?})", word);
+        }
+    }
+
+    #[test]
+    fn test_registry_vocabulary() {
+        // Inflections used in queries collapse onto the pattern wording
+        assert_eq!(stem("running"), stem("run"));
+        assert_eq!(stem("nodes"), stem("node"));
+        assert_eq!(stem("hosting"), stem("host"));
+        assert_eq!(stem("passwords"), stem("password"));
+        assert_eq!(stem("managers"), stem("manager"));
+        assert_eq!(stem("learning"), stem("learn"));
+        assert_eq!(stem("hardened"), stem("hardening"));
+        assert_eq!(stem("servers"), stem("server"));
+    }
+
+    #[test]
+    fn test_short_and_non_ascii_words_unchanged() {
+        assert_eq!(stem("is"), "is");
+        assert_eq!(stem("mcp"), "mcp");
+        assert_eq!(stem("2fa"), "2fa");
+        assert_eq!(stem("über"), "über");
+    }
+
+    #[test]
+    fn test_stem_text() {
+        assert_eq!(stem_text("running bitcoin nodes"), "run bitcoin node");
+    }
+}
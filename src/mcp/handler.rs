@@ -1,58 +1,125 @@
-use crate::audit::AuditEntry;
-use crate::contributions::Proposal;
+use crate::analytics::QueryLog;
 use crate::federation::PeerCache;
-use crate::identity::Identity;
 use crate::matcher::MatchConfig;
+use crate::mcp::prompts::{self, PromptError};
+use crate::mcp::resources::{self, ResourceError};
 use crate::mcp::tools::{self, ToolCallError};
-use crate::mcp::types::{CallToolParams, InitializeParams, JsonRpcRequest, JsonRpcResponse};
-use crate::registry::Registry;
+use crate::mcp::types::{
+    CallToolParams, GetPromptParams, InitializeParams, JsonRpcRequest, JsonRpcResponse,
+    ReadResourceParams,
+};
+use crate::reload::DataStore;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Protocol versions this server can speak, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] =
+    &["2025-11-25", "2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Idle time after which an HTTP session expires (default)
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(3600);
+
+/// Methods a client should only call once it has sent `notifications/initialized`
+const REQUIRES_INITIALIZED: &[&str] = &["tools/call", "prompts/get", "resources/read"];
+
+/// Per-client state established by `initialize`
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// Protocol version negotiated during initialize
+    pub protocol_version: String,
+    /// `clientInfo` the client sent with initialize
+    pub client_info: Value,
+    /// Set once the client sends `notifications/initialized`
+    pub initialized: bool,
+    last_seen: Instant,
+}
+
+/// Which session a message belongs to
+#[derive(Debug, Clone, Copy)]
+pub enum SessionKey<'a> {
+    /// Single-client transport: one implicit session per handler
+    Local,
+    /// Streamable HTTP: value of the Mcp-Session-Id header, if any
+    Http(Option<&'a str>),
+}
+
+/// Result of handling a JSON-RPC message
+#[derive(Debug)]
+pub enum McpReply {
+    /// JSON-RPC response body (None for notifications).
+    /// `session_id` is set when an HTTP `initialize` created a new session.
+    Message {
+        body: Option<String>,
+        session_id: Option<String>,
+    },
+    /// HTTP message without a session id (other than initialize)
+    BadRequest(String),
+    /// HTTP session id is unknown, expired, or was ended
+    SessionNotFound(String),
+}
+
 pub struct McpHandler {
-    initialized: Arc<AtomicBool>,
-    registry: Arc<Registry>,
+    sessions: RwLock<HashMap<String, Session>>,
+    local_session: RwLock<Option<Session>>,
+    session_ttl: Duration,
+    data: Arc<DataStore>,
     match_config: MatchConfig,
     pubkey_z32: String,
-    audit_log: Arc<Vec<AuditEntry>>,
-    identities: Arc<HashMap<String, Identity>>,
-    proposals: Arc<HashMap<Uuid, Proposal>>,
     peer_cache: Arc<PeerCache>,
+    query_log: Option<Arc<QueryLog>>,
 }
 
 impl McpHandler {
     pub fn new(
-        registry: Arc<Registry>,
+        data: Arc<DataStore>,
         match_config: MatchConfig,
         pubkey_z32: String,
-        audit_log: Arc<Vec<AuditEntry>>,
-        identities: Arc<HashMap<String, Identity>>,
-        proposals: Arc<HashMap<Uuid, Proposal>>,
         peer_cache: Arc<PeerCache>,
     ) -> Self {
         Self {
-            initialized: Arc::new(AtomicBool::new(false)),
-            registry,
+            sessions: RwLock::new(HashMap::new()),
+            local_session: RwLock::new(None),
+            session_ttl: DEFAULT_SESSION_TTL,
+            data,
             match_config,
             pubkey_z32,
-            audit_log,
-            identities,
-            proposals,
             peer_cache,
+            query_log: None,
         }
     }
 
-    /// Main entry point for handling JSON-RPC requests
+    /// Override the idle expiry for HTTP sessions
+    pub fn with_session_ttl(mut self, session_ttl: Duration) -> Self {
+        self.session_ttl = session_ttl;
+        self
+    }
+
+    /// Record get_sources outcomes to an anonymous query log
+    pub fn with_query_log(mut self, query_log: Arc<QueryLog>) -> Self {
+        self.query_log = Some(query_log);
+        self
+    }
+
+    /// Handle a JSON-RPC message on a single-client transport.
     /// Returns None for notifications (no response needed)
     pub async fn handle_json(&self, raw_json: &str) -> Option<String> {
+        match self.handle_message(SessionKey::Local, raw_json).await {
+            McpReply::Message { body, .. } => body,
+            McpReply::BadRequest(body) | McpReply::SessionNotFound(body) => Some(body),
+        }
+    }
+
+    /// Main entry point for handling JSON-RPC messages
+    pub async fn handle_message(&self, key: SessionKey<'_>, raw_json: &str) -> McpReply {
         // Parse raw JSON
         let parsed: Value = match serde_json::from_str(raw_json) {
             Ok(v) => v,
             Err(_) => {
-                return Some(self.serialize_response(JsonRpcResponse::parse_error()));
+                return self.reply(JsonRpcResponse::parse_error());
             }
         };
 
@@ -62,65 +129,158 @@ impl McpHandler {
             if let Some(error) = response.error.as_mut() {
                 error.message = "Batch requests not supported".to_string();
             }
-            return Some(self.serialize_response(response));
+            return self.reply(response);
         }
 
         // Deserialize into JsonRpcRequest
         let request: JsonRpcRequest = match serde_json::from_value(parsed) {
             Ok(r) => r,
             Err(_) => {
-                return Some(self.serialize_response(JsonRpcResponse::parse_error()));
+                return self.reply(JsonRpcResponse::parse_error());
             }
         };
 
         // Validate jsonrpc field
         if request.jsonrpc != "2.0" {
-            return Some(self.serialize_response(JsonRpcResponse::invalid_request()));
+            return self.reply(JsonRpcResponse::invalid_request());
         }
 
-        // Check if this is a notification (no id field)
-        let id = request.id?; // Notification - silently ignore
+        // initialize creates a new session (or replaces the local one)
+        if request.method == "initialize" {
+            return match request.id {
+                Some(id) => self.handle_initialize(key, id, request.params).await,
+                None => self.no_reply(),
+            };
+        }
 
-        // Check initialization gate (except for initialize method itself)
-        if request.method != "initialize"
-            && request.method != "notifications/initialized"
-            && !self.initialized.load(Ordering::SeqCst)
-        {
-            return Some(self.serialize_response(JsonRpcResponse::not_initialized(id)));
+        // Every other message needs an established session
+        let error_id = request.id.clone().unwrap_or(Value::Null);
+        let session = match key {
+            SessionKey::Local => match self.local_session.write().await.as_mut() {
+                Some(session) => observe_message(session, &request.method),
+                None => {
+                    return match request.id {
+                        Some(id) => self.reply(JsonRpcResponse::not_initialized(id)),
+                        None => self.no_reply(), // Notification - silently ignore
+                    };
+                }
+            },
+            SessionKey::Http(None) => {
+                return McpReply::BadRequest(
+                    self.serialize_response(JsonRpcResponse::not_initialized(error_id)),
+                );
+            }
+            SessionKey::Http(Some(session_id)) => {
+                match self.touch_session(session_id, &request.method).await {
+                    Some(session) => session,
+                    None => {
+                        return McpReply::SessionNotFound(
+                            self.serialize_response(JsonRpcResponse::session_not_found(error_id)),
+                        );
+                    }
+                }
+            }
+        };
+
+        // Clients that skip the initialized notification are still served
+        if REQUIRES_INITIALIZED.contains(&request.method.as_str()) && !session.initialized {
+            tracing::warn!(
+                method = %request.method,
+                client = %session.client_info["name"],
+                "MCP request before notifications/initialized"
+            );
         }
 
+        // Check if this is a notification (no id field)
+        let Some(id) = request.id else {
+            return self.no_reply();
+        };
+
         // Dispatch to method handlers
-        match request.method.as_str() {
-            "initialize" => self.handle_initialize(id, request.params),
-            "notifications/initialized" => None, // Client notification - ignore
+        let body = match request.method.as_str() {
             "tools/list" => self.handle_tools_list(id, request.params),
             "tools/call" => self.handle_tools_call(id, request.params).await,
+            "resources/list" => self.handle_resources_list(id),
+            "resources/templates/list" => self.handle_resource_templates_list(id),
+            "resources/read" => self.handle_resources_read(id, request.params),
+            "prompts/list" => self.handle_prompts_list(id),
+            "prompts/get" => self.handle_prompts_get(id, request.params).await,
             _ => Some(self.serialize_response(JsonRpcResponse::method_not_found(id))),
+        };
+
+        McpReply::Message {
+            body,
+            session_id: None,
         }
     }
 
-    fn handle_initialize(&self, id: Value, params: Option<Value>) -> Option<String> {
+    /// End an HTTP session. Returns false if the session did not exist.
+    pub async fn end_session(&self, session_id: &str) -> bool {
+        self.sessions.write().await.remove(session_id).is_some()
+    }
+
+    /// Returns a copy of an HTTP session's state, if it exists and has not expired
+    pub async fn session(&self, session_id: &str) -> Option<Session> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .get(session_id)
+            .filter(|s| s.last_seen.elapsed() <= self.session_ttl)
+            .cloned()
+    }
+
+    async fn handle_initialize(
+        &self,
+        key: SessionKey<'_>,
+        id: Value,
+        params: Option<Value>,
+    ) -> McpReply {
         // Deserialize params
-        let _init_params: InitializeParams = match params {
+        let init_params: InitializeParams = match params {
             Some(p) => match serde_json::from_value(p) {
                 Ok(params) => params,
                 Err(_) => {
-                    return Some(self.serialize_response(JsonRpcResponse::invalid_params(id)));
+                    return self.reply(JsonRpcResponse::invalid_params(id));
                 }
             },
             None => {
-                return Some(self.serialize_response(JsonRpcResponse::invalid_params(id)));
+                return self.reply(JsonRpcResponse::invalid_params(id));
             }
         };
 
-        // Set initialized flag
-        self.initialized.store(true, Ordering::SeqCst);
+        let protocol_version = negotiate_protocol_version(&init_params.protocol_version);
+        let session = Session {
+            protocol_version: protocol_version.to_string(),
+            client_info: init_params.client_info.clone(),
+            initialized: false,
+            last_seen: Instant::now(),
+        };
+
+        let session_id = match key {
+            SessionKey::Local => {
+                *self.local_session.write().await = Some(session);
+                None
+            }
+            SessionKey::Http(_) => {
+                let session_id = Uuid::new_v4().to_string();
+                let mut sessions = self.sessions.write().await;
+                self.prune_expired(&mut sessions);
+                sessions.insert(session_id.clone(), session);
+                tracing::debug!(
+                    sessions = sessions.len(),
+                    client = %init_params.client_info["name"],
+                    "MCP session created"
+                );
+                Some(session_id)
+            }
+        };
 
         // Build response
         let result = serde_json::json!({
-            "protocolVersion": "2025-11-25",
+            "protocolVersion": protocol_version,
             "capabilities": {
-                "tools": {}
+                "tools": {},
+                "resources": {},
+                "prompts": {}
             },
             "serverInfo": {
                 "name": "three-good-sources",
@@ -128,7 +288,27 @@ impl McpHandler {
             }
         });
 
-        Some(self.serialize_response(JsonRpcResponse::success(id, result)))
+        McpReply::Message {
+            body: Some(self.serialize_response(JsonRpcResponse::success(id, result))),
+            session_id,
+        }
+    }
+
+    /// Refresh an HTTP session's idle timer and record `method` on it.
+    /// Returns a copy of the session, or None if it is unknown or expired.
+    /// Every expired session is dropped on the way, so sessions that are
+    /// never used again do not linger until the next initialize.
+    async fn touch_session(&self, session_id: &str, method: &str) -> Option<Session> {
+        let mut sessions = self.sessions.write().await;
+        self.prune_expired(&mut sessions);
+        let session = sessions.get_mut(session_id)?;
+        session.last_seen = Instant::now();
+        Some(observe_message(session, method))
+    }
+
+    /// Drop HTTP sessions idle for longer than the session TTL
+    fn prune_expired(&self, sessions: &mut HashMap<String, Session>) {
+        sessions.retain(|_, s| s.last_seen.elapsed() <= self.session_ttl);
     }
 
     /// Handle tools/list request
@@ -152,17 +332,16 @@ impl McpHandler {
             }
         };
 
-        // Dispatch to tool handler
+        // Dispatch to tool handler against the current data snapshot
+        let data = self.data.load();
         match tools::handle_tool_call(
             &call_params.name,
             call_params.arguments,
-            &self.registry,
+            &data,
             &self.match_config,
             &self.pubkey_z32,
-            &self.audit_log,
-            &self.identities,
-            &self.proposals,
             &self.peer_cache,
+            self.query_log.as_deref(),
         )
         .await
         {
@@ -176,6 +355,87 @@ impl McpHandler {
         }
     }
 
+    /// Handle resources/list request
+    fn handle_resources_list(&self, id: Value) -> Option<String> {
+        let result = resources::list_resources(&self.data.load());
+        Some(self.serialize_response(JsonRpcResponse::success(id, result)))
+    }
+
+    /// Handle resources/templates/list request
+    fn handle_resource_templates_list(&self, id: Value) -> Option<String> {
+        let result = resources::list_resource_templates();
+        Some(self.serialize_response(JsonRpcResponse::success(id, result)))
+    }
+
+    /// Handle resources/read request
+    fn handle_resources_read(&self, id: Value, params: Option<Value>) -> Option<String> {
+        let read_params: ReadResourceParams = match params.map(serde_json::from_value) {
+            Some(Ok(params)) => params,
+            _ => return Some(self.serialize_response(JsonRpcResponse::invalid_params(id))),
+        };
+
+        let response = match resources::read_resource(&read_params.uri, &self.data.load()) {
+            Ok(result) => JsonRpcResponse::success(id, result),
+            Err(ResourceError::NotFound) => {
+                JsonRpcResponse::resource_not_found(id, &read_params.uri)
+            }
+            Err(ResourceError::Serialization(e)) => {
+                tracing::error!(uri = %read_params.uri, error = %e, "Failed to serialize resource");
+                JsonRpcResponse::internal_error(id, "Failed to serialize resource".to_string())
+            }
+        };
+        Some(self.serialize_response(response))
+    }
+
+    /// Handle prompts/list request
+    fn handle_prompts_list(&self, id: Value) -> Option<String> {
+        let result = prompts::get_prompts_list();
+        Some(self.serialize_response(JsonRpcResponse::success(id, result)))
+    }
+
+    /// Handle prompts/get request
+    async fn handle_prompts_get(&self, id: Value, params: Option<Value>) -> Option<String> {
+        let get_params: GetPromptParams = match params.map(serde_json::from_value) {
+            Some(Ok(params)) => params,
+            _ => return Some(self.serialize_response(JsonRpcResponse::invalid_params(id))),
+        };
+
+        // Prompts are filled from the current data snapshot
+        let data = self.data.load();
+        match prompts::get_prompt(
+            &get_params.name,
+            get_params.arguments,
+            &data.registry,
+            &data.match_index,
+            &self.match_config,
+            &self.peer_cache,
+        )
+        .await
+        {
+            Ok(result) => Some(self.serialize_response(JsonRpcResponse::success(id, result))),
+            // MCP reports unknown prompt names as invalid params
+            Err(PromptError::UnknownPrompt | PromptError::InvalidParams) => {
+                Some(self.serialize_response(JsonRpcResponse::invalid_params(id)))
+            }
+        }
+    }
+
+    /// Wrap a JSON-RPC response that does not create a session
+    fn reply(&self, response: JsonRpcResponse) -> McpReply {
+        McpReply::Message {
+            body: Some(self.serialize_response(response)),
+            session_id: None,
+        }
+    }
+
+    /// Reply for notifications (no response body)
+    fn no_reply(&self) -> McpReply {
+        McpReply::Message {
+            body: None,
+            session_id: None,
+        }
+    }
+
     fn serialize_response(&self, response: JsonRpcResponse) -> String {
         serde_json::to_string(&response).unwrap_or_else(|_| {
             r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32603,"message":"Internal error"}}"#
@@ -184,34 +444,62 @@ impl McpHandler {
     }
 }
 
+/// Mark `session` initialized when `method` is the client's initialized
+/// notification, and return a copy of it
+fn observe_message(session: &mut Session, method: &str) -> Session {
+    if method == "notifications/initialized" {
+        session.initialized = true;
+    }
+    session.clone()
+}
+
+/// Pick the client's requested protocol version if supported, else our newest
+fn negotiate_protocol_version(requested: &str) -> &'static str {
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .find(|v| **v == requested)
+        .copied()
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::federation::PeerCache;
-    use crate::matcher::MatchConfig;
+    use crate::matcher::{MatchConfig, MatchMode};
     use crate::registry::Registry;
+    use crate::reload::DataSnapshot;
     use std::collections::HashMap;
 
     fn test_handler() -> McpHandler {
         let registry_json = include_str!("../../registry.json");
         let registry: Registry = serde_json::from_str(registry_json)
             .expect("Failed to parse test registry.json");
+        handler_with_registry(registry)
+    }
 
+    fn handler_with_registry(registry: Registry) -> McpHandler {
         let match_config = MatchConfig {
             match_threshold: 0.4,
             match_fuzzy_weight: 0.7,
             match_keyword_weight: 0.3,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
         };
 
         let peer_cache = Arc::new(PeerCache::new(vec![], "test-pubkey-z32".to_string()));
 
+        let data = Arc::new(DataStore::new(DataSnapshot::new(
+            registry,
+            vec![],
+            HashMap::new(),
+            HashMap::new(),
+        )));
+
         McpHandler::new(
-            Arc::new(registry),
+            data,
             match_config,
             "test-pubkey-z32".to_string(),
-            Arc::new(vec![]),
-            Arc::new(HashMap::new()),
-            Arc::new(HashMap::new()),
             peer_cache,
         )
     }
@@ -237,14 +525,16 @@ mod tests {
         assert_eq!(response["jsonrpc"], "2.0");
         assert_eq!(response["result"]["protocolVersion"], "2025-11-25");
         assert!(response["result"]["capabilities"]["tools"].is_object());
+        assert!(response["result"]["capabilities"]["resources"].is_object());
+        assert!(response["result"]["capabilities"]["prompts"].is_object());
         assert_eq!(response["result"]["serverInfo"]["name"], "three-good-sources");
         assert!(response["result"]["serverInfo"]["version"].is_string());
     }
 
     #[tokio::test]
-    async fn test_initialize_sets_initialized_flag() {
+    async fn test_initialize_creates_local_session() {
         let handler = test_handler();
-        assert!(!handler.initialized.load(Ordering::SeqCst));
+        assert!(handler.local_session.read().await.is_none());
 
         let request = r#"{
             "jsonrpc": "2.0",
@@ -258,7 +548,193 @@ mod tests {
         }"#;
 
         handler.handle_json(request).await;
-        assert!(handler.initialized.load(Ordering::SeqCst));
+        let session = handler.local_session.read().await.clone().expect("Local session");
+        assert_eq!(session.protocol_version, "2025-11-25");
+        assert_eq!(session.client_info["name"], "test");
+        assert!(!session.initialized);
+
+        assert!(handler.handle_json(INITIALIZED).await.is_none());
+        let session = handler.local_session.read().await.clone().expect("Local session");
+        assert!(session.initialized);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_without_capabilities_or_client_info() {
+        let handler = test_handler();
+        let request = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {"protocolVersion": "2025-11-25"}
+        }"#;
+
+        let response_str = handler.handle_json(request).await.expect("Expected response");
+        let response: Value = serde_json::from_str(&response_str).expect("Valid JSON");
+        assert_eq!(response["result"]["protocolVersion"], "2025-11-25");
+        assert!(handler.local_session.read().await.is_some());
+    }
+
+    const HTTP_INIT: &str = r#"{
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": {"name": "http-client", "version": "1.0"}
+        }
+    }"#;
+
+    const TOOLS_LIST: &str = r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/list"}"#;
+
+    const INITIALIZED: &str = r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#;
+
+    /// Initialize over the HTTP transport and return the issued session id
+    async fn http_session(handler: &McpHandler) -> String {
+        match handler.handle_message(SessionKey::Http(None), HTTP_INIT).await {
+            McpReply::Message {
+                body: Some(_),
+                session_id: Some(session_id),
+            } => session_id,
+            other => panic!("Expected initialize reply with session id, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_initialize_issues_session_id() {
+        let handler = test_handler();
+        let session_id = http_session(&handler).await;
+
+        let session = handler.session(&session_id).await.expect("Session should exist");
+        assert_eq!(session.protocol_version, "2024-11-05");
+    }
+
+    #[tokio::test]
+    async fn test_http_sessions_are_independent() {
+        let handler = test_handler();
+        let first = http_session(&handler).await;
+        let second = http_session(&handler).await;
+        assert_ne!(first, second);
+
+        // An initialized HTTP session does not initialize the local transport
+        let response_str = handler.handle_json(TOOLS_LIST).await.expect("Expected response");
+        let response: Value = serde_json::from_str(&response_str).expect("Valid JSON");
+        assert_eq!(response["error"]["code"], -32002);
+    }
+
+    #[tokio::test]
+    async fn test_initialized_notification_marks_only_its_session() {
+        let handler = test_handler();
+        let first = http_session(&handler).await;
+        let second = http_session(&handler).await;
+
+        let reply = handler
+            .handle_message(SessionKey::Http(Some(first.as_str())), INITIALIZED)
+            .await;
+        assert!(matches!(reply, McpReply::Message { body: None, .. }));
+
+        let first = handler.session(&first).await.expect("First session");
+        let second = handler.session(&second).await.expect("Second session");
+        assert!(first.initialized);
+        assert!(!second.initialized);
+        assert_eq!(second.client_info["name"], "http-client");
+        assert!(handler.local_session.read().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_http_request_without_session_rejected() {
+        let handler = test_handler();
+        http_session(&handler).await;
+
+        let reply = handler.handle_message(SessionKey::Http(None), TOOLS_LIST).await;
+        match reply {
+            McpReply::BadRequest(body) => {
+                let response: Value = serde_json::from_str(&body).expect("Valid JSON");
+                assert_eq!(response["error"]["code"], -32002);
+            }
+            other => panic!("Expected BadRequest, got: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_unknown_session_not_found() {
+        let handler = test_handler();
+
+        let reply = handler
+            .handle_message(SessionKey::Http(Some("no-such-session")), TOOLS_LIST)
+            .await;
+        assert!(matches!(reply, McpReply::SessionNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_http_session_expires() {
+        let handler = test_handler().with_session_ttl(Duration::ZERO);
+        let session_id = http_session(&handler).await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let reply = handler
+            .handle_message(SessionKey::Http(Some(&session_id)), TOOLS_LIST)
+            .await;
+        assert!(matches!(reply, McpReply::SessionNotFound(_)));
+        assert!(handler.session(&session_id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_sessions_pruned_on_lookup() {
+        let handler = test_handler().with_session_ttl(Duration::from_millis(200));
+        let expiring = http_session(&handler).await;
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        let active = http_session(&handler).await;
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert_eq!(handler.sessions.read().await.len(), 2);
+
+        // Any request drops the expired session, not just a new initialize
+        let reply = handler
+            .handle_message(SessionKey::Http(Some(&active)), TOOLS_LIST)
+            .await;
+        assert!(matches!(reply, McpReply::Message { body: Some(_), .. }));
+        let sessions = handler.sessions.read().await;
+        assert_eq!(sessions.len(), 1);
+        assert!(!sessions.contains_key(&expiring));
+    }
+
+    #[tokio::test]
+    async fn test_http_end_session() {
+        let handler = test_handler();
+        let session_id = http_session(&handler).await;
+
+        let reply = handler
+            .handle_message(SessionKey::Http(Some(&session_id)), TOOLS_LIST)
+            .await;
+        assert!(matches!(reply, McpReply::Message { body: Some(_), .. }));
+
+        assert!(handler.end_session(&session_id).await);
+        assert!(!handler.end_session(&session_id).await);
+
+        let reply = handler
+            .handle_message(SessionKey::Http(Some(&session_id)), TOOLS_LIST)
+            .await;
+        assert!(matches!(reply, McpReply::SessionNotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_protocol_version_negotiates_latest() {
+        let handler = test_handler();
+
+        let request = r#"{
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "1999-01-01",
+                "capabilities": {},
+                "clientInfo": {}
+            }
+        }"#;
+
+        let response_str = handler.handle_json(request).await.expect("Expected response");
+        let response: Value = serde_json::from_str(&response_str).expect("Valid JSON");
+        assert_eq!(response["result"]["protocolVersion"], SUPPORTED_PROTOCOL_VERSIONS[0]);
     }
 
     #[tokio::test]
@@ -449,6 +925,15 @@ mod tests {
         handler.handle_json(init_request).await.expect("Initialize should succeed");
     }
 
+    /// Send a request to an initialized handler and parse the response
+    async fn call(handler: &McpHandler, request: Value) -> Value {
+        let response_str = handler
+            .handle_json(&request.to_string())
+            .await
+            .expect("Expected response");
+        serde_json::from_str(&response_str).expect("Valid JSON")
+    }
+
     // ===== TDD Tests for Plan 02: Tool Implementations =====
 
     #[tokio::test]
@@ -470,7 +955,7 @@ mod tests {
         assert!(response["result"]["tools"].is_array());
 
         let tools = response["result"]["tools"].as_array().unwrap();
-        assert_eq!(tools.len(), 9, "Should return exactly 9 tools");
+        assert_eq!(tools.len(), 11, "Should return exactly 11 tools");
 
         // Check tool names
         let tool_names: Vec<&str> = tools
@@ -479,6 +964,7 @@ mod tests {
             .collect();
         assert!(tool_names.contains(&"get_sources"));
         assert!(tool_names.contains(&"list_categories"));
+        assert!(tool_names.contains(&"browse_categories"));
         assert!(tool_names.contains(&"get_provenance"));
         assert!(tool_names.contains(&"get_endorsements"));
         assert!(tool_names.contains(&"get_audit_log"));
@@ -486,6 +972,7 @@ mod tests {
         assert!(tool_names.contains(&"list_proposals"));
         assert!(tool_names.contains(&"get_proposal"));
         assert!(tool_names.contains(&"get_federated_sources"));
+        assert!(tool_names.contains(&"get_peer_status"));
     }
 
     #[tokio::test]
@@ -516,6 +1003,212 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_tools_list_has_output_schemas() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+        )
+        .await;
+
+        for tool in response["result"]["tools"].as_array().unwrap() {
+            assert_eq!(
+                tool["outputSchema"]["type"], "object",
+                "{} should declare an object outputSchema",
+                tool["name"]
+            );
+            assert!(tool["outputSchema"]["properties"].is_object());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_structured_content() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "get_sources", "arguments": {"query": "learn rust"}}
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        assert!(result["content"][0]["text"].as_str().unwrap().contains("URL: "), "Text content kept");
+
+        let structured = &result["structuredContent"];
+        assert_eq!(structured["slug"], "rust-learning");
+        assert_eq!(structured["name"], "Rust Learning");
+        let sources = structured["sources"].as_array().unwrap();
+        assert_eq!(sources.len(), 3);
+        assert!(structured.get("honourable_mentions").is_none(), "v1 registries have none");
+        for (i, source) in sources.iter().enumerate() {
+            assert_eq!(source["rank"], i + 1);
+            assert!(source["url"].as_str().unwrap().starts_with("http"));
+            assert!(source["type"].is_string());
+            assert!(source["why"].is_string());
+        }
+
+        // Every property the output schema requires is present
+        let tools = tools::get_tools_list();
+        let schema = &tools["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["name"] == "get_sources")
+            .unwrap()["outputSchema"];
+        for key in schema["required"].as_array().unwrap() {
+            assert!(
+                structured.get(key.as_str().unwrap()).is_some(),
+                "structuredContent missing required {}",
+                key
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_categories_structured_content() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "list_categories", "arguments": {}}
+            }),
+        )
+        .await;
+
+        let categories = response["result"]["structuredContent"]["categories"]
+            .as_array()
+            .unwrap();
+        assert_eq!(categories.len(), 10);
+        let slugs: Vec<&str> = categories.iter().map(|c| c["slug"].as_str().unwrap()).collect();
+        let mut sorted = slugs.clone();
+        sorted.sort();
+        assert_eq!(slugs, sorted, "Categories should be sorted by slug");
+    }
+
+    #[tokio::test]
+    async fn test_list_categories_filters_by_tag() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "list_categories", "arguments": {"tag": "security"}}
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        let slugs: Vec<&str> = result["structuredContent"]["categories"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["slug"].as_str().unwrap())
+            .collect();
+        assert_eq!(slugs, vec!["linux-hardening", "password-management", "threat-modeling"]);
+        assert_eq!(
+            result["structuredContent"]["categories"][0]["tags"],
+            serde_json::json!(["security", "self-hosting"])
+        );
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("Categories tagged 'security' (3):"));
+    }
+
+    #[tokio::test]
+    async fn test_browse_categories() {
+        let mut registry: Registry =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        for slug in ["password-management", "linux-hardening"] {
+            let category = registry.categories.get_mut(slug).unwrap();
+            category.parent = Some("threat-modeling".to_string());
+        }
+        let handler = handler_with_registry(registry);
+        init_handler(&handler).await;
+
+        let browse = |arguments: serde_json::Value| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "browse_categories", "arguments": arguments}
+            })
+        };
+
+        // Top level: categories without a parent
+        let response = call(&handler, browse(serde_json::json!({}))).await;
+        let structured = &response["result"]["structuredContent"];
+        assert!(structured.get("category").is_none());
+        let children = structured["children"].as_array().unwrap();
+        assert_eq!(children.len(), 8);
+        let threat_modeling = children
+            .iter()
+            .find(|c| c["slug"] == "threat-modeling")
+            .unwrap();
+        assert_eq!(threat_modeling["subcategories"], 2);
+
+        // One level down
+        let response =
+            call(&handler, browse(serde_json::json!({"slug": "linux-hardening"}))).await;
+        let result = &response["result"];
+        let structured = &result["structuredContent"];
+        assert_eq!(structured["category"]["slug"], "linux-hardening");
+        assert_eq!(structured["category"]["parent"], "threat-modeling");
+        assert_eq!(structured["path"], serde_json::json!(["threat-modeling"]));
+        assert_eq!(structured["children"].as_array().unwrap().len(), 0);
+        let related: Vec<&str> = structured["related"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["slug"].as_str().unwrap())
+            .collect();
+        assert_eq!(related, vec!["threat-modeling", "self-hosted-email"]);
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Path: threat-modeling > linux-hardening"));
+        assert!(text.contains("Related categories:"));
+
+        let response = call(&handler, browse(serde_json::json!({"slug": "go-learning"}))).await;
+        assert_eq!(response["result"]["isError"], true);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("Unknown category 'go-learning'"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_no_match_has_no_structured_content() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "get_sources", "arguments": {"query": "quantum physics supercollider"}}
+            }),
+        )
+        .await;
+
+        assert_eq!(response["result"]["isError"], true);
+        assert!(response["result"].get("structuredContent").is_none());
+    }
+
     #[tokio::test]
     async fn test_get_sources_success() {
         let handler = test_handler();
@@ -598,6 +1291,11 @@ mod tests {
             text.contains("No matching category") || text.contains("Available categories"),
             "Should explain no match and show available categories"
         );
+        assert!(text.contains("You could also try: "), "Should suggest related categories");
+        assert!(
+            !text.contains("bitcoin-node-setup, home-automation-private"),
+            "Should not list every slug"
+        );
     }
 
     #[tokio::test]
@@ -731,6 +1429,90 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_peer_status_no_peers() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 10,
+                "method": "tools/call",
+                "params": {"name": "get_peer_status", "arguments": {}}
+            }),
+        )
+        .await;
+
+        assert_eq!(response["result"]["isError"], false);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.starts_with("Peers: 0"));
+        let structured = &response["result"]["structuredContent"];
+        assert_eq!(structured["peers"], serde_json::json!([]));
+        assert_eq!(structured["unreachable"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_peer_status_reports_failing_peer() {
+        let endorsement = crate::registry::types::Endorsement {
+            pubkey: "peer-pubkey".to_string(),
+            url: "http://127.0.0.1:1".to_string(),
+            name: Some("Offline Peer".to_string()),
+            since: "2026-04-03".to_string(),
+        };
+        let peer_cache =
+            Arc::new(PeerCache::new(vec![endorsement], "test-pubkey-z32".to_string()));
+        peer_cache.refresh_all().await;
+
+        let registry: Registry =
+            serde_json::from_str(include_str!("../../registry.json")).unwrap();
+        let data = Arc::new(DataStore::new(DataSnapshot::new(
+            registry,
+            vec![],
+            HashMap::new(),
+            HashMap::new(),
+        )));
+        let handler = McpHandler::new(
+            data,
+            MatchConfig {
+                match_threshold: 0.4,
+                match_fuzzy_weight: 0.7,
+                match_keyword_weight: 0.3,
+                match_mode: MatchMode::Fuzzy,
+                match_bm25_weight: 0.5,
+            },
+            "test-pubkey-z32".to_string(),
+            peer_cache,
+        );
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 10,
+                "method": "tools/call",
+                "params": {"name": "get_peer_status"}
+            }),
+        )
+        .await;
+
+        assert_eq!(response["result"]["isError"], false);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Peers: 1 (0 fresh, 0 stale, 1 unreachable, 0 untrusted)"));
+        assert!(text.contains("- Offline Peer (peer-pubkey)"));
+        assert!(text.contains("Last success: never"));
+        assert!(text.contains("Last error: request failed"));
+
+        let peer = &response["result"]["structuredContent"]["peers"][0];
+        assert_eq!(peer["url"], "http://127.0.0.1:1");
+        assert_eq!(peer["status"], "unreachable");
+        assert_eq!(peer["consecutive_failures"], 1);
+        assert!(peer["last_success"].is_null());
+        assert!(peer["category_count"].is_null());
+    }
+
     #[tokio::test]
     async fn test_unknown_tool_returns_error() {
         let handler = test_handler();
@@ -795,4 +1577,604 @@ mod tests {
 
         assert_eq!(response["error"]["code"], -32602, "Should be Invalid params");
     }
+
+    #[tokio::test]
+    async fn test_get_sources_limit_returns_alternatives() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 14,
+                "method": "tools/call",
+                "params": {
+                    "name": "get_sources",
+                    "arguments": {"query": "self host", "threshold": 0.1, "limit": 3}
+                }
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        assert_eq!(result["isError"], false);
+        let structured = &result["structuredContent"];
+        let alternatives = structured["alternatives"].as_array().unwrap();
+        assert_eq!(alternatives.len(), 2);
+
+        let best = structured["score"].as_f64().unwrap();
+        let second = alternatives[0]["score"].as_f64().unwrap();
+        let third = alternatives[1]["score"].as_f64().unwrap();
+        assert!(best >= second && second >= third, "Matches sorted by score");
+        assert_ne!(structured["slug"], alternatives[0]["slug"]);
+        assert!(structured.get("explanation").is_none(), "No explanation unless requested");
+
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Alternative match 1"));
+        assert!(text.contains("Alternative match 2"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_default_limit_has_no_alternatives() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 15,
+                "method": "tools/call",
+                "params": {"name": "get_sources", "arguments": {"query": "learn rust"}}
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        assert_eq!(result["structuredContent"]["alternatives"], serde_json::json!([]));
+        assert!(!result["content"][0]["text"].as_str().unwrap().contains("Alternative match"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_explain_breakdown() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 16,
+                "method": "tools/call",
+                "params": {
+                    "name": "get_sources",
+                    "arguments": {"query": "bitcoin node", "explain": true}
+                }
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        assert_eq!(result["isError"], false);
+        let explanation = &result["structuredContent"]["explanation"];
+        assert!(!explanation["pattern_scores"].as_array().unwrap().is_empty());
+        assert_eq!(explanation["fuzzy_weight"], 0.7);
+        assert_eq!(explanation["keyword_weight"], 0.3);
+
+        let score = result["structuredContent"]["score"].as_f64().unwrap();
+        let expected = 0.7 * explanation["fuzzy_score"].as_f64().unwrap()
+            + 0.3 * explanation["keyword_score"].as_f64().unwrap();
+        assert!((score - expected.min(1.0)).abs() < 1e-9);
+
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Match score:"));
+        assert!(text.contains("Keyword (slug terms in query):"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_explain_negative_pattern() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        // "rust belt" is a negative pattern of rust-learning in the seed registry
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 16,
+                "method": "tools/call",
+                "params": {
+                    "name": "get_sources",
+                    "arguments": {"query": "rust belt", "threshold": 0.0, "limit": 10, "explain": true}
+                }
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        assert_eq!(result["isError"], false);
+        let structured = &result["structuredContent"];
+        assert_ne!(structured["slug"], "rust-learning");
+        let rust = structured["alternatives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|alternative| alternative["slug"] == "rust-learning")
+            .expect("rust-learning is still ranked");
+        assert_eq!(rust["explanation"]["negative_pattern"], "rust belt");
+        assert_eq!(rust["explanation"]["negative_penalty"], 0.5);
+        assert_eq!(structured["explanation"]["negative_penalty"], 0.0);
+        assert!(structured["explanation"].get("negative_pattern").is_none());
+
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Negative pattern \"rust belt\" in query: -0.50"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_lists_honourable_mentions() {
+        let registry_json = include_str!("../../tests/fixtures/valid_registry_v2.json");
+        let registry: Registry = serde_json::from_str(registry_json)
+            .expect("Failed to parse valid_registry_v2.json");
+        let handler = handler_with_registry(registry);
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": "get_sources", "arguments": {"query": "learn rust"}}
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        let structured = &result["structuredContent"];
+        let ranks: Vec<u64> = structured["sources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["rank"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ranks, vec![1, 2, 3]);
+        let mentions = structured["honourable_mentions"].as_array().unwrap();
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0]["rank"], 4);
+        assert_eq!(mentions[0]["name"], "Rust by Example");
+
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let heading = text.find("Honourable mentions:").expect("mentions heading");
+        assert!(text.find("4. Rust by Example").unwrap() > heading);
+        assert!(text.find("3. Rust Playground").unwrap() < heading);
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_source_metadata_and_filters() {
+        let registry_json = include_str!("../../tests/fixtures/valid_registry_v2.json");
+        let registry: Registry = serde_json::from_str(registry_json)
+            .expect("Failed to parse valid_registry_v2.json");
+        let handler = &handler_with_registry(registry);
+        init_handler(handler).await;
+
+        let get_sources = move |arguments: serde_json::Value| {
+            call(
+                handler,
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "tools/call",
+                    "params": {"name": "get_sources", "arguments": arguments}
+                }),
+            )
+        };
+
+        let response = get_sources(serde_json::json!({"query": "learn rust"})).await;
+        let result = &response["result"];
+        let book = &result["structuredContent"]["sources"][0];
+        assert_eq!(book["cost"], "free");
+        assert_eq!(book["license"], "MIT OR Apache-2.0");
+        assert_eq!(book["language"], "en");
+        assert_eq!(book["format"], "html");
+        assert_eq!(book["last_verified"], "2026-09-14");
+        assert_eq!(book["archived"], false);
+        assert!(result["structuredContent"]["sources"][2].get("cost").is_none());
+        assert!(result["structuredContent"].get("filtered_out").is_none());
+
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains(
+            "Cost: Free | License: MIT OR Apache-2.0 | Language: en | Format: Html | Last verified: 2026-09-14"
+        ));
+        assert!(text.contains("Archived: no longer maintained"));
+
+        // Rust Playground records neither cost nor language
+        let response =
+            get_sources(serde_json::json!({"query": "learn rust", "free_only": true})).await;
+        let structured = &response["result"]["structuredContent"];
+        assert_eq!(structured["sources"].as_array().unwrap().len(), 2);
+        assert_eq!(structured["honourable_mentions"].as_array().unwrap().len(), 1);
+        assert_eq!(structured["filtered_out"], 1);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(!text.contains("Rust Playground"));
+        assert!(text.contains("(1 of 4 sources left out by the free_only/source_lang filters)"));
+
+        let response =
+            get_sources(serde_json::json!({"query": "learn rust", "source_lang": "DE"})).await;
+        let structured = &response["result"]["structuredContent"];
+        assert_eq!(structured["sources"].as_array().unwrap().len(), 0);
+        assert_eq!(structured["filtered_out"], 4);
+
+        let response =
+            get_sources(serde_json::json!({"query": "learn rust", "source_lang": "german"})).await;
+        assert!(response["error"].is_object(), "non ISO 639-1 source_lang is rejected");
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_records_query_log() {
+        use crate::analytics::{read_events, QueryOutcome};
+        use crate::test_support::TempPath;
+
+        let path = TempPath::new("query-log.jsonl");
+        let query_log = Arc::new(QueryLog::open(&path).await.unwrap());
+        let handler = test_handler().with_query_log(query_log);
+        init_handler(&handler).await;
+
+        for (id, query) in ["learn rust", "quantum physics supercollider", "the and of"]
+            .iter()
+            .enumerate()
+        {
+            call(
+                &handler,
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": id + 2,
+                    "method": "tools/call",
+                    "params": {"name": "get_sources", "arguments": {"query": query}}
+                }),
+            )
+            .await;
+        }
+
+        // Stop-word-only queries are not recorded
+        let events = read_events(&path).await.unwrap();
+        assert_eq!(events.len(), 2);
+        match &events[0].outcome {
+            QueryOutcome::Matched { slug, score } => {
+                assert_eq!(slug, "rust-learning");
+                assert!(*score >= 0.4);
+            }
+            other => panic!("expected a match, got {:?}", other),
+        }
+        match &events[1].outcome {
+            QueryOutcome::Unmatched { query, .. } => assert!(query.starts_with("quantum ")),
+            other => panic!("expected no match, got {:?}", other),
+        }
+
+        // The raw text of matched queries is never written
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("learn rust"));
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_limit_out_of_range() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        for limit in [0, 11] {
+            let response = call(
+                &handler,
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 17,
+                    "method": "tools/call",
+                    "params": {
+                        "name": "get_sources",
+                        "arguments": {"query": "learn rust", "limit": limit}
+                    }
+                }),
+            )
+            .await;
+
+            assert_eq!(response["error"]["code"], -32602, "limit {} should be rejected", limit);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_lang_uses_translation() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 18,
+                "method": "tools/call",
+                "params": {
+                    "name": "get_sources",
+                    "arguments": {"query": "aprender rust", "lang": "es"}
+                }
+            }),
+        )
+        .await;
+
+        let result = &response["result"];
+        assert_eq!(result["isError"], false);
+        let structured = &result["structuredContent"];
+        assert_eq!(structured["slug"], "rust-learning");
+        assert_eq!(structured["name"], "Aprender Rust");
+        assert_eq!(structured["lang"], "es");
+    }
+
+    #[tokio::test]
+    async fn test_get_sources_unsupported_lang() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 19,
+                "method": "tools/call",
+                "params": {
+                    "name": "get_sources",
+                    "arguments": {"query": "learn rust", "lang": "xx"}
+                }
+            }),
+        )
+        .await;
+
+        assert_eq!(response["error"]["code"], -32602);
+    }
+
+    // ===== Resources =====
+
+    #[tokio::test]
+    async fn test_resources_list_includes_categories_and_audit() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "resources/list"}),
+        )
+        .await;
+
+        let resources = response["result"]["resources"].as_array().unwrap();
+        let uris: Vec<&str> = resources.iter().map(|r| r["uri"].as_str().unwrap()).collect();
+        assert_eq!(uris.len(), 11, "10 categories plus the audit log");
+        assert!(uris.contains(&"3gs://category/rust-learning"));
+        assert!(uris.contains(&"3gs://audit"));
+        for resource in resources {
+            assert_eq!(resource["mimeType"], "application/json");
+            assert!(resource["name"].is_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resource_templates_list() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "resources/templates/list"}),
+        )
+        .await;
+
+        let templates: Vec<&str> = response["result"]["resourceTemplates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["uriTemplate"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            templates,
+            vec!["3gs://category/{slug}", "3gs://identity/{pubkey}", "3gs://proposal/{id}"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resources_read_category() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "resources/read",
+                "params": {"uri": "3gs://category/rust-learning"}
+            }),
+        )
+        .await;
+
+        let contents = &response["result"]["contents"][0];
+        assert_eq!(contents["uri"], "3gs://category/rust-learning");
+        assert_eq!(contents["mimeType"], "application/json");
+
+        let category: Value = serde_json::from_str(contents["text"].as_str().unwrap()).unwrap();
+        assert_eq!(category["name"], "Rust Learning");
+        assert_eq!(category["sources"].as_array().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resources_read_audit() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "resources/read",
+                "params": {"uri": "3gs://audit"}
+            }),
+        )
+        .await;
+
+        let text = response["result"]["contents"][0]["text"].as_str().unwrap();
+        let entries: Value = serde_json::from_str(text).unwrap();
+        assert!(entries.is_array());
+    }
+
+    #[tokio::test]
+    async fn test_resources_read_not_found() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        for uri in [
+            "3gs://category/no-such-category",
+            "3gs://identity/unknown",
+            "3gs://proposal/not-a-uuid",
+            "3gs://category/rust-learning/extra",
+            "https://example.com/",
+        ] {
+            let response = call(
+                &handler,
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "resources/read",
+                    "params": {"uri": uri}
+                }),
+            )
+            .await;
+
+            assert_eq!(response["error"]["code"], -32002, "Should be Resource not found: {}", uri);
+            assert_eq!(response["error"]["data"]["uri"], uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resources_read_missing_uri() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "resources/read", "params": {}}),
+        )
+        .await;
+
+        assert_eq!(response["error"]["code"], -32602, "Should be Invalid params");
+    }
+
+    // ===== Prompts =====
+
+    #[tokio::test]
+    async fn test_prompts_list() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/list"}),
+        )
+        .await;
+
+        let prompts = response["result"]["prompts"].as_array().unwrap();
+        let names: Vec<&str> = prompts.iter().map(|p| p["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["research_topic", "compare_curators"]);
+        for prompt in prompts {
+            assert!(prompt["description"].is_string());
+            assert_eq!(prompt["arguments"][0]["name"], "topic");
+            assert_eq!(prompt["arguments"][0]["required"], true);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_research_topic_embeds_sources() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "prompts/get",
+                "params": {"name": "research_topic", "arguments": {"topic": "learn rust"}}
+            }),
+        )
+        .await;
+
+        let message = &response["result"]["messages"][0];
+        assert_eq!(message["role"], "user");
+        assert_eq!(message["content"]["type"], "text");
+
+        let text = message["content"]["text"].as_str().unwrap();
+        assert!(text.contains("learn rust"));
+        assert!(text.contains("Rust Learning"));
+        assert!(text.matches("URL: http").count() >= 3, "Should embed all three sources");
+        assert!(text.matches("Why: ").count() >= 3, "Should embed each source's rationale");
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_research_topic_no_match_lists_categories() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "prompts/get",
+                "params": {"name": "research_topic", "arguments": {"topic": "quantum physics supercollider"}}
+            }),
+        )
+        .await;
+
+        let text = response["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("no curated category"));
+        assert!(text.contains("rust-learning"));
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_compare_curators_local_only() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        let response = call(
+            &handler,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "prompts/get",
+                "params": {"name": "compare_curators", "arguments": {"topic": "learn rust"}}
+            }),
+        )
+        .await;
+
+        let text = response["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("1 curator(s)"));
+        assert!(text.contains("Trust: direct"));
+        assert!(text.contains("Rust Learning"));
+    }
+
+    #[tokio::test]
+    async fn test_prompts_get_invalid_requests() {
+        let handler = test_handler();
+        init_handler(&handler).await;
+
+        for params in [
+            serde_json::json!({"name": "no_such_prompt", "arguments": {"topic": "rust"}}),
+            serde_json::json!({"name": "research_topic"}),
+            serde_json::json!({"name": "research_topic", "arguments": {"subject": "rust"}}),
+            serde_json::json!({"name": "research_topic", "arguments": {"topic": "   "}}),
+            serde_json::json!({"arguments": {"topic": "rust"}}),
+        ] {
+            let response = call(
+                &handler,
+                serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/get", "params": params}),
+            )
+            .await;
+            assert_eq!(response["error"]["code"], -32602, "Should be Invalid params: {}", params);
+        }
+    }
 }
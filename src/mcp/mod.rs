@@ -1,5 +1,8 @@
 pub mod handler;
+pub mod output;
+pub mod prompts;
+pub mod resources;
 pub mod tools;
 pub mod types;
 
-pub use handler::McpHandler;
+pub use handler::{McpHandler, McpReply, SessionKey, SUPPORTED_PROTOCOL_VERSIONS};
@@ -0,0 +1,747 @@
+//! Structured output types for MCP tools.
+//!
+//! Each tool returns one of these as `structuredContent` next to its text
+//! content, and declares the matching JSON Schema as its `outputSchema`.
+//! Enums, UUIDs and timestamps are flattened to strings so the schemas stay
+//! independent of the internal data types.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::audit::AuditEntry;
+use crate::contributions::Proposal;
+use crate::federation::{FederatedMatch, FederationSummary, PeerReport, TrustLevel};
+use crate::identity::Identity;
+use crate::matcher::{MatchConfig, MatchResult, ScoreBreakdown, NEGATIVE_PATTERN_PENALTY};
+use crate::registry::types::{Category, Endorsement, Source, SourceFilter, SourcePolicy};
+use crate::registry::Registry;
+use uuid::Uuid;
+
+/// Serialize a unit enum to its serde string name (e.g. "source_added")
+fn serde_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// RFC 3339 timestamp with second precision, matching the text output
+fn timestamp(value: &chrono::DateTime<chrono::Utc>) -> String {
+    value.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// A ranked, curated source
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SourceOutput {
+    /// Rank order (1 is the best starting point)
+    pub rank: u8,
+    /// Source display name
+    pub name: String,
+    /// Source URL
+    pub url: String,
+    /// Source type (e.g. "documentation", "tutorial")
+    #[serde(rename = "type")]
+    pub source_type: String,
+    /// Curator's explanation of why this source is valuable
+    pub why: String,
+    /// Day the curator last checked the source (YYYY-MM-DD)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_verified: Option<String>,
+    /// Cost of use: "free", "freemium" or "paid"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<String>,
+    /// License the content is published under (e.g. "CC-BY-4.0")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// ISO 639-1 code of the source's language
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Medium (e.g. "html", "pdf", "video")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// True if the source is no longer maintained
+    pub archived: bool,
+}
+
+impl SourceOutput {
+    fn from_sources<'a>(sources: impl IntoIterator<Item = &'a Source>) -> Vec<Self> {
+        sources
+            .into_iter()
+            .map(|source| Self {
+                rank: source.rank,
+                name: source.name.clone(),
+                url: source.url.clone(),
+                source_type: serde_name(&source.source_type),
+                why: source.why.clone(),
+                last_verified: source.last_verified.map(|date| date.to_string()),
+                cost: source.cost.as_ref().map(serde_name),
+                license: source.license.clone(),
+                language: source.language.clone(),
+                format: source.format.as_ref().map(serde_name),
+                archived: source.archived,
+            })
+            .collect()
+    }
+}
+
+/// Curator identity
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CuratorOutput {
+    /// Curator display name
+    pub name: String,
+    /// Curator public key
+    pub pubkey: String,
+}
+
+/// Score of the query against one query pattern
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PatternScoreOutput {
+    /// Query pattern as written in the registry
+    pub pattern: String,
+    /// Fuzzy similarity (0.0-1.0)
+    pub score: f64,
+}
+
+/// Per-surface breakdown of a match score
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ScoreExplanationOutput {
+    /// Scoring mode: "fuzzy", "bm25", or "hybrid"
+    pub mode: String,
+    /// Fuzzy similarity against the slug
+    pub slug_score: f64,
+    /// Fuzzy similarity against the category name
+    pub name_score: f64,
+    /// Fuzzy similarity against each query pattern
+    pub pattern_scores: Vec<PatternScoreOutput>,
+    /// Best fuzzy similarity across slug, name, and patterns
+    pub fuzzy_score: f64,
+    /// Fraction of slug terms found in the query
+    pub keyword_score: f64,
+    /// BM25 token score, absent in fuzzy mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bm25_score: Option<f64>,
+    /// Weight applied to the fuzzy score
+    pub fuzzy_weight: f64,
+    /// Weight applied to the keyword score
+    pub keyword_weight: f64,
+    /// Share of the BM25 score in hybrid mode
+    pub bm25_weight: f64,
+    /// Negative pattern found in the query, absent if none was
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negative_pattern: Option<String>,
+    /// Amount subtracted from the score for the negative pattern (0.0 if none)
+    pub negative_penalty: f64,
+}
+
+impl ScoreExplanationOutput {
+    pub fn new(breakdown: &ScoreBreakdown, config: &MatchConfig) -> Self {
+        Self {
+            mode: serde_name(&config.match_mode),
+            slug_score: breakdown.slug,
+            name_score: breakdown.name,
+            pattern_scores: breakdown
+                .patterns
+                .iter()
+                .map(|p| PatternScoreOutput {
+                    pattern: p.pattern.clone(),
+                    score: p.score,
+                })
+                .collect(),
+            fuzzy_score: breakdown.fuzzy,
+            keyword_score: breakdown.keyword,
+            bm25_score: breakdown.bm25,
+            fuzzy_weight: config.match_fuzzy_weight,
+            keyword_weight: config.match_keyword_weight,
+            bm25_weight: config.match_bm25_weight,
+            negative_pattern: breakdown.negative_pattern.clone(),
+            negative_penalty: if breakdown.negative_pattern.is_some() {
+                NEGATIVE_PATTERN_PENALTY
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// A matched category with its score and sources
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CategoryMatchOutput {
+    /// Matched category slug
+    pub slug: String,
+    /// Matched category display name
+    pub name: String,
+    /// Matched category description
+    pub description: String,
+    /// Match score (0.0-1.0)
+    pub score: f64,
+    /// Score breakdown, present when explain was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<ScoreExplanationOutput>,
+    /// Curated primary sources in rank order
+    pub sources: Vec<SourceOutput>,
+    /// Alternates listed after the primary sources, in rank order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub honourable_mentions: Vec<SourceOutput>,
+    /// Sources left out by the free_only and source_lang filters
+    #[serde(skip_serializing_if = "is_zero")]
+    pub filtered_out: usize,
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+impl CategoryMatchOutput {
+    pub fn new(
+        result: &MatchResult,
+        policy: SourcePolicy,
+        filter: &SourceFilter,
+        config: &MatchConfig,
+        explain: bool,
+    ) -> Self {
+        let (sources, honourable_mentions) = policy.split(&result.category.sources);
+        let total = sources.len() + honourable_mentions.len();
+        let keep = |source: &&Source| filter.matches(source);
+        let sources = SourceOutput::from_sources(sources.into_iter().filter(keep));
+        let honourable_mentions = SourceOutput::from_sources(honourable_mentions.into_iter().filter(keep));
+        Self {
+            slug: result.slug.clone(),
+            name: result.category.name.clone(),
+            description: result.category.description.clone(),
+            score: result.score,
+            explanation: explain.then(|| ScoreExplanationOutput::new(&result.breakdown, config)),
+            filtered_out: total - sources.len() - honourable_mentions.len(),
+            sources,
+            honourable_mentions,
+        }
+    }
+}
+
+/// Output of get_sources
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetSourcesOutput {
+    /// Best match
+    #[serde(flatten)]
+    pub best: CategoryMatchOutput,
+    /// ISO 639-1 code of the language the query was matched in; names and
+    /// descriptions are translated into it where the registry has translations
+    pub lang: String,
+    /// Registry version the sources come from
+    pub registry_version: String,
+    /// Curator of the registry
+    pub curator: CuratorOutput,
+    /// Further matches above the threshold, best first (only when limit > 1)
+    pub alternatives: Vec<CategoryMatchOutput>,
+}
+
+impl GetSourcesOutput {
+    /// Build from match results sorted best first; `results` must be non-empty
+    pub fn new(
+        results: &[MatchResult],
+        registry: &Registry,
+        filter: &SourceFilter,
+        config: &MatchConfig,
+        explain: bool,
+    ) -> Self {
+        let policy = registry.source_policy();
+        let mut matches = results
+            .iter()
+            .map(|result| CategoryMatchOutput::new(result, policy, filter, config, explain));
+        Self {
+            best: matches.next().expect("get_sources output needs at least one match"),
+            lang: results[0].language.code().to_string(),
+            registry_version: registry.version.clone(),
+            curator: CuratorOutput {
+                name: registry.curator.name.clone(),
+                pubkey: registry.curator.pubkey.clone(),
+            },
+            alternatives: matches.collect(),
+        }
+    }
+}
+
+/// Category summary
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CategorySummaryOutput {
+    /// Category slug
+    pub slug: String,
+    /// Category display name
+    pub name: String,
+    /// Category description
+    pub description: String,
+    /// Topic labels
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Slug of the broader category this one belongs under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Slugs of related categories
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<String>,
+}
+
+impl CategorySummaryOutput {
+    pub fn new(slug: &str, category: &Category) -> Self {
+        Self {
+            slug: slug.to_string(),
+            name: category.name.clone(),
+            description: category.description.clone(),
+            tags: category.tags.clone(),
+            parent: category.parent.clone(),
+            related: category.related.clone(),
+        }
+    }
+}
+
+/// Output of list_categories
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ListCategoriesOutput {
+    /// Categories sorted by slug
+    pub categories: Vec<CategorySummaryOutput>,
+}
+
+impl ListCategoriesOutput {
+    pub fn new(categories: &[(&String, &Category)]) -> Self {
+        Self {
+            categories: categories
+                .iter()
+                .map(|(slug, category)| CategorySummaryOutput::new(slug, category))
+                .collect(),
+        }
+    }
+}
+
+/// A category one level down the hierarchy
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CategoryNodeOutput {
+    /// Category slug
+    pub slug: String,
+    /// Category display name
+    pub name: String,
+    /// Category description
+    pub description: String,
+    /// Number of categories directly under this one
+    pub subcategories: usize,
+}
+
+/// Output of browse_categories
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BrowseCategoriesOutput {
+    /// Category being browsed, absent at the top level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<CategorySummaryOutput>,
+    /// Slugs of the browsed category's ancestors, top-level first
+    pub path: Vec<String>,
+    /// Categories directly under the browsed one (top-level categories when
+    /// none is browsed), sorted by slug
+    pub children: Vec<CategoryNodeOutput>,
+    /// Categories the browsed one links to as related
+    pub related: Vec<CategorySummaryOutput>,
+}
+
+impl BrowseCategoriesOutput {
+    /// Browse the top level (`slug` None) or one category; `slug` must name
+    /// a category in `registry`
+    pub fn new(registry: &Registry, slug: Option<&str>) -> Self {
+        let node = |(slug, category): (&String, &Category)| CategoryNodeOutput {
+            slug: slug.clone(),
+            name: category.name.clone(),
+            description: category.description.clone(),
+            subcategories: registry.subcategories(Some(slug.as_str())).len(),
+        };
+        let category = slug.and_then(|slug| registry.categories.get_key_value(slug));
+
+        Self {
+            category: category.map(|(slug, category)| CategorySummaryOutput::new(slug, category)),
+            path: slug
+                .map(|slug| registry.ancestors(slug).into_iter().rev().cloned().collect())
+                .unwrap_or_default(),
+            children: registry.subcategories(slug).into_iter().map(node).collect(),
+            related: category
+                .map(|(_, category)| {
+                    category
+                        .related
+                        .iter()
+                        .filter_map(|slug| registry.categories.get_key_value(slug))
+                        .map(|(slug, category)| CategorySummaryOutput::new(slug, category))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Output of get_provenance
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetProvenanceOutput {
+    /// Curator display name
+    pub curator: String,
+    /// PKARR public key of this node (z-base-32)
+    pub pubkey: String,
+    /// Registry version
+    pub registry_version: String,
+    /// Date the registry was last updated
+    pub updated: String,
+    /// Number of endorsed peer curators
+    pub endorsement_count: usize,
+}
+
+impl GetProvenanceOutput {
+    pub fn new(registry: &Registry, pubkey_z32: &str) -> Self {
+        Self {
+            curator: registry.curator.name.clone(),
+            pubkey: pubkey_z32.to_string(),
+            registry_version: registry.version.clone(),
+            updated: registry.updated.clone(),
+            endorsement_count: registry.endorsements.len(),
+        }
+    }
+}
+
+/// An endorsed peer curator
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct EndorsementOutput {
+    /// Peer public key (z-base-32)
+    pub pubkey: String,
+    /// Peer node URL
+    pub url: String,
+    /// Peer display name, if given
+    pub name: Option<String>,
+    /// Date the endorsement was made
+    pub since: String,
+}
+
+/// Output of get_endorsements
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetEndorsementsOutput {
+    /// Endorsed peer curators
+    pub endorsements: Vec<EndorsementOutput>,
+}
+
+impl GetEndorsementsOutput {
+    pub fn new(endorsements: &[Endorsement]) -> Self {
+        Self {
+            endorsements: endorsements
+                .iter()
+                .map(|endorsement| EndorsementOutput {
+                    pubkey: endorsement.pubkey.clone(),
+                    url: endorsement.url.clone(),
+                    name: endorsement.name.clone(),
+                    since: endorsement.since.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A signed, hash-chained audit log entry
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AuditEntryOutput {
+    /// Entry UUID
+    pub id: String,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+    /// Action type (e.g. "source_added")
+    pub action: String,
+    /// Category slug the entry applies to, if any
+    pub category: Option<String>,
+    /// Action payload
+    pub data: Value,
+    /// Public key of the actor who signed the entry
+    pub actor: String,
+    /// Ed25519 signature (hex)
+    pub signature: String,
+    /// SHA-256 of the previous entry (null for the first entry)
+    pub previous_hash: Option<String>,
+}
+
+/// Output of get_audit_log
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetAuditLogOutput {
+    /// Entries matching the filters, in log order
+    pub entries: Vec<AuditEntryOutput>,
+}
+
+impl GetAuditLogOutput {
+    pub fn new(entries: &[&AuditEntry]) -> Self {
+        Self {
+            entries: entries
+                .iter()
+                .map(|entry| AuditEntryOutput {
+                    id: entry.id.to_string(),
+                    timestamp: timestamp(&entry.timestamp),
+                    action: serde_name(&entry.action),
+                    category: entry.category.clone(),
+                    data: entry.data.clone(),
+                    actor: entry.actor.clone(),
+                    signature: entry.signature.clone(),
+                    previous_hash: entry.previous_hash.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A platform claim with a proof URL for independent verification
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PlatformClaimOutput {
+    /// Platform name (e.g. "github", "nostr", "x")
+    pub platform: String,
+    /// Handle on the platform
+    pub handle: String,
+    /// URL proving the key owns the handle
+    pub proof_url: String,
+}
+
+/// Output of get_identity
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetIdentityOutput {
+    /// Identity public key
+    pub pubkey: String,
+    /// Display name
+    pub name: String,
+    /// Identity type ("human" or "bot")
+    #[serde(rename = "type")]
+    pub identity_type: String,
+    /// Linked platform handles
+    pub platforms: Vec<PlatformClaimOutput>,
+    /// Operator public key (bots only)
+    pub operator_pubkey: Option<String>,
+}
+
+impl GetIdentityOutput {
+    pub fn new(pubkey: &str, identity: &Identity) -> Self {
+        Self {
+            pubkey: pubkey.to_string(),
+            name: identity.name.clone(),
+            identity_type: serde_name(&identity.identity_type),
+            platforms: identity
+                .platforms
+                .iter()
+                .map(|claim| PlatformClaimOutput {
+                    platform: serde_name(&claim.platform),
+                    handle: claim.handle.clone(),
+                    proof_url: claim.proof_url.clone(),
+                })
+                .collect(),
+            operator_pubkey: identity.operator_pubkey.clone(),
+        }
+    }
+}
+
+/// Proposal summary
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ProposalSummaryOutput {
+    /// Proposal UUID
+    pub id: String,
+    /// Requested action (e.g. "add_source")
+    pub action: String,
+    /// Status (pending, approved, rejected, withdrawn)
+    pub status: String,
+    /// Category slug
+    pub category: String,
+    /// Proposer public key
+    pub proposer: String,
+    /// RFC 3339 creation timestamp
+    pub created_at: String,
+}
+
+impl ProposalSummaryOutput {
+    fn new(id: &Uuid, proposal: &Proposal) -> Self {
+        Self {
+            id: id.to_string(),
+            action: serde_name(&proposal.action),
+            status: serde_name(&proposal.status),
+            category: proposal.category.clone(),
+            proposer: proposal.proposer.clone(),
+            created_at: timestamp(&proposal.created_at),
+        }
+    }
+}
+
+/// Output of list_proposals
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ListProposalsOutput {
+    /// Proposals matching the filters, newest first
+    pub proposals: Vec<ProposalSummaryOutput>,
+}
+
+impl ListProposalsOutput {
+    pub fn new(entries: &[(Uuid, &Proposal)]) -> Self {
+        Self {
+            proposals: entries
+                .iter()
+                .map(|(id, proposal)| ProposalSummaryOutput::new(id, proposal))
+                .collect(),
+        }
+    }
+}
+
+/// A vote on a proposal
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct VoteOutput {
+    /// Voter public key
+    pub voter: String,
+    /// "support" or "oppose"
+    pub vote: String,
+    /// RFC 3339 timestamp
+    pub timestamp: String,
+}
+
+/// Output of get_proposal
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetProposalOutput {
+    #[serde(flatten)]
+    pub summary: ProposalSummaryOutput,
+    /// Proposal payload
+    pub data: Value,
+    /// Votes cast on the proposal
+    pub votes: Vec<VoteOutput>,
+}
+
+impl GetProposalOutput {
+    pub fn new(id: &Uuid, proposal: &Proposal) -> Self {
+        Self {
+            summary: ProposalSummaryOutput::new(id, proposal),
+            data: proposal.data.clone(),
+            votes: proposal
+                .votes
+                .iter()
+                .map(|vote| VoteOutput {
+                    voter: vote.voter.clone(),
+                    vote: serde_name(&vote.vote),
+                    timestamp: timestamp(&vote.timestamp),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A category match from one curator in the federated network
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FederatedResultOutput {
+    /// "direct" for this node's registry, "endorsed" for peers
+    pub trust: String,
+    /// True if the peer's cached registry is out of date
+    pub stale: bool,
+    /// Curator of the matching registry
+    pub curator: CuratorOutput,
+    /// Peer display name from the endorsement (null for the local registry)
+    pub peer_name: Option<String>,
+    /// Peer node URL (null for the local registry)
+    pub peer_url: Option<String>,
+    /// Matched category slug
+    pub slug: String,
+    /// Matched category display name
+    pub name: String,
+    /// Matched category description
+    pub description: String,
+    /// Curated primary sources in rank order
+    pub sources: Vec<SourceOutput>,
+    /// Alternates listed after the primary sources, in rank order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub honourable_mentions: Vec<SourceOutput>,
+}
+
+/// Output of get_federated_sources
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetFederatedSourcesOutput {
+    /// Local result first, then endorsed peers
+    pub results: Vec<FederatedResultOutput>,
+}
+
+impl GetFederatedSourcesOutput {
+    pub fn new(matches: &[FederatedMatch]) -> Self {
+        Self {
+            results: matches
+                .iter()
+                .map(|federated| FederatedResultOutput {
+                    trust: match federated.trust {
+                        TrustLevel::Direct => "direct".to_string(),
+                        TrustLevel::Endorsed => "endorsed".to_string(),
+                    },
+                    stale: federated.stale,
+                    curator: CuratorOutput {
+                        name: federated.curator_name.clone(),
+                        pubkey: federated.curator_pubkey.clone(),
+                    },
+                    peer_name: federated.peer_name.clone(),
+                    peer_url: federated.source_url.clone(),
+                    slug: federated.slug.clone(),
+                    name: federated.category_name.clone(),
+                    description: federated.category_description.clone(),
+                    sources: SourceOutput::from_sources(&federated.sources),
+                    honourable_mentions: SourceOutput::from_sources(&federated.honourable_mentions),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Health of one endorsed peer
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PeerStatusOutput {
+    /// Peer public key (z-base-32)
+    pub pubkey: String,
+    /// Peer node URL
+    pub url: String,
+    /// Peer display name, if given
+    pub name: Option<String>,
+    /// "fresh", "stale", "unreachable" or "untrusted"
+    pub status: String,
+    /// When the peer's registry was last fetched successfully (RFC 3339)
+    pub last_success: Option<String>,
+    /// Seconds since the last successful fetch
+    pub seconds_since_success: Option<u64>,
+    /// Why the last fetch failed, if it did
+    pub last_error: Option<String>,
+    /// Failed fetches since the last success
+    pub consecutive_failures: u32,
+    /// Version of the cached peer registry
+    pub registry_version: Option<String>,
+    /// Categories in the cached peer registry
+    pub category_count: Option<usize>,
+}
+
+/// Output of get_peer_status
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GetPeerStatusOutput {
+    /// Peers fetched within the last hour
+    pub fresh: usize,
+    /// Peers serving an older cached registry
+    pub stale: usize,
+    /// Peers with no registry fetched
+    pub unreachable: usize,
+    /// Peers whose registry failed verification
+    pub untrusted: usize,
+    /// Every endorsed peer, sorted by URL
+    pub peers: Vec<PeerStatusOutput>,
+}
+
+impl GetPeerStatusOutput {
+    pub fn new(reports: &[PeerReport]) -> Self {
+        let summary = FederationSummary::new(reports);
+        Self {
+            fresh: summary.fresh,
+            stale: summary.stale,
+            unreachable: summary.unreachable,
+            untrusted: summary.untrusted,
+            peers: reports
+                .iter()
+                .map(|report| PeerStatusOutput {
+                    pubkey: report.pubkey.clone(),
+                    url: report.url.clone(),
+                    name: report.name.clone(),
+                    status: serde_name(&report.status),
+                    last_success: report.last_success.as_ref().map(timestamp),
+                    seconds_since_success: report.seconds_since_success,
+                    last_error: report.last_error.clone(),
+                    consecutive_failures: report.consecutive_failures,
+                    registry_version: report.registry_version.clone(),
+                    category_count: report.category_count,
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::federation::{match_federated, PeerCache, TrustLevel};
+use crate::matcher::{MatchConfig, MatchError, MatchIndex};
+use crate::registry::types::Source;
+use crate::registry::Registry;
+
+/// Prompt arguments for research_topic
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResearchTopicArgs {
+    /// Topic to research, in natural language
+    pub topic: String,
+}
+
+/// Prompt arguments for compare_curators
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompareCuratorsArgs {
+    /// Topic to compare curator recommendations for
+    pub topic: String,
+}
+
+/// Error type for prompt operations
+#[derive(Debug)]
+pub enum PromptError {
+    UnknownPrompt,
+    InvalidParams,
+}
+
+/// Build a prompts/get result with a single user message
+fn prompt_response(description: &str, text: &str) -> Value {
+    json!({
+        "description": description,
+        "messages": [{
+            "role": "user",
+            "content": {"type": "text", "text": text}
+        }]
+    })
+}
+
+/// Format ranked sources as a numbered list with the curator's rationale,
+/// honourable mentions (if any) after the primary sources
+fn format_sources<'a>(
+    sources: impl IntoIterator<Item = &'a Source>,
+    honourable_mentions: impl IntoIterator<Item = &'a Source>,
+) -> String {
+    let format = |source: &Source| {
+        format!(
+            "\n{}. {}\n   URL: {}\n   Type: {:?}\n   Why: {}\n",
+            source.rank, source.name, source.url, source.source_type, source.why
+        )
+    };
+    let mut text: String = sources.into_iter().map(format).collect();
+    let mentions: String = honourable_mentions.into_iter().map(format).collect();
+    if !mentions.is_empty() {
+        text.push_str("\nHonourable mentions (alternates, after the sources above):\n");
+        text.push_str(&mentions);
+    }
+    text
+}
+
+/// Get the prompts/list response with all prompt definitions
+pub fn get_prompts_list() -> Value {
+    json!({
+        "prompts": [
+            {
+                "name": "research_topic",
+                "title": "Research a topic with 3GS sources",
+                "description": "Research a topic starting from the curated ranked sources (plus any honourable mentions) for the best-matching category. The prompt embeds each source's URL and the curator's explanation of why it is worth reading.",
+                "arguments": [
+                    {
+                        "name": "topic",
+                        "description": "Topic to research, e.g. 'learn rust programming'",
+                        "required": true
+                    }
+                ]
+            },
+            {
+                "name": "compare_curators",
+                "title": "Compare sources across federated curators",
+                "description": "Compare the sources recommended for a topic by this node's curator and every endorsed peer curator, noting agreement, differences, and trust level.",
+                "arguments": [
+                    {
+                        "name": "topic",
+                        "description": "Topic to compare recommendations for, e.g. 'set up a bitcoin node'",
+                        "required": true
+                    }
+                ]
+            }
+        ]
+    })
+}
+
+/// Handle a prompts/get request by dispatching to the appropriate prompt
+pub async fn get_prompt(
+    name: &str,
+    arguments: Option<Value>,
+    registry: &Registry,
+    match_index: &MatchIndex,
+    match_config: &MatchConfig,
+    peer_cache: &PeerCache,
+) -> Result<Value, PromptError> {
+    match name {
+        "research_topic" => prompt_research_topic(arguments, registry, match_index, match_config),
+        "compare_curators" => {
+            prompt_compare_curators(arguments, registry, match_index, match_config, peer_cache)
+                .await
+        }
+        _ => Err(PromptError::UnknownPrompt),
+    }
+}
+
+/// Build the research_topic prompt
+///
+/// Matches the topic against the local registry and embeds the matched
+/// category's sources. When nothing matches, the prompt lists the available
+/// categories instead so the agent can tell the user what is covered.
+fn prompt_research_topic(
+    arguments: Option<Value>,
+    registry: &Registry,
+    match_index: &MatchIndex,
+    match_config: &MatchConfig,
+) -> Result<Value, PromptError> {
+    let args: ResearchTopicArgs = arguments
+        .map(serde_json::from_value)
+        .ok_or(PromptError::InvalidParams)?
+        .map_err(|_| PromptError::InvalidParams)?;
+
+    match crate::matcher::match_query(&args.topic, match_index, match_config) {
+        Ok(match_result) => {
+            let category = &match_result.category;
+            let (sources, honourable_mentions) = registry.source_policy().split(&category.sources);
+            let text = format!(
+                "I want to research \"{}\".\n\n{} has vetted these sources for the category \"{}\" ({}):\n{}\nUse these sources as the starting point. Summarize what each one covers, suggest an order to work through them, and cite them by URL. If my question goes beyond what they cover, say so rather than guessing.",
+                args.topic,
+                registry.curator.name,
+                category.name,
+                category.description,
+                format_sources(sources, honourable_mentions),
+            );
+            Ok(prompt_response(
+                &format!("Research {} using curated 3GS sources", category.name),
+                &text,
+            ))
+        }
+        Err(MatchError::BelowThreshold { closest_slug, suggestions, .. }) => {
+            let text = format!(
+                "I want to research \"{}\".\n\n3GS has no curated category for this topic (closest: {}). Related curated categories are: {}.\n\nTell me whether one of these categories fits my question. If none does, say that no curated sources exist for it.",
+                args.topic,
+                closest_slug,
+                suggestions.join(", "),
+            );
+            Ok(prompt_response("No curated 3GS category matches this topic", &text))
+        }
+        Err(MatchError::NoCategories) => {
+            let text = format!(
+                "I want to research \"{}\".\n\n3GS has no curated categories yet. Say that no curated sources exist for this topic.",
+                args.topic,
+            );
+            Ok(prompt_response("No curated 3GS category matches this topic", &text))
+        }
+        Err(MatchError::EmptyQuery | MatchError::QueryAllStopWords) => {
+            Err(PromptError::InvalidParams)
+        }
+    }
+}
+
+/// Build the compare_curators prompt
+///
+/// Embeds the matched category and sources from the local registry and every
+/// reachable peer, each labelled with its curator and trust level.
+async fn prompt_compare_curators(
+    arguments: Option<Value>,
+    registry: &Registry,
+    match_index: &MatchIndex,
+    match_config: &MatchConfig,
+    peer_cache: &PeerCache,
+) -> Result<Value, PromptError> {
+    let args: CompareCuratorsArgs = arguments
+        .map(serde_json::from_value)
+        .ok_or(PromptError::InvalidParams)?
+        .map_err(|_| PromptError::InvalidParams)?;
+
+    if args.topic.trim().is_empty() {
+        return Err(PromptError::InvalidParams);
+    }
+
+    let matches = match_federated(&args.topic, registry, match_index, match_config, peer_cache).await;
+
+    if matches.is_empty() {
+        let text = format!(
+            "I want to compare curated sources for \"{}\", but neither this 3GS node nor any of its endorsed peers has a matching category.\n\nTell me that no curated sources exist for this topic and suggest how I could narrow or rephrase it.",
+            args.topic,
+        );
+        return Ok(prompt_response("No curator has sources for this topic", &text));
+    }
+
+    let mut text = format!(
+        "I want to compare how different curators recommend sources for \"{}\". {} curator(s) have a matching category:\n",
+        args.topic,
+        matches.len(),
+    );
+
+    for federated in &matches {
+        let trust = match federated.trust {
+            TrustLevel::Direct => "direct: this node's own curator",
+            TrustLevel::Endorsed => "endorsed: a peer trusted by this node's curator",
+        };
+        let stale_note = if federated.stale {
+            " (cached copy may be outdated)"
+        } else {
+            ""
+        };
+        text.push_str(&format!(
+            "\n=== {} ({}) ===\nTrust: {}{}\n",
+            federated.curator_name, federated.curator_pubkey, trust, stale_note,
+        ));
+        if let Some(ref url) = federated.source_url {
+            text.push_str(&format!("Node: {}\n", url));
+        }
+        text.push_str(&format!(
+            "Category: {} ({})\nSources:\n{}",
+            federated.category_name,
+            federated.category_description,
+            format_sources(&federated.sources, &federated.honourable_mentions),
+        ));
+    }
+
+    text.push_str("\nCompare these recommendations. Where do the curators agree, where do they differ, and which sources would you start with and why? Weigh direct recommendations above endorsed ones when they conflict, and cite sources by URL.");
+
+    Ok(prompt_response(
+        &format!("Compare curated sources for \"{}\"", args.topic),
+        &text,
+    ))
+}
@@ -0,0 +1,194 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::reload::DataSnapshot;
+use uuid::Uuid;
+
+/// URI scheme for all 3GS resources
+pub const URI_SCHEME: &str = "3gs://";
+
+/// MIME type of every resource body
+const JSON_MIME_TYPE: &str = "application/json";
+
+/// Error type for resource operations
+#[derive(Debug)]
+pub enum ResourceError {
+    /// URI does not name a known resource
+    NotFound,
+    /// Resource exists but could not be serialized
+    Serialization(String),
+}
+
+/// A resource addressed by a 3gs:// URI
+#[derive(Debug, Clone, PartialEq)]
+enum ResourceUri {
+    Category(String),
+    Audit,
+    Identity(String),
+    Proposal(Uuid),
+}
+
+impl ResourceUri {
+    /// Parse a 3gs:// URI. Returns None for unknown schemes or paths.
+    fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix(URI_SCHEME)?;
+        if path == "audit" {
+            return Some(Self::Audit);
+        }
+
+        let (kind, key) = path.split_once('/')?;
+        if key.is_empty() || key.contains('/') {
+            return None;
+        }
+        match kind {
+            "category" => Some(Self::Category(key.to_string())),
+            "identity" => Some(Self::Identity(key.to_string())),
+            "proposal" => Uuid::parse_str(key).ok().map(Self::Proposal),
+            _ => None,
+        }
+    }
+}
+
+/// Entry in a resources/list response
+#[derive(Debug, Serialize)]
+struct ResourceEntry {
+    uri: String,
+    name: String,
+    title: String,
+    description: String,
+    #[serde(rename = "mimeType")]
+    mime_type: &'static str,
+}
+
+/// Get the resources/list response: every category, the audit log,
+/// every identity, and every proposal in the current snapshot
+pub fn list_resources(data: &DataSnapshot) -> Value {
+    let mut resources = Vec::new();
+
+    let mut categories: Vec<_> = data.registry.categories.iter().collect();
+    categories.sort_by_key(|(slug, _)| *slug);
+    for (slug, category) in categories {
+        resources.push(ResourceEntry {
+            uri: format!("{}category/{}", URI_SCHEME, slug),
+            name: slug.clone(),
+            title: category.name.clone(),
+            description: category.description.clone(),
+            mime_type: JSON_MIME_TYPE,
+        });
+    }
+
+    resources.push(ResourceEntry {
+        uri: format!("{}audit", URI_SCHEME),
+        name: "audit".to_string(),
+        title: "Audit Log".to_string(),
+        description: format!(
+            "Signed, hash-chained log of all registry changes ({} entries)",
+            data.audit_log.len()
+        ),
+        mime_type: JSON_MIME_TYPE,
+    });
+
+    let mut identities: Vec<_> = data.identities.iter().collect();
+    identities.sort_by_key(|(pubkey, _)| *pubkey);
+    for (pubkey, identity) in identities {
+        resources.push(ResourceEntry {
+            uri: format!("{}identity/{}", URI_SCHEME, pubkey),
+            name: pubkey.clone(),
+            title: identity.name.clone(),
+            description: format!("Registered identity: {}", identity.name),
+            mime_type: JSON_MIME_TYPE,
+        });
+    }
+
+    let mut proposals: Vec<_> = data.proposals.iter().collect();
+    proposals.sort_by_key(|(id, _)| *id);
+    for (id, proposal) in proposals {
+        let action_str = serde_json::to_value(&proposal.action)
+            .ok()
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let status_str = serde_json::to_value(&proposal.status)
+            .ok()
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        resources.push(ResourceEntry {
+            uri: format!("{}proposal/{}", URI_SCHEME, id),
+            name: id.to_string(),
+            title: format!("Proposal {}", id),
+            description: format!(
+                "{} proposal for {} ({})",
+                action_str, proposal.category, status_str
+            ),
+            mime_type: JSON_MIME_TYPE,
+        });
+    }
+
+    json!({ "resources": resources })
+}
+
+/// Get the resources/templates/list response
+pub fn list_resource_templates() -> Value {
+    json!({
+        "resourceTemplates": [
+            {
+                "uriTemplate": format!("{}category/{{slug}}", URI_SCHEME),
+                "name": "category",
+                "title": "Category",
+                "description": "A curated topic category with its name, description, query patterns, and ranked sources (primary sources and any honourable mentions). Slugs are listed by the list_categories tool.",
+                "mimeType": JSON_MIME_TYPE
+            },
+            {
+                "uriTemplate": format!("{}identity/{{pubkey}}", URI_SCHEME),
+                "name": "identity",
+                "title": "Identity",
+                "description": "A registered identity by PKARR public key, with platform claims and proof URLs.",
+                "mimeType": JSON_MIME_TYPE
+            },
+            {
+                "uriTemplate": format!("{}proposal/{{id}}", URI_SCHEME),
+                "name": "proposal",
+                "title": "Proposal",
+                "description": "A community proposal by UUID, including its data and votes.",
+                "mimeType": JSON_MIME_TYPE
+            }
+        ]
+    })
+}
+
+/// Handle a resources/read request.
+///
+/// Bodies are the same JSON the HTTP API serves for the underlying
+/// Category, AuditEntry list, Identity, or Proposal.
+pub fn read_resource(uri: &str, data: &DataSnapshot) -> Result<Value, ResourceError> {
+    let text = match ResourceUri::parse(uri).ok_or(ResourceError::NotFound)? {
+        ResourceUri::Category(slug) => {
+            let category = data
+                .registry
+                .categories
+                .get(&slug)
+                .ok_or(ResourceError::NotFound)?;
+            serde_json::to_string_pretty(category)
+        }
+        ResourceUri::Audit => serde_json::to_string_pretty(&data.audit_log),
+        ResourceUri::Identity(pubkey) => {
+            let identity = data.identities.get(&pubkey).ok_or(ResourceError::NotFound)?;
+            serde_json::to_string_pretty(identity)
+        }
+        ResourceUri::Proposal(id) => {
+            let proposal = data.proposals.get(&id).ok_or(ResourceError::NotFound)?;
+            proposal
+                .to_json_with_id(&id)
+                .and_then(|value| serde_json::to_string_pretty(&value))
+        }
+    }
+    .map_err(|e| ResourceError::Serialization(e.to_string()))?;
+
+    Ok(json!({
+        "contents": [{
+            "uri": uri,
+            "mimeType": JSON_MIME_TYPE,
+            "text": text
+        }]
+    }))
+}
@@ -1,13 +1,25 @@
 use schemars::{schema_for, JsonSchema};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+use crate::mcp::output::{
+    BrowseCategoriesOutput, GetAuditLogOutput, GetEndorsementsOutput, GetFederatedSourcesOutput,
+    GetIdentityOutput, GetPeerStatusOutput, GetProposalOutput, GetProvenanceOutput,
+    GetSourcesOutput, ListCategoriesOutput, ListProposalsOutput,
+};
+
+use crate::analytics::{QueryEvent, QueryLog, QueryOutcome};
 use crate::audit::{filter_entries, AuditEntry, AuditFilterParams};
 use crate::contributions::Proposal;
-use crate::federation::{PeerCache, PeerStatus};
+use crate::federation::{peer_reports, PeerCache, TrustLevel};
 use crate::identity::{Identity, IdentityType};
-use crate::matcher::{MatchConfig, MatchError};
+use crate::matcher::{
+    Language, MatchConfig, MatchError, MatchIndex, MatchMode, MatchResult,
+    NEGATIVE_PATTERN_PENALTY,
+};
+use crate::registry::types::{is_language_code, Source, SourceFilter, SourcePolicy};
 use crate::registry::Registry;
+use crate::reload::DataSnapshot;
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -19,6 +31,14 @@ fn tool_response(text: &str, is_error: bool) -> Value {
     })
 }
 
+/// Build a successful MCP tool response with text content and structuredContent.
+/// The text is kept for clients that do not read structured output.
+fn structured_response<T: Serialize>(text: &str, output: &T) -> Value {
+    let mut response = tool_response(text, false);
+    response["structuredContent"] = serde_json::to_value(output).unwrap_or(Value::Null);
+    response
+}
+
 /// Tool parameter type for get_sources
 #[derive(Debug, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -28,15 +48,59 @@ pub struct GetSourcesParams {
     pub query: String,
     /// Optional match threshold (0.0-1.0) for sensitivity tuning.
     /// Lower values return more results, higher values require closer matches.
+    /// Categories that set their own threshold in the registry keep it.
     /// Default: 0.4
     #[serde(skip_serializing_if = "Option::is_none")]
     pub threshold: Option<f64>,
+    /// Optional maximum number of matching categories to return (1-10).
+    /// Matches beyond the first are listed as alternatives, best first.
+    /// Default: 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Optional flag to include a per-surface score breakdown for each match
+    /// (slug, name, each query pattern, and keyword scores).
+    /// Default: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<bool>,
+    /// Optional ISO 639-1 code of the query's language: "en", "es" or "de".
+    /// Selects the stop words used to normalize the query and the category
+    /// translations it is matched against.
+    /// Default: detected from the query's stop words, falling back to "en"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Optional flag to return only sources the curator recorded as free.
+    /// Sources with no recorded cost are left out.
+    /// Default: false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub free_only: Option<bool>,
+    /// Optional ISO 639-1 code (e.g. "en"): return only sources the curator
+    /// recorded as written in this language.
+    /// Sources with no recorded language are left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_lang: Option<String>,
 }
 
+/// Maximum number of matches get_sources returns
+const MAX_SOURCES_LIMIT: usize = 10;
+
 /// Tool parameter type for list_categories
 #[derive(Debug, Deserialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
-pub struct ListCategoriesParams {}
+pub struct ListCategoriesParams {
+    /// Only list categories with this tag (e.g. "privacy", "programming")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// Tool parameter type for browse_categories
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BrowseCategoriesParams {
+    /// Slug of the category to browse (e.g. "rust-learning").
+    /// Omit to start from the top-level categories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+}
 
 /// Tool parameter type for get_provenance
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -103,6 +167,11 @@ pub struct GetFederatedSourcesParams {
     pub threshold: Option<f64>,
 }
 
+/// Tool parameter type for get_peer_status
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetPeerStatusParams {}
+
 /// Error type for tool call operations
 #[derive(Debug)]
 pub enum ToolCallError {
@@ -110,10 +179,11 @@ pub enum ToolCallError {
     InvalidParams,
 }
 
-/// Get the tools/list response with all 9 tool definitions
+/// Get the tools/list response with all 11 tool definitions
 pub fn get_tools_list() -> Value {
     let get_sources_schema = schema_for!(GetSourcesParams);
     let list_categories_schema = schema_for!(ListCategoriesParams);
+    let browse_categories_schema = schema_for!(BrowseCategoriesParams);
     let get_provenance_schema = schema_for!(GetProvenanceParams);
     let get_endorsements_schema = schema_for!(GetEndorsementsParams);
     let get_audit_log_schema = schema_for!(GetAuditLogParams);
@@ -121,53 +191,75 @@ pub fn get_tools_list() -> Value {
     let list_proposals_schema = schema_for!(ListProposalsParams);
     let get_proposal_schema = schema_for!(GetProposalParams);
     let get_federated_sources_schema = schema_for!(GetFederatedSourcesParams);
+    let get_peer_status_schema = schema_for!(GetPeerStatusParams);
 
     json!({
         "tools": [
             {
                 "name": "get_sources",
-                "description": "Find three curated, human-vetted sources for a topic. Searches across categories using fuzzy matching against known query patterns. Returns the matching category with name, description, and all three ranked sources including URLs and explanations. Example queries: 'learn rust programming', 'set up a bitcoin node', 'self-host email server'.",
-                "inputSchema": serde_json::to_value(get_sources_schema).unwrap()
+                "description": "Find curated, human-vetted sources for a topic. Searches across categories using fuzzy matching against known query patterns. Returns the matching category with name, description, and its ranked sources (usually three, plus any honourable mentions) including URLs and explanations. Example queries: 'learn rust programming', 'set up a bitcoin node', 'self-host email server'. Queries may also be in Spanish or German; the language is detected from the query or given with 'lang'. Sources carry optional cost, license, language, format, last-verified and archived metadata; use 'free_only' or 'source_lang' to return only free sources or sources in one language.",
+                "inputSchema": serde_json::to_value(get_sources_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetSourcesOutput)).unwrap()
             },
             {
                 "name": "list_categories",
-                "description": "List all available topic categories in the registry. Returns each category's slug identifier, display name, and description. Use this to discover what topics have curated sources before querying. No parameters required.",
-                "inputSchema": serde_json::to_value(list_categories_schema).unwrap()
+                "description": "List all available topic categories in the registry. Returns each category's slug identifier, display name, description, tags, parent, and related categories. Use this to discover what topics have curated sources before querying. Pass 'tag' to list only categories with that tag.",
+                "inputSchema": serde_json::to_value(list_categories_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(ListCategoriesOutput)).unwrap()
+            },
+            {
+                "name": "browse_categories",
+                "description": "Browse the category hierarchy one level at a time. Without a slug, returns the top-level categories; with a slug, returns that category with its path from the top level, its subcategories, and its related categories. Each subcategory lists how many categories sit under it.",
+                "inputSchema": serde_json::to_value(browse_categories_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(BrowseCategoriesOutput)).unwrap()
             },
             {
                 "name": "get_provenance",
                 "description": "Get curator identity and verification information for this registry. Returns the curator's name, PKARR public key (when available), registry version, and instructions for cryptographic verification of source authenticity. No parameters required.",
-                "inputSchema": serde_json::to_value(get_provenance_schema).unwrap()
+                "inputSchema": serde_json::to_value(get_provenance_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetProvenanceOutput)).unwrap()
             },
             {
                 "name": "get_endorsements",
                 "description": "Get the list of endorsed curators for this registry. In v1, this returns an empty list. Future versions will support curator endorsements with trust relationships. No parameters required.",
-                "inputSchema": serde_json::to_value(get_endorsements_schema).unwrap()
+                "inputSchema": serde_json::to_value(get_endorsements_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetEndorsementsOutput)).unwrap()
             },
             {
                 "name": "get_audit_log",
                 "description": "Get the public audit log of all registry changes. Returns signed, hash-chained entries showing when sources and categories were added, updated, or removed. Supports optional filtering by timestamp (since), category slug (category), and action type (action).",
-                "inputSchema": serde_json::to_value(get_audit_log_schema).unwrap()
+                "inputSchema": serde_json::to_value(get_audit_log_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetAuditLogOutput)).unwrap()
             },
             {
                 "name": "get_identity",
                 "description": "Look up a registered identity by PKARR public key. Returns the identity's display name, type (human/bot), linked platform handles with proof URLs for independent verification, and operator info for bot identities.",
-                "inputSchema": serde_json::to_value(get_identity_schema).unwrap()
+                "inputSchema": serde_json::to_value(get_identity_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetIdentityOutput)).unwrap()
             },
             {
                 "name": "list_proposals",
                 "description": "List community proposals for source changes. Returns proposal summaries with optional filtering by status (pending, approved, rejected, withdrawn) and category slug.",
-                "inputSchema": serde_json::to_value(list_proposals_schema).unwrap()
+                "inputSchema": serde_json::to_value(list_proposals_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(ListProposalsOutput)).unwrap()
             },
             {
                 "name": "get_proposal",
                 "description": "Get full details of a community proposal by UUID, including all votes with voter pubkeys and timestamps.",
-                "inputSchema": serde_json::to_value(get_proposal_schema).unwrap()
+                "inputSchema": serde_json::to_value(get_proposal_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetProposalOutput)).unwrap()
             },
             {
                 "name": "get_federated_sources",
                 "description": "Search for curated sources across the federated network. Queries the local registry and all endorsed peer registries, returning results tagged with trust level (direct for local, endorsed for peers). Stale peer data is flagged. Use this instead of get_sources when you want results from the entire network.",
-                "inputSchema": serde_json::to_value(get_federated_sources_schema).unwrap()
+                "inputSchema": serde_json::to_value(get_federated_sources_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetFederatedSourcesOutput)).unwrap()
+            },
+            {
+                "name": "get_peer_status",
+                "description": "Get the health of each endorsed peer in the federated network. Returns each peer's URL, name, status (fresh, stale, unreachable or untrusted), time since its registry was last fetched, the last fetch error, and the version and category count of its cached registry, with counts by status. No parameters required.",
+                "inputSchema": serde_json::to_value(get_peer_status_schema).unwrap(),
+                "outputSchema": serde_json::to_value(schema_for!(GetPeerStatusOutput)).unwrap()
             }
         ]
     })
@@ -177,39 +269,181 @@ pub fn get_tools_list() -> Value {
 pub async fn handle_tool_call(
     name: &str,
     arguments: Option<Value>,
-    registry: &Registry,
+    data: &DataSnapshot,
     match_config: &MatchConfig,
     pubkey_z32: &str,
-    audit_log: &[AuditEntry],
-    identities: &HashMap<String, Identity>,
-    proposals: &HashMap<Uuid, Proposal>,
     peer_cache: &PeerCache,
+    query_log: Option<&QueryLog>,
 ) -> Result<Value, ToolCallError> {
+    let registry = &data.registry;
+    let audit_log = &data.audit_log;
+    let identities = &data.identities;
+    let proposals = &data.proposals;
     match name {
-        "get_sources" => tool_get_sources(arguments, registry, match_config).await,
+        "get_sources" => {
+            tool_get_sources(arguments, registry, &data.match_index, match_config, query_log).await
+        }
         "list_categories" => tool_list_categories(arguments, registry).await,
+        "browse_categories" => tool_browse_categories(arguments, registry).await,
         "get_provenance" => tool_get_provenance(arguments, registry, pubkey_z32).await,
         "get_endorsements" => tool_get_endorsements(arguments, registry).await,
         "get_audit_log" => tool_get_audit_log(arguments, audit_log).await,
         "get_identity" => tool_get_identity(arguments, identities).await,
         "list_proposals" => tool_list_proposals(arguments, proposals).await,
         "get_proposal" => tool_get_proposal(arguments, proposals).await,
-        "get_federated_sources" => tool_get_federated_sources(arguments, registry, match_config, peer_cache).await,
+        "get_federated_sources" => tool_get_federated_sources(arguments, registry, &data.match_index, match_config, peer_cache).await,
+        "get_peer_status" => tool_get_peer_status(arguments, peer_cache).await,
         _ => Err(ToolCallError::UnknownTool),
     }
 }
 
+/// Format ranked sources as a numbered list, honourable mentions (if any)
+/// under their own heading after the primary sources
+fn format_sources<'a>(
+    sources: impl IntoIterator<Item = &'a Source>,
+    honourable_mentions: impl IntoIterator<Item = &'a Source>,
+) -> String {
+    let format = |source: &Source| {
+        format!(
+            "\n{}. {}\n   URL: {}\n   Type: {:?}\n   Why: {}\n{}",
+            source.rank,
+            source.name,
+            source.url,
+            source.source_type,
+            source.why,
+            format_metadata(source)
+        )
+    };
+    let mut text: String = sources.into_iter().map(format).collect();
+    let mentions: String = honourable_mentions.into_iter().map(format).collect();
+    if !mentions.is_empty() {
+        text.push_str("\nHonourable mentions:\n");
+        text.push_str(&mentions);
+    }
+    text
+}
+
+/// Format a source's optional metadata, one line for whatever is recorded
+/// and a warning line if it is archived
+fn format_metadata(source: &Source) -> String {
+    let mut details = Vec::new();
+    if let Some(cost) = source.cost {
+        details.push(format!("Cost: {:?}", cost));
+    }
+    if let Some(license) = &source.license {
+        details.push(format!("License: {}", license));
+    }
+    if let Some(language) = &source.language {
+        details.push(format!("Language: {}", language));
+    }
+    if let Some(format) = source.format {
+        details.push(format!("Format: {:?}", format));
+    }
+    if let Some(date) = source.last_verified {
+        details.push(format!("Last verified: {}", date));
+    }
+
+    let mut text = String::new();
+    if !details.is_empty() {
+        text.push_str(&format!("   {}\n", details.join(" | ")));
+    }
+    if source.archived {
+        text.push_str("   Archived: no longer maintained\n");
+    }
+    text
+}
+
+/// Format a category's sources after applying the get_sources filters,
+/// noting how many the filters left out
+fn format_filtered_sources(sources: &[Source], policy: SourcePolicy, filter: &SourceFilter) -> String {
+    let (primary, honourable_mentions) = policy.split(sources);
+    let total = primary.len() + honourable_mentions.len();
+    let keep = |source: &&Source| filter.matches(source);
+    let primary: Vec<&Source> = primary.into_iter().filter(keep).collect();
+    let honourable_mentions: Vec<&Source> = honourable_mentions.into_iter().filter(keep).collect();
+
+    let filtered_out = total - primary.len() - honourable_mentions.len();
+    let mut text = format_sources(primary, honourable_mentions);
+    if filtered_out > 0 {
+        text.push_str(&format!(
+            "\n({} of {} sources left out by the free_only/source_lang filters)\n",
+            filtered_out, total
+        ));
+    }
+    text
+}
+
+/// Format the explanation block for one match
+fn format_explanation(match_result: &MatchResult, config: &MatchConfig) -> String {
+    let breakdown = &match_result.breakdown;
+    let mut text = format!(
+        "\nMatch score: {:.2}\n   Slug similarity: {:.2}\n   Name similarity: {:.2}\n",
+        match_result.score, breakdown.slug, breakdown.name
+    );
+    for pattern in &breakdown.patterns {
+        text.push_str(&format!(
+            "   Pattern \"{}\": {:.2}\n",
+            pattern.pattern, pattern.score
+        ));
+    }
+    text.push_str(&format!(
+        "   Fuzzy (best of above): {:.2}\n   Keyword (slug terms in query): {:.2}\n",
+        breakdown.fuzzy, breakdown.keyword
+    ));
+    if let Some(bm25) = breakdown.bm25 {
+        text.push_str(&format!("   BM25 (query terms in category text): {:.2}\n", bm25));
+    }
+    if let Some(pattern) = &breakdown.negative_pattern {
+        text.push_str(&format!(
+            "   Negative pattern \"{}\" in query: -{:.2}\n",
+            pattern, NEGATIVE_PATTERN_PENALTY
+        ));
+    }
+
+    let fuzzy_formula = format!(
+        "{:.2} x {:.2} + {:.2} x {:.2}",
+        config.match_fuzzy_weight, breakdown.fuzzy, config.match_keyword_weight, breakdown.keyword
+    );
+    let formula = match config.match_mode {
+        MatchMode::Fuzzy => fuzzy_formula,
+        MatchMode::Bm25 => format!("BM25 {:.2}", breakdown.bm25.unwrap_or(0.0)),
+        MatchMode::Hybrid => format!(
+            "{:.2} x ({}) + {:.2} x {:.2}",
+            1.0 - config.match_bm25_weight,
+            fuzzy_formula,
+            config.match_bm25_weight,
+            breakdown.bm25.unwrap_or(0.0)
+        ),
+    };
+    let penalty = if breakdown.negative_pattern.is_some() {
+        format!(" - {:.2}", NEGATIVE_PATTERN_PENALTY)
+    } else {
+        String::new()
+    };
+    text.push_str(&format!("   Score = {}{}\n", formula, penalty));
+    text
+}
+
 /// Handle get_sources tool call
 ///
 /// Matches a natural language query against the registry categories and returns
-/// the matching category with all three curated sources. Supports optional threshold
-/// parameter for match sensitivity tuning.
+/// the matching category with its ranked curated sources. Supports optional threshold
+/// parameter for match sensitivity tuning, `limit` for returning further matching
+/// categories as alternatives, `explain` for a per-surface score breakdown,
+/// `lang` to override the detected query language, and `free_only` and
+/// `source_lang` to leave out sources by their recorded cost and language.
 ///
 /// Returns MCP content with isError: true for no match, empty query, or stop-word-only queries.
+///
+/// When a query log is configured, the outcome of every query that normalizes
+/// to something is recorded: the matched slug and score, or the normalized
+/// query and closest category when nothing matched.
 async fn tool_get_sources(
     arguments: Option<Value>,
     registry: &Registry,
+    match_index: &MatchIndex,
     match_config: &MatchConfig,
+    query_log: Option<&QueryLog>,
 ) -> Result<Value, ToolCallError> {
     // Parse arguments
     let params: GetSourcesParams = if let Some(args) = arguments {
@@ -218,57 +452,123 @@ async fn tool_get_sources(
         return Err(ToolCallError::InvalidParams);
     };
 
+    let limit = params.limit.unwrap_or(1);
+    if !(1..=MAX_SOURCES_LIMIT).contains(&limit) {
+        return Err(ToolCallError::InvalidParams);
+    }
+    let explain = params.explain.unwrap_or(false);
+    let language = params
+        .lang
+        .as_deref()
+        .map(|code| Language::from_code(code).ok_or(ToolCallError::InvalidParams))
+        .transpose()?;
+    let filter = SourceFilter {
+        free_only: params.free_only.unwrap_or(false),
+        language: params
+            .source_lang
+            .map(|code| code.to_lowercase())
+            .map(|code| {
+                if is_language_code(&code) {
+                    Ok(code)
+                } else {
+                    Err(ToolCallError::InvalidParams)
+                }
+            })
+            .transpose()?,
+    };
+
     // Create modified config if threshold provided
     let config = if let Some(threshold) = params.threshold {
         MatchConfig {
             match_threshold: threshold,
-            match_fuzzy_weight: match_config.match_fuzzy_weight,
-            match_keyword_weight: match_config.match_keyword_weight,
+            ..match_config.clone()
         }
     } else {
         match_config.clone()
     };
 
     // Attempt to match query
-    let result = crate::matcher::match_query(&params.query, registry, &config);
+    let result =
+        crate::matcher::match_query_top_k(&params.query, match_index, &config, limit, language);
+
+    if let Some(query_log) = query_log {
+        let outcome = match &result {
+            Ok(results) => Some(QueryOutcome::Matched {
+                slug: results[0].slug.clone(),
+                score: results[0].score,
+            }),
+            Err(MatchError::BelowThreshold {
+                closest_slug,
+                closest_score,
+                normalized_query,
+                ..
+            }) => Some(QueryOutcome::Unmatched {
+                query: normalized_query.clone(),
+                closest_slug: closest_slug.clone(),
+                closest_score: *closest_score,
+            }),
+            // Nothing searchable to learn from
+            Err(_) => None,
+        };
+        if let Some(outcome) = outcome {
+            query_log.record(QueryEvent::new(outcome)).await;
+        }
+    }
 
     match result {
-        Ok(match_result) => {
+        Ok(results) => {
             // Format successful response
+            let policy = registry.source_policy();
+            let match_result = &results[0];
             let category = &match_result.category;
             let mut text = format!(
-                "Category: {}\nSlug: {}\nDescription: {}\n\nRegistry Version: {}\nCurator: {} ({})\n\nSources:\n",
+                "Category: {}\nSlug: {}\nDescription: {}\nLanguage: {}\n\nRegistry Version: {}\nCurator: {} ({})\n\nSources:\n",
                 category.name,
                 match_result.slug,
                 category.description,
+                match_result.language.code(),
                 registry.version,
                 registry.curator.name,
                 registry.curator.pubkey
             );
 
-            for source in &category.sources {
+            text.push_str(&format_filtered_sources(&category.sources, policy, &filter));
+
+            if explain {
+                text.push_str(&format_explanation(match_result, &config));
+            }
+
+            for (index, alternative) in results.iter().enumerate().skip(1) {
+                let category = &alternative.category;
                 text.push_str(&format!(
-                    "\n{}. {}\n   URL: {}\n   Type: {:?}\n   Why: {}\n",
-                    source.rank, source.name, source.url, source.source_type, source.why
+                    "\n--- Alternative match {} (score: {:.2}) ---\nCategory: {}\nSlug: {}\nDescription: {}\n\nSources:\n",
+                    index,
+                    alternative.score,
+                    category.name,
+                    alternative.slug,
+                    category.description
                 ));
+                text.push_str(&format_filtered_sources(&category.sources, policy, &filter));
+                if explain {
+                    text.push_str(&format_explanation(alternative, &config));
+                }
             }
 
-            Ok(tool_response(&text, false))
+            let output = GetSourcesOutput::new(&results, registry, &filter, &config, explain);
+            Ok(structured_response(&text, &output))
         }
         Err(MatchError::BelowThreshold {
             closest_slug,
             closest_score,
-            all_slugs,
+            suggestions,
             ..
         }) => {
-            let mut slugs = all_slugs;
-            slugs.sort();
             let text = format!(
-                "No matching category found for query '{}'. Closest match: {} (score: {:.2}). Available categories: {}",
+                "No matching category found for query '{}'. Closest match: {} (score: {:.2}). You could also try: {}. Use browse_categories or list_categories to see every category.",
                 params.query,
                 closest_slug,
                 closest_score,
-                slugs.join(", ")
+                suggestions.join(", ")
             );
             Ok(tool_response(&text, true))
         }
@@ -280,38 +580,121 @@ async fn tool_get_sources(
             let text = "Query contains only common words (stop words) with no searchable content. Try more specific terms.";
             Ok(tool_response(text, true))
         }
+        Err(MatchError::NoCategories) => {
+            let text = "This registry has no categories yet, so no curated sources can match.";
+            Ok(tool_response(text, true))
+        }
     }
 }
 
 /// Handle list_categories tool call
 ///
 /// Returns a formatted list of all available categories in the registry,
-/// sorted by slug. Each entry includes the slug, display name, and description.
+/// sorted by slug, or only those carrying the optional `tag`. Each entry
+/// includes the slug, display name, description, and any tags.
 async fn tool_list_categories(
     arguments: Option<Value>,
     registry: &Registry,
 ) -> Result<Value, ToolCallError> {
-    // Parse arguments if provided (should be empty object or None)
-    if let Some(args) = arguments {
-        // Try to parse to validate structure
-        let _params: ListCategoriesParams =
-            serde_json::from_value(args).map_err(|_| ToolCallError::InvalidParams)?;
-    }
+    // Parse arguments if provided (empty object, or a tag filter)
+    let params: ListCategoriesParams = match arguments {
+        Some(args) => serde_json::from_value(args).map_err(|_| ToolCallError::InvalidParams)?,
+        None => ListCategoriesParams { tag: None },
+    };
 
     // Collect and sort categories by slug
-    let mut categories: Vec<_> = registry.categories.iter().collect();
+    let mut categories: Vec<_> = registry
+        .categories
+        .iter()
+        .filter(|(_, category)| {
+            params
+                .tag
+                .as_ref()
+                .is_none_or(|tag| category.tags.contains(tag))
+        })
+        .collect();
     categories.sort_by_key(|(slug, _)| *slug);
 
-    let mut text = format!("Categories ({}):\n", categories.len());
+    let output = ListCategoriesOutput::new(&categories);
+    let mut text = match &params.tag {
+        Some(tag) => format!("Categories tagged '{}' ({}):\n", tag, categories.len()),
+        None => format!("Categories ({}):\n", categories.len()),
+    };
 
     for (slug, category) in categories {
         text.push_str(&format!(
             "\n- {}: {}\n  {}\n",
             slug, category.name, category.description
         ));
+        if !category.tags.is_empty() {
+            text.push_str(&format!("  Tags: {}\n", category.tags.join(", ")));
+        }
+    }
+
+    Ok(structured_response(&text, &output))
+}
+
+/// Handle browse_categories tool call
+///
+/// Without a slug, lists the top-level categories. With one, shows that
+/// category's path from the top level, its subcategories, and its related
+/// categories. An unknown slug is a tool error pointing back to the top level.
+async fn tool_browse_categories(
+    arguments: Option<Value>,
+    registry: &Registry,
+) -> Result<Value, ToolCallError> {
+    let params: BrowseCategoriesParams = match arguments {
+        Some(args) => serde_json::from_value(args).map_err(|_| ToolCallError::InvalidParams)?,
+        None => BrowseCategoriesParams { slug: None },
+    };
+    let slug = params.slug.as_deref();
+
+    let output = BrowseCategoriesOutput::new(registry, slug);
+    let mut text = match (&output.category, slug) {
+        (Some(category), _) => {
+            let mut path = output.path.clone();
+            path.push(category.slug.clone());
+            let mut text = format!(
+                "Category: {}\nSlug: {}\nPath: {}\nDescription: {}\n",
+                category.name,
+                category.slug,
+                path.join(" > "),
+                category.description
+            );
+            if !category.tags.is_empty() {
+                text.push_str(&format!("Tags: {}\n", category.tags.join(", ")));
+            }
+            text.push_str(&format!("\nSubcategories ({}):\n", output.children.len()));
+            text
+        }
+        (None, Some(slug)) => {
+            let text = format!(
+                "Unknown category '{}'. Call browse_categories without a slug to see the top-level categories.",
+                slug
+            );
+            return Ok(tool_response(&text, true));
+        }
+        (None, None) => format!("Top-level categories ({}):\n", output.children.len()),
+    };
+
+    for child in &output.children {
+        text.push_str(&format!(
+            "\n- {}: {} ({} subcategories)\n  {}\n",
+            child.slug, child.name, child.subcategories, child.description
+        ));
+    }
+
+    if !output.related.is_empty() {
+        text.push_str("\nRelated categories:\n");
+        for related in &output.related {
+            text.push_str(&format!(
+                "\n- {}: {}\n  {}\n",
+                related.slug, related.name, related.description
+            ));
+        }
     }
 
-    Ok(tool_response(&text, false))
+    Ok(structured_response(&text, &output))
 }
 
 /// Handle get_provenance tool call
@@ -340,7 +723,7 @@ async fn tool_get_provenance(
         registry.curator.name
     );
 
-    Ok(tool_response(&text, false))
+    Ok(structured_response(&text, &GetProvenanceOutput::new(registry, pubkey_z32)))
 }
 
 /// Handle get_endorsements tool call
@@ -359,7 +742,8 @@ async fn tool_get_endorsements(
 
     if registry.endorsements.is_empty() {
         let text = "Endorsements: 0\n\nThis registry does not yet have any endorsements. Endorsements allow\nother curators to vouch for the quality of this registry's sources.\nThis feature will be available in a future version.";
-        return Ok(tool_response(text, false));
+        let output = GetEndorsementsOutput::new(&registry.endorsements);
+        return Ok(structured_response(text, &output));
     }
 
     let mut text = format!("Endorsements: {}\n", registry.endorsements.len());
@@ -371,7 +755,7 @@ async fn tool_get_endorsements(
         ));
     }
 
-    Ok(tool_response(&text, false))
+    Ok(structured_response(&text, &GetEndorsementsOutput::new(&registry.endorsements)))
 }
 
 /// Handle get_audit_log tool call
@@ -432,7 +816,7 @@ async fn tool_get_audit_log(
         ));
     }
 
-    Ok(tool_response(&text, false))
+    Ok(structured_response(&text, &GetAuditLogOutput::new(&filtered)))
 }
 
 /// Handle get_identity tool call
@@ -475,7 +859,7 @@ async fn tool_get_identity(
                 }
             }
 
-            Ok(tool_response(&text, false))
+            Ok(structured_response(&text, &GetIdentityOutput::new(&params.pubkey, identity)))
         }
         None => {
             let text = format!("No identity found for pubkey: {}", params.pubkey);
@@ -556,7 +940,7 @@ async fn tool_list_proposals(
         ));
     }
 
-    Ok(tool_response(&text, false))
+    Ok(structured_response(&text, &ListProposalsOutput::new(&entries)))
 }
 
 /// Handle get_proposal tool call
@@ -621,7 +1005,7 @@ async fn tool_get_proposal(
                 ));
             }
 
-            Ok(tool_response(&text, false))
+            Ok(structured_response(&text, &GetProposalOutput::new(&uuid, proposal)))
         }
         None => {
             let text = format!("No proposal found for id: {}", uuid);
@@ -637,6 +1021,7 @@ async fn tool_get_proposal(
 async fn tool_get_federated_sources(
     arguments: Option<Value>,
     registry: &Registry,
+    match_index: &MatchIndex,
     match_config: &MatchConfig,
     peer_cache: &PeerCache,
 ) -> Result<Value, ToolCallError> {
@@ -656,89 +1041,39 @@ async fn tool_get_federated_sources(
     let config = if let Some(threshold) = params.threshold {
         MatchConfig {
             match_threshold: threshold,
-            match_fuzzy_weight: match_config.match_fuzzy_weight,
-            match_keyword_weight: match_config.match_keyword_weight,
+            ..match_config.clone()
         }
     } else {
         match_config.clone()
     };
 
-    let mut text = String::new();
-    let mut has_results = false;
+    let matches = crate::federation::match_federated(&params.query, registry, match_index, &config, peer_cache).await;
 
-    // Local results first (trust: direct)
-    match crate::matcher::match_query(&params.query, registry, &config) {
-        Ok(match_result) => {
-            has_results = true;
-            text.push_str(&format!(
-                "=== Local Registry (trust: direct) ===\nCurator: {} ({})\n\nCategory: {}\nSlug: {}\nDescription: {}\n\nSources:\n",
-                registry.curator.name,
-                registry.curator.pubkey,
-                match_result.category.name,
-                match_result.slug,
-                match_result.category.description,
-            ));
-            for source in &match_result.category.sources {
+    // Local result first (trust: direct), then peers (trust: endorsed)
+    let mut text = String::new();
+    for federated in &matches {
+        match federated.trust {
+            TrustLevel::Direct => text.push_str(&format!(
+                "=== Local Registry (trust: direct) ===\nCurator: {} ({})\n",
+                federated.curator_name, federated.curator_pubkey,
+            )),
+            TrustLevel::Endorsed => {
+                let stale_tag = if federated.stale { " [STALE]" } else { "" };
+                let peer_name = federated.peer_name.as_deref().unwrap_or("(unnamed)");
                 text.push_str(&format!(
-                    "\n{}. {}\n   URL: {}\n   Type: {:?}\n   Why: {}\n",
-                    source.rank, source.name, source.url, source.source_type, source.why
+                    "\n=== Peer: {} (trust: endorsed){} ===\nCurator: {} ({})\n",
+                    peer_name, stale_tag, federated.curator_name, federated.curator_pubkey,
                 ));
             }
         }
-        Err(_) => {
-            // No local match — continue to peer results
-        }
-    }
-
-    // Peer results (trust: endorsed)
-    let peers = peer_cache.get_all_cached().await;
-    for peer in &peers {
-        if peer.status == PeerStatus::Unreachable {
-            continue; // Skip unreachable peers
-        }
-        if let Some(ref peer_registry) = peer.registry {
-            // Build a temporary Registry from the peer's data so we can reuse match_query
-            let peer_as_registry = crate::registry::types::Registry {
-                version: peer_registry.version.clone(),
-                updated: peer_registry.updated.clone(),
-                curator: crate::registry::types::Curator {
-                    name: peer_registry.curator.name.clone(),
-                    pubkey: peer_registry.curator.pubkey.clone(),
-                },
-                endorsements: vec![],
-                categories: peer_registry.categories.clone(),
-            };
-
-            match crate::matcher::match_query(&params.query, &peer_as_registry, &config) {
-                Ok(match_result) => {
-                    has_results = true;
-                    let stale_tag = if peer.stale { " [STALE]" } else { "" };
-                    let peer_name = peer.name.as_deref().unwrap_or("(unnamed)");
-                    text.push_str(&format!(
-                        "\n=== Peer: {} (trust: endorsed){} ===\nCurator: {} ({})\n\nCategory: {}\nSlug: {}\nDescription: {}\n\nSources:\n",
-                        peer_name,
-                        stale_tag,
-                        peer_as_registry.curator.name,
-                        peer.pubkey,
-                        match_result.category.name,
-                        match_result.slug,
-                        match_result.category.description,
-                    ));
-                    for source in &match_result.category.sources {
-                        text.push_str(&format!(
-                            "\n{}. {}\n   URL: {}\n   Type: {:?}\n   Why: {}\n",
-                            source.rank, source.name, source.url, source.source_type, source.why
-                        ));
-                    }
-                }
-                Err(_) => {
-                    // No match from this peer — skip
-                }
-            }
-        }
+        text.push_str(&format!(
+            "\nCategory: {}\nSlug: {}\nDescription: {}\n\nSources:\n",
+            federated.category_name, federated.slug, federated.category_description,
+        ));
+        text.push_str(&format_sources(&federated.sources, &federated.honourable_mentions));
     }
 
-    if !has_results {
+    if matches.is_empty() {
         // No results from local or peers — return error with available local categories
         let mut slugs: Vec<String> = registry.categories.keys().cloned().collect();
         slugs.sort();
@@ -750,5 +1085,61 @@ async fn tool_get_federated_sources(
         return Ok(tool_response(&text, true));
     }
 
-    Ok(tool_response(&text, false))
+    Ok(structured_response(&text, &GetFederatedSourcesOutput::new(&matches)))
+}
+
+/// Handle get_peer_status tool call
+///
+/// Reports the fetch status and cached registry of every endorsed peer,
+/// with counts by status.
+async fn tool_get_peer_status(
+    arguments: Option<Value>,
+    peer_cache: &PeerCache,
+) -> Result<Value, ToolCallError> {
+    // Parse arguments if provided (should be empty object or None)
+    if let Some(args) = arguments {
+        let _params: GetPeerStatusParams =
+            serde_json::from_value(args).map_err(|_| ToolCallError::InvalidParams)?;
+    }
+
+    let reports = peer_reports(&peer_cache.get_all_cached().await, chrono::Utc::now());
+    let output = GetPeerStatusOutput::new(&reports);
+    if reports.is_empty() {
+        let text = "Peers: 0\n\nThis node does not endorse any peers, so federated queries only search the local registry.";
+        return Ok(structured_response(text, &output));
+    }
+
+    let mut text = format!(
+        "Peers: {} ({} fresh, {} stale, {} unreachable, {} untrusted)\n",
+        reports.len(),
+        output.fresh,
+        output.stale,
+        output.unreachable,
+        output.untrusted
+    );
+    for (report, peer) in reports.iter().zip(&output.peers) {
+        let name_display = report.name.as_deref().unwrap_or("(unnamed)");
+        text.push_str(&format!(
+            "\n- {} ({})\n  URL: {}\n  Status: {}\n",
+            name_display, report.pubkey, report.url, peer.status
+        ));
+        match (&peer.last_success, report.seconds_since_success) {
+            (Some(last_success), Some(seconds)) => text.push_str(&format!(
+                "  Last success: {} ({}s ago)\n",
+                last_success, seconds
+            )),
+            _ => text.push_str("  Last success: never\n"),
+        }
+        if let (Some(version), Some(count)) = (&report.registry_version, report.category_count) {
+            text.push_str(&format!("  Registry: version {}, {} categories\n", version, count));
+        }
+        if let Some(error) = &report.last_error {
+            text.push_str(&format!(
+                "  Last error: {} ({} failure(s) in a row)\n",
+                error, report.consecutive_failures
+            ));
+        }
+    }
+
+    Ok(structured_response(&text, &output))
 }
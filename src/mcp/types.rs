@@ -6,7 +6,11 @@ pub const PARSE_ERROR: i32 = -32700;
 pub const INVALID_REQUEST: i32 = -32600;
 pub const METHOD_NOT_FOUND: i32 = -32601;
 pub const INVALID_PARAMS: i32 = -32602;
+pub const INTERNAL_ERROR: i32 = -32603;
 pub const NOT_INITIALIZED: i32 = -32002;
+pub const SESSION_NOT_FOUND: i32 = -32001;
+/// MCP resource not found. The MCP spec assigns -32002, the same code as NOT_INITIALIZED.
+pub const RESOURCE_NOT_FOUND: i32 = -32002;
 
 /// JSON-RPC 2.0 request message
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +81,29 @@ impl JsonRpcResponse {
         Self::error(id, INVALID_PARAMS, "Invalid params".to_string())
     }
 
+    /// Session not found (-32001): unknown, expired, or ended Mcp-Session-Id
+    pub fn session_not_found(id: Value) -> Self {
+        Self::error(
+            id,
+            SESSION_NOT_FOUND,
+            "Session not found. Call initialize to start a new session.".to_string(),
+        )
+    }
+
+    /// Resource not found (-32002), with the requested URI in `data`
+    pub fn resource_not_found(id: Value, uri: &str) -> Self {
+        let mut response = Self::error(id, RESOURCE_NOT_FOUND, "Resource not found".to_string());
+        if let Some(error) = response.error.as_mut() {
+            error.data = Some(serde_json::json!({ "uri": uri }));
+        }
+        response
+    }
+
+    /// Internal error (-32603)
+    pub fn internal_error(id: Value, message: String) -> Self {
+        Self::error(id, INTERNAL_ERROR, message)
+    }
+
     /// Server not initialized (-32002)
     pub fn not_initialized(id: Value) -> Self {
         Self::error(
@@ -102,12 +129,12 @@ pub struct JsonRpcError {
 #[derive(Debug, Clone, Deserialize)]
 pub struct InitializeParams {
     #[serde(rename = "protocolVersion")]
-    #[allow(dead_code)]
     pub protocol_version: String,
+    /// Defaults to empty so minimal clients can initialize
     #[allow(dead_code)]
+    #[serde(default)]
     pub capabilities: Value,
-    #[serde(rename = "clientInfo")]
-    #[allow(dead_code)]
+    #[serde(rename = "clientInfo", default)]
     pub client_info: Value,
 }
 
@@ -119,3 +146,19 @@ pub struct CallToolParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Value>,
 }
+
+/// MCP resources/read request parameters
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReadResourceParams {
+    pub uri: String,
+}
+
+/// MCP prompts/get request parameters
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPromptParams {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Value>,
+}
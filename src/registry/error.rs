@@ -20,11 +20,20 @@ pub enum RegistryError {
     #[error("Invalid category slug '{slug}': must be lowercase alphanumeric with hyphens")]
     InvalidSlug { slug: String },
 
-    /// Source count doesn't match expected count
-    #[error("Category '{category}' has {actual} sources, expected {expected}")]
+    /// Unknown schema_version
+    #[error("Unsupported registry schema_version {version}, supported: {supported}")]
+    UnsupportedSchemaVersion { version: u32, supported: String },
+
+    /// source_policy that is out of bounds or not allowed by the schema version
+    #[error("Invalid source_policy: {reason}")]
+    InvalidSourcePolicy { reason: String },
+
+    /// Source count outside what the source policy allows
+    #[error("Category '{category}' has {actual} sources, expected {}", describe_count(*.minimum, *.maximum))]
     InvalidSourceCount {
         category: String,
-        expected: usize,
+        minimum: usize,
+        maximum: usize,
         actual: usize,
     },
 
@@ -36,11 +45,93 @@ pub enum RegistryError {
         actual: usize,
     },
 
-    /// Invalid source ranks (must be sequential 1, 2, 3)
+    /// Invalid source ranks (must be sequential from 1)
     #[error("Category '{category}' has invalid ranks: {actual:?}, expected sequential ranks 1-{expected}")]
     InvalidRanks {
         category: String,
         actual: Vec<u8>,
         expected: usize,
     },
+
+    /// Invalid synonym in the matching section
+    #[error("Invalid synonym '{alias}' for '{canonical}': {reason}")]
+    InvalidSynonym {
+        canonical: String,
+        alias: String,
+        reason: String,
+    },
+
+    /// Category match threshold outside 0.0-1.0
+    #[error("Category '{category}' has match_threshold {threshold}, must be between 0.0 and 1.0")]
+    InvalidMatchThreshold { category: String, threshold: f64 },
+
+    /// Negative pattern that can never apply, or that penalizes the category's own patterns
+    #[error("Category '{category}' has invalid negative pattern '{pattern}': {reason}")]
+    InvalidNegativePattern {
+        category: String,
+        pattern: String,
+        reason: String,
+    },
+
+    /// Source language that is not an ISO 639-1 code
+    #[error("Source '{source_name}' in category '{category}' has language '{language}', expected a two-letter ISO 639-1 code")]
+    InvalidSourceLanguage {
+        category: String,
+        source_name: String,
+        language: String,
+    },
+
+    /// Tag that is not lowercase alphanumeric with hyphens
+    #[error("Category '{category}' has invalid tag '{tag}': must be lowercase alphanumeric with hyphens")]
+    InvalidTag { category: String, tag: String },
+
+    /// parent or related slug that does not name another category
+    #[error("Category '{category}' has invalid {field} '{slug}': {reason}")]
+    InvalidCategoryLink {
+        category: String,
+        field: String,
+        slug: String,
+        reason: String,
+    },
+
+    /// Categories whose parents lead back to themselves
+    #[error("Category hierarchy has a cycle: {cycle}")]
+    CategoryCycle { cycle: String },
+
+    /// Translation for a language the matcher does not support
+    #[error("Category '{category}' has a translation for unsupported language '{language}', supported: {supported}")]
+    UnsupportedLanguage {
+        category: String,
+        language: String,
+        supported: String,
+    },
+}
+
+/// Errors verifying a signed registry document
+#[derive(Debug, Error)]
+pub enum SignatureError {
+    /// Expected signer is not a valid public key
+    #[error("Invalid signer pubkey '{pubkey}'")]
+    InvalidPubkey { pubkey: String },
+
+    /// Signature is not 64 hex-encoded bytes
+    #[error("Malformed registry signature: {reason}")]
+    MalformedSignature { reason: String },
+
+    /// Signed body is not JSON
+    #[error("Signed registry is not valid JSON: {error}")]
+    JsonParse { error: String },
+
+    /// Signature does not match the content and signer
+    #[error("Registry signature does not verify against pubkey {pubkey}")]
+    Mismatch { pubkey: String },
+}
+
+/// "3" for an exact count, "3 to 5" for a range
+fn describe_count(minimum: usize, maximum: usize) -> String {
+    if minimum == maximum {
+        minimum.to_string()
+    } else {
+        format!("{} to {}", minimum, maximum)
+    }
 }
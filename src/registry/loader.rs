@@ -1,5 +1,9 @@
+use super::types::{is_language_code, MAX_SOURCES_PER_CATEGORY, SUPPORTED_SCHEMA_VERSIONS};
 use super::{Registry, RegistryError};
+use crate::matcher::normalize;
+use crate::matcher::Language;
 use regex::Regex;
+use std::collections::HashMap;
 use std::path::Path;
 use tokio::fs;
 
@@ -46,6 +50,11 @@ pub async fn load(path: impl AsRef<Path>) -> Result<Registry, RegistryError> {
 fn validate(registry: &Registry) -> Result<(), RegistryError> {
     let slug_pattern = Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap();
 
+    validate_source_policy(registry)?;
+    let policy = registry.source_policy();
+    let min_sources = policy.primary_sources;
+    let max_sources = policy.primary_sources + policy.max_honourable_mentions;
+
     for (slug, category) in &registry.categories {
         // Validate slug format (lowercase alphanumeric with hyphens)
         if !slug_pattern.is_match(slug) {
@@ -54,11 +63,13 @@ fn validate(registry: &Registry) -> Result<(), RegistryError> {
             });
         }
 
-        // Validate source count (must be exactly 3 for v1)
-        if category.sources.len() != 3 {
+        // Validate source count (exactly 3 for v1, the primary sources plus
+        // up to the allowed honourable mentions for v2)
+        if !(min_sources..=max_sources).contains(&category.sources.len()) {
             return Err(RegistryError::InvalidSourceCount {
                 category: category.name.clone(),
-                expected: 3,
+                minimum: min_sources,
+                maximum: max_sources,
                 actual: category.sources.len(),
             });
         }
@@ -72,20 +83,224 @@ fn validate(registry: &Registry) -> Result<(), RegistryError> {
             });
         }
 
-        // Validate source ranks (must be sequential 1, 2, 3)
+        // Validate threshold override (same range as MATCH_THRESHOLD)
+        if let Some(threshold) = category
+            .match_threshold
+            .filter(|threshold| !(0.0..=1.0).contains(threshold))
+        {
+            return Err(RegistryError::InvalidMatchThreshold {
+                category: category.name.clone(),
+                threshold,
+            });
+        }
+
+        // Validate translations (supported languages other than English)
+        for language in category.localized.keys() {
+            if !matches!(Language::from_code(language), Some(l) if l != Language::English) {
+                return Err(RegistryError::UnsupportedLanguage {
+                    category: category.name.clone(),
+                    language: language.clone(),
+                    supported: Language::supported_codes(),
+                });
+            }
+        }
+
+        // Validate source languages (ISO 639-1, e.g. "en")
+        if let Some(source) = category
+            .sources
+            .iter()
+            .find(|source| source.language.as_deref().is_some_and(|l| !is_language_code(l)))
+        {
+            return Err(RegistryError::InvalidSourceLanguage {
+                category: category.name.clone(),
+                source_name: source.name.clone(),
+                language: source.language.clone().unwrap_or_default(),
+            });
+        }
+
+        // Validate source ranks (must be sequential from 1, e.g. 1, 2, 3)
         let mut ranks: Vec<u8> = category.sources.iter().map(|s| s.rank).collect();
         ranks.sort_unstable();
-        let expected_ranks: Vec<u8> = (1..=3).collect();
+        // Source count is at most MAX_SOURCES_PER_CATEGORY, so fits in a u8
+        let expected_ranks: Vec<u8> = (1..=category.sources.len() as u8).collect();
 
         if ranks != expected_ranks {
             return Err(RegistryError::InvalidRanks {
                 category: category.name.clone(),
                 actual: ranks,
-                expected: 3,
+                expected: category.sources.len(),
             });
         }
     }
 
+    validate_category_links(registry, &slug_pattern)?;
+    validate_synonyms(registry)?;
+    validate_negative_patterns(registry)
+}
+
+/// Validate tags, parent and related links: tags use the slug format, links
+/// name another category in this registry, and following parents never
+/// leads back to where it started
+fn validate_category_links(registry: &Registry, slug_pattern: &Regex) -> Result<(), RegistryError> {
+    let mut slugs: Vec<&String> = registry.categories.keys().collect();
+    slugs.sort();
+
+    for slug in &slugs {
+        let category = &registry.categories[*slug];
+
+        if let Some(tag) = category.tags.iter().find(|tag| !slug_pattern.is_match(tag)) {
+            return Err(RegistryError::InvalidTag {
+                category: category.name.clone(),
+                tag: tag.clone(),
+            });
+        }
+
+        let links = category
+            .parent
+            .iter()
+            .map(|target| ("parent", target))
+            .chain(category.related.iter().map(|target| ("related", target)));
+        for (field, target) in links {
+            let reason = if target == *slug {
+                "a category cannot link to itself"
+            } else if !registry.categories.contains_key(target) {
+                "no category has this slug"
+            } else {
+                continue;
+            };
+            return Err(RegistryError::InvalidCategoryLink {
+                category: category.name.clone(),
+                field: field.to_string(),
+                slug: target.clone(),
+                reason: reason.to_string(),
+            });
+        }
+    }
+
+    // Every parent exists, so walking up either reaches a root or repeats
+    for slug in slugs {
+        let mut path = vec![slug.as_str()];
+        let mut current = slug;
+        while let Some(parent) = &registry.categories[current].parent {
+            if let Some(start) = path.iter().position(|seen| *seen == parent.as_str()) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(parent);
+                return Err(RegistryError::CategoryCycle {
+                    cycle: cycle.join(" -> "),
+                });
+            }
+            path.push(parent);
+            current = parent;
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the schema version and the source policy: v1 registries take
+/// the default policy (exactly three sources), v2 registries may declare
+/// their own within `MAX_SOURCES_PER_CATEGORY`
+fn validate_source_policy(registry: &Registry) -> Result<(), RegistryError> {
+    if !SUPPORTED_SCHEMA_VERSIONS.contains(&registry.schema_version) {
+        return Err(RegistryError::UnsupportedSchemaVersion {
+            version: registry.schema_version,
+            supported: SUPPORTED_SCHEMA_VERSIONS
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        });
+    }
+
+    let Some(policy) = registry.source_policy else {
+        return Ok(());
+    };
+    let invalid = |reason: String| RegistryError::InvalidSourcePolicy { reason };
+
+    if registry.schema_version < 2 {
+        return Err(invalid("source_policy requires schema_version 2".to_string()));
+    }
+    if policy.primary_sources == 0 {
+        return Err(invalid("primary_sources must be at least 1".to_string()));
+    }
+    if policy.primary_sources + policy.max_honourable_mentions > MAX_SOURCES_PER_CATEGORY {
+        return Err(invalid(format!(
+            "primary_sources plus max_honourable_mentions must be at most {}",
+            MAX_SOURCES_PER_CATEGORY
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate the synonym map: every alias must be a single word, every
+/// canonical term must survive normalization, and no alias may map to two
+/// canonical terms (after stemming, if enabled)
+fn validate_synonyms(registry: &Registry) -> Result<(), RegistryError> {
+    let stemming = registry.matching.stemming;
+    let mut seen: HashMap<String, &str> = HashMap::new();
+
+    for (canonical, aliases) in &registry.matching.synonyms {
+        for alias in aliases {
+            let invalid = |reason: String| RegistryError::InvalidSynonym {
+                canonical: canonical.clone(),
+                alias: alias.clone(),
+                reason,
+            };
+
+            if normalize::normalize_text(canonical).is_err() {
+                return Err(invalid(
+                    "canonical term is empty or only stop words".to_string(),
+                ));
+            }
+
+            let key = normalize::synonym_key(alias, stemming).ok_or_else(|| {
+                invalid("alias must be a single word that is not a stop word".to_string())
+            })?;
+
+            if let Some(other) = seen
+                .insert(key, canonical.as_str())
+                .filter(|other| *other != canonical.as_str())
+            {
+                return Err(invalid(format!("alias is already a synonym for '{}'", other)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate negative patterns: each must keep at least one word after
+/// normalization, and none may penalize a query that is one of the
+/// category's own query patterns
+fn validate_negative_patterns(registry: &Registry) -> Result<(), RegistryError> {
+    let normalizer = normalize::Normalizer::new(&registry.matching);
+
+    for category in registry.categories.values() {
+        for pattern in &category.negative_patterns {
+            let invalid = |reason: String| RegistryError::InvalidNegativePattern {
+                category: category.name.clone(),
+                pattern: pattern.clone(),
+                reason,
+            };
+
+            let negative = normalizer
+                .normalize(pattern, Language::English)
+                .map_err(|_| invalid("pattern is empty or only stop words".to_string()))?;
+
+            if let Some(query_pattern) = category.query_patterns.iter().find(|query_pattern| {
+                normalizer
+                    .normalize(query_pattern, Language::English)
+                    .is_ok_and(|normalized| normalize::contains_words(&normalized, &negative))
+            }) {
+                return Err(invalid(format!(
+                    "it would penalize the query pattern '{}'",
+                    query_pattern
+                )));
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -97,3 +312,403 @@ fn count_sources(registry: &Registry) -> usize {
         .map(|cat| cat.sources.len())
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::types::{SourceCost, SourceFilter, SourceFormat, SourcePolicy};
+    use chrono::NaiveDate;
+
+    fn load_test_registry() -> Registry {
+        let json = include_str!("../../registry.json");
+        serde_json::from_str(json).expect("Failed to parse registry.json")
+    }
+
+    fn with_synonyms(stemming: bool, synonyms: &[(&str, &[&str])]) -> Registry {
+        let mut registry = load_test_registry();
+        registry.matching.stemming = stemming;
+        registry.matching.synonyms = synonyms
+            .iter()
+            .map(|(canonical, aliases)| {
+                (
+                    canonical.to_string(),
+                    aliases.iter().map(|a| a.to_string()).collect(),
+                )
+            })
+            .collect();
+        registry
+    }
+
+    #[test]
+    fn test_seed_registry_is_valid() {
+        assert!(validate(&load_test_registry()).is_ok());
+    }
+
+    #[test]
+    fn test_multi_word_alias_rejected() {
+        let registry = with_synonyms(false, &[("email", &["electronic mail"])]);
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidSynonym { alias, .. }) if alias == "electronic mail"
+        ));
+    }
+
+    #[test]
+    fn test_stop_word_canonical_rejected() {
+        let registry = with_synonyms(false, &[("the", &["teh"])]);
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidSynonym { canonical, .. }) if canonical == "the"
+        ));
+    }
+
+    #[test]
+    fn test_alias_with_two_canonicals_rejected() {
+        let registry = with_synonyms(false, &[("email", &["mail"]), ("post", &["mail"])]);
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidSynonym { .. })
+        ));
+    }
+
+    #[test]
+    fn test_aliases_clashing_after_stemming_rejected() {
+        // "nodes" and "node" share a stem, so they clash only with stemming on
+        let synonyms: &[(&str, &[&str])] = &[("bitcoin", &["nodes"]), ("server", &["node"])];
+        assert!(validate(&with_synonyms(false, synonyms)).is_ok());
+        assert!(validate(&with_synonyms(true, synonyms)).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_translation_language_rejected() {
+        let mut registry = load_test_registry();
+        let category = registry.categories.get_mut("rust-learning").unwrap();
+        let translation = category.localized.values().next().unwrap().clone();
+        category.localized.insert("xx".to_string(), translation);
+
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::UnsupportedLanguage { language, .. }) if language == "xx"
+        ));
+    }
+
+    fn with_negative_patterns(slug: &str, patterns: &[&str]) -> Registry {
+        let mut registry = load_test_registry();
+        registry.categories.get_mut(slug).unwrap().negative_patterns =
+            patterns.iter().map(|p| p.to_string()).collect();
+        registry
+    }
+
+    #[test]
+    fn test_category_threshold_out_of_range_rejected() {
+        for threshold in [-0.1, 1.5] {
+            let mut registry = load_test_registry();
+            registry.categories.get_mut("rust-learning").unwrap().match_threshold = Some(threshold);
+            assert!(matches!(
+                validate(&registry),
+                Err(RegistryError::InvalidMatchThreshold { threshold: t, .. }) if t == threshold
+            ));
+        }
+    }
+
+    #[test]
+    fn test_category_threshold_bounds_accepted() {
+        for threshold in [0.0, 0.55, 1.0] {
+            let mut registry = load_test_registry();
+            registry.categories.get_mut("rust-learning").unwrap().match_threshold = Some(threshold);
+            assert!(validate(&registry).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_stop_word_negative_pattern_rejected() {
+        let registry = with_negative_patterns("rust-learning", &["how to"]);
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidNegativePattern { pattern, .. }) if pattern == "how to"
+        ));
+    }
+
+    #[test]
+    fn test_negative_pattern_covering_query_pattern_rejected() {
+        // Every word of "rust tutorial" is in the pattern "rust tutorial for beginners"
+        let registry = with_negative_patterns("rust-learning", &["rust tutorial"]);
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidNegativePattern { reason, .. })
+                if reason.contains("rust tutorial for beginners")
+        ));
+    }
+
+    fn load_v2_fixture() -> Registry {
+        let json = include_str!("../../tests/fixtures/valid_registry_v2.json");
+        serde_json::from_str(json).expect("Failed to parse valid_registry_v2.json")
+    }
+
+    #[test]
+    fn test_v1_registry_defaults() {
+        let registry = load_test_registry();
+        assert_eq!(registry.schema_version, 1);
+        assert!(registry.source_policy.is_none());
+        assert_eq!(registry.source_policy(), SourcePolicy::default());
+
+        // v1 registries serialize without the v2 fields
+        let json = serde_json::to_value(&registry).unwrap();
+        assert!(json.get("schema_version").is_none());
+        assert!(json.get("source_policy").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_v2_registry_with_honourable_mention_loads() {
+        let registry = load("tests/fixtures/valid_registry_v2.json").await.unwrap();
+        assert_eq!(registry.schema_version, 2);
+        assert_eq!(registry.categories["rust-learning"].sources.len(), 4);
+
+        let json = serde_json::to_value(&registry).unwrap();
+        assert_eq!(json["schema_version"], 2);
+        assert_eq!(json["source_policy"]["max_honourable_mentions"], 2);
+    }
+
+    #[test]
+    fn test_v1_registry_rejects_fourth_source() {
+        let mut v1 = load_v2_fixture();
+        v1.schema_version = 1;
+        v1.source_policy = None;
+        let err = validate(&v1).unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::InvalidSourceCount { minimum: 3, maximum: 3, actual: 4, .. }
+        ));
+        assert_eq!(err.to_string(), "Category 'Rust Learning' has 4 sources, expected 3");
+    }
+
+    #[test]
+    fn test_v2_source_count_bounds() {
+        let mut registry = load_v2_fixture();
+        let sources = &mut registry.categories.get_mut("rust-learning").unwrap().sources;
+        sources.truncate(2);
+        let err = validate(&registry).unwrap_err();
+        assert_eq!(err.to_string(), "Category 'Rust Learning' has 2 sources, expected 3 to 5");
+
+        let mut registry = load_v2_fixture();
+        registry.source_policy = Some(SourcePolicy {
+            primary_sources: 3,
+            max_honourable_mentions: 0,
+        });
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidSourceCount { actual: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_v2_ranks_must_be_sequential() {
+        let mut registry = load_v2_fixture();
+        registry.categories.get_mut("rust-learning").unwrap().sources[3].rank = 5;
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidRanks { expected: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_source_policy_requires_v2() {
+        let mut registry = load_test_registry();
+        registry.source_policy = Some(SourcePolicy::default());
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidSourcePolicy { reason }) if reason.contains("schema_version 2")
+        ));
+    }
+
+    #[test]
+    fn test_source_policy_bounds() {
+        for (primary_sources, max_honourable_mentions) in [(0, 3), (3, 8)] {
+            let mut registry = load_v2_fixture();
+            registry.source_policy = Some(SourcePolicy {
+                primary_sources,
+                max_honourable_mentions,
+            });
+            assert!(matches!(
+                validate(&registry),
+                Err(RegistryError::InvalidSourcePolicy { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_schema_version_rejected() {
+        let mut registry = load_test_registry();
+        registry.schema_version = 3;
+        assert_eq!(
+            validate(&registry).unwrap_err().to_string(),
+            "Unsupported registry schema_version 3, supported: 1, 2"
+        );
+    }
+
+    #[test]
+    fn test_source_policy_split() {
+        let registry = load_v2_fixture();
+        let mut sources = registry.categories["rust-learning"].sources.clone();
+        sources.reverse();
+
+        let (primary, mentions) = registry.source_policy().split(&sources);
+        let ranks: Vec<u8> = primary.iter().map(|s| s.rank).collect();
+        assert_eq!(ranks, vec![1, 2, 3]);
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(mentions[0].name, "Rust by Example");
+    }
+
+    #[test]
+    fn test_source_metadata_loads() {
+        let registry = load_v2_fixture();
+        let sources = &registry.categories["rust-learning"].sources;
+
+        let book = &sources[0];
+        assert_eq!(book.last_verified, NaiveDate::from_ymd_opt(2026, 9, 14));
+        assert_eq!(book.cost, Some(SourceCost::Free));
+        assert_eq!(book.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(book.language.as_deref(), Some("en"));
+        assert_eq!(book.format, Some(SourceFormat::Html));
+        assert!(!book.archived);
+
+        let playground = &sources[2];
+        assert!(playground.cost.is_none() && playground.language.is_none());
+        assert!(sources[3].archived);
+    }
+
+    #[test]
+    fn test_invalid_source_language_rejected() {
+        let mut registry = load_test_registry();
+        let category = registry.categories.get_mut("rust-learning").unwrap();
+        category.sources[0].language = Some("English".to_string());
+
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidSourceLanguage { language, .. }) if language == "English"
+        ));
+    }
+
+    #[test]
+    fn test_unknown_source_cost_rejected() {
+        let json = include_str!("../../registry.json").replacen(
+            "\"type\": \"documentation\",",
+            "\"type\": \"documentation\", \"cost\": \"donation\",",
+            1,
+        );
+        assert!(serde_json::from_str::<Registry>(&json).is_err());
+    }
+
+    #[test]
+    fn test_source_filter() {
+        let registry = load_v2_fixture();
+        let sources = &registry.categories["rust-learning"].sources;
+        let kept = |filter: &SourceFilter| -> Vec<u8> {
+            sources
+                .iter()
+                .filter(|s| filter.matches(s))
+                .map(|s| s.rank)
+                .collect()
+        };
+
+        assert_eq!(kept(&SourceFilter::default()), vec![1, 2, 3, 4]);
+
+        // Sources without recorded cost or language do not qualify
+        let free = SourceFilter {
+            free_only: true,
+            language: None,
+        };
+        assert_eq!(kept(&free), vec![1, 2, 4]);
+        let english = SourceFilter {
+            free_only: false,
+            language: Some("en".to_string()),
+        };
+        assert_eq!(kept(&english), vec![1, 2, 4]);
+        let german = SourceFilter {
+            free_only: true,
+            language: Some("de".to_string()),
+        };
+        assert!(kept(&german).is_empty());
+    }
+
+    #[test]
+    fn test_category_links_must_name_other_categories() {
+        let mut registry = load_test_registry();
+        let category = registry.categories.get_mut("rust-learning").unwrap();
+        category.parent = Some("go-learning".to_string());
+        assert_eq!(
+            validate(&registry).unwrap_err().to_string(),
+            "Category 'Rust Learning' has invalid parent 'go-learning': no category has this slug"
+        );
+
+        let mut registry = load_test_registry();
+        registry
+            .categories
+            .get_mut("rust-learning")
+            .unwrap()
+            .related
+            .push("rust-learning".to_string());
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidCategoryLink { field, .. }) if field == "related"
+        ));
+    }
+
+    #[test]
+    fn test_invalid_tag_rejected() {
+        let mut registry = load_test_registry();
+        let category = registry.categories.get_mut("rust-learning").unwrap();
+        category.tags.push("Systems Programming".to_string());
+        assert!(matches!(
+            validate(&registry),
+            Err(RegistryError::InvalidTag { tag, .. }) if tag == "Systems Programming"
+        ));
+    }
+
+    #[test]
+    fn test_category_hierarchy() {
+        let mut registry = load_test_registry();
+        let mut set_parent = |slug: &str, parent: &str| {
+            registry.categories.get_mut(slug).unwrap().parent = Some(parent.to_string());
+        };
+        set_parent("password-management", "threat-modeling");
+        set_parent("linux-hardening", "threat-modeling");
+        set_parent("self-hosted-email", "linux-hardening");
+        assert!(validate(&registry).is_ok());
+
+        assert_eq!(
+            registry.ancestors("self-hosted-email"),
+            vec!["linux-hardening", "threat-modeling"]
+        );
+        let children: Vec<&String> = registry
+            .subcategories(Some("threat-modeling"))
+            .into_iter()
+            .map(|(slug, _)| slug)
+            .collect();
+        assert_eq!(children, vec!["linux-hardening", "password-management"]);
+        assert_eq!(registry.subcategories(None).len(), 7);
+
+        // Closing the loop is rejected, reported from the first slug in order
+        registry.categories.get_mut("threat-modeling").unwrap().parent =
+            Some("self-hosted-email".to_string());
+        assert_eq!(
+            validate(&registry).unwrap_err().to_string(),
+            "Category hierarchy has a cycle: linux-hardening -> threat-modeling -> self-hosted-email -> linux-hardening"
+        );
+        assert_eq!(registry.ancestors("threat-modeling").len(), 2, "ancestors stop at a cycle");
+    }
+
+    #[test]
+    fn test_v2_without_policy_keeps_three_sources() {
+        let mut registry = load_test_registry();
+        registry.schema_version = 2;
+        assert!(validate(&registry).is_ok());
+    }
+
+    #[test]
+    fn test_negative_pattern_checked_after_stemming() {
+        // "beginner" stems like "beginners" in "rust tutorial for beginners"
+        let registry = with_negative_patterns("rust-learning", &["tutorial beginner"]);
+        assert!(validate(&registry).is_err());
+    }
+}
@@ -1,8 +1,12 @@
 pub mod error;
 pub mod loader;
+pub mod signature;
 pub mod types;
 
 // Re-export types for convenient access
-pub use error::RegistryError;
+pub use error::{RegistryError, SignatureError};
 pub use loader::load;
+pub use signature::{
+    registry_etag, sign_registry, verify_registry, PUBKEY_HEADER, SIGNATURE_HEADER,
+};
 pub use types::Registry;
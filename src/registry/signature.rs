@@ -0,0 +1,223 @@
+use super::error::SignatureError;
+use super::types::Registry;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use pkarr::{Keypair, PublicKey};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Response header carrying the hex-encoded Ed25519 signature of the registry
+pub const SIGNATURE_HEADER: &str = "x-registry-signature";
+
+/// Response header carrying the z32 pubkey of the node that signed the registry
+pub const PUBKEY_HEADER: &str = "x-registry-pubkey";
+
+/// Canonical form of a JSON document: object keys sorted by byte order and
+/// no whitespace between tokens. Reformatting a document (pretty printing,
+/// reordering keys) does not change its canonical form, so a signature over
+/// it covers the content rather than the bytes on the wire.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key).expect("strings always serialize"));
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Sign the canonical form of a registry with the node key.
+/// Returns the detached Ed25519 signature, hex-encoded.
+pub fn sign_registry(registry: &Registry, keypair: &Keypair) -> String {
+    let value = serde_json::to_value(registry).expect("Registry always serializes");
+    let signing_key = SigningKey::from_bytes(&keypair.secret_key());
+    let signature = signing_key.sign(canonical_json(&value).as_bytes());
+    hex::encode(signature.to_bytes())
+}
+
+/// Strong HTTP entity tag for a registry: the quoted SHA-256 of its
+/// canonical form, so it only changes when the content does
+pub fn registry_etag(registry: &Registry) -> String {
+    let value = serde_json::to_value(registry).expect("Registry always serializes");
+    format!("\"{}\"", hex::encode(Sha256::digest(canonical_json(&value).as_bytes())))
+}
+
+/// Verify a registry document against its detached signature and the pubkey
+/// (z32) expected to have signed it. `body` may be formatted any way, since
+/// the signature covers its canonical form.
+/// Returns the parsed document, ready to deserialize.
+pub fn verify_registry(
+    body: &[u8],
+    signature: &str,
+    pubkey: &str,
+) -> Result<Value, SignatureError> {
+    let public_key = PublicKey::try_from(pubkey).map_err(|_| SignatureError::InvalidPubkey {
+        pubkey: pubkey.to_string(),
+    })?;
+    let verifying_key = VerifyingKey::from_bytes(public_key.as_bytes()).map_err(|_| {
+        SignatureError::InvalidPubkey {
+            pubkey: pubkey.to_string(),
+        }
+    })?;
+
+    let signature_bytes =
+        hex::decode(signature.trim()).map_err(|e| SignatureError::MalformedSignature {
+            reason: e.to_string(),
+        })?;
+    let signature_array: [u8; 64] = signature_bytes.try_into().map_err(|bytes: Vec<u8>| {
+        SignatureError::MalformedSignature {
+            reason: format!("expected 64 bytes, got {}", bytes.len()),
+        }
+    })?;
+    let signature = Signature::from_bytes(&signature_array);
+
+    let value: Value = serde_json::from_slice(body).map_err(|e| SignatureError::JsonParse {
+        error: e.to_string(),
+    })?;
+    verifying_key
+        .verify(canonical_json(&value).as_bytes(), &signature)
+        .map_err(|_| SignatureError::Mismatch {
+            pubkey: pubkey.to_string(),
+        })?;
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY_JSON: &str = include_str!("../../registry.json");
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::from_secret_key(&[seed; 32])
+    }
+
+    fn registry() -> Registry {
+        serde_json::from_str(REGISTRY_JSON).unwrap()
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys_and_drops_whitespace() {
+        let value: Value = serde_json::from_str(
+            r#"{ "b": [1, {"z": null, "a": "x\"y"}], "a": { "d": 0.5, "c": true } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"c":true,"d":0.5},"b":[1,{"a":"x\"y","z":null}]}"#
+        );
+    }
+
+    #[test]
+    fn test_registry_etag_tracks_content() {
+        let mut registry = registry();
+        let etag = registry_etag(&registry);
+        assert_eq!(etag.len(), 66, "quoted hex SHA-256");
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+        // Category order in the map does not matter, content does
+        let reparsed: Registry =
+            serde_json::from_str(&serde_json::to_string(&registry).unwrap()).unwrap();
+        assert_eq!(registry_etag(&reparsed), etag);
+        registry.version = "9.9.9".to_string();
+        assert_ne!(registry_etag(&registry), etag);
+    }
+
+    #[test]
+    fn test_signed_registry_verifies_after_reformatting() {
+        let keypair = keypair(7);
+        let registry = registry();
+        let signature = sign_registry(&registry, &keypair);
+        let pubkey = keypair.public_key().to_z32();
+
+        let pretty = serde_json::to_string_pretty(&registry).unwrap();
+        let value = verify_registry(pretty.as_bytes(), &signature, &pubkey).unwrap();
+        assert_eq!(value["version"], registry.version.as_str());
+
+        // Compact output with the same content carries the same signature
+        let compact = serde_json::to_string(&registry).unwrap();
+        assert!(verify_registry(compact.as_bytes(), &signature, &pubkey).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_registry_rejected() {
+        let keypair = keypair(7);
+        let registry = registry();
+        let signature = sign_registry(&registry, &keypair);
+        let pubkey = keypair.public_key().to_z32();
+
+        let mut value = serde_json::to_value(&registry).unwrap();
+        value["categories"]["rust-learning"]["sources"][0]["url"] =
+            serde_json::json!("https://attacker.example/");
+        let body = serde_json::to_vec(&value).unwrap();
+        assert!(matches!(
+            verify_registry(&body, &signature, &pubkey),
+            Err(SignatureError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_wrong_signer_rejected() {
+        let registry = registry();
+        let signature = sign_registry(&registry, &keypair(7));
+        let body = serde_json::to_vec(&registry).unwrap();
+
+        let other = keypair(8).public_key().to_z32();
+        assert!(matches!(
+            verify_registry(&body, &signature, &other),
+            Err(SignatureError::Mismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_inputs_rejected() {
+        let keypair = keypair(7);
+        let registry = registry();
+        let signature = sign_registry(&registry, &keypair);
+        let pubkey = keypair.public_key().to_z32();
+        let body = serde_json::to_vec(&registry).unwrap();
+
+        assert!(matches!(
+            verify_registry(&body, &signature, "not-a-pubkey"),
+            Err(SignatureError::InvalidPubkey { .. })
+        ));
+        assert!(matches!(
+            verify_registry(&body, "zz", &pubkey),
+            Err(SignatureError::MalformedSignature { .. })
+        ));
+        assert!(matches!(
+            verify_registry(&body, &signature[..64], &pubkey),
+            Err(SignatureError::MalformedSignature { .. })
+        ));
+        assert!(matches!(
+            verify_registry(b"<html>", &signature, &pubkey),
+            Err(SignatureError::JsonParse { .. })
+        ));
+    }
+}
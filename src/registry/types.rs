@@ -1,10 +1,29 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// Registry schema versions this node can load
+pub const SUPPORTED_SCHEMA_VERSIONS: &[u32] = &[1, 2];
+
+/// Most sources a category may list, primary and honourable mentions together
+pub const MAX_SOURCES_PER_CATEGORY: usize = 10;
+
+fn default_schema_version() -> u32 {
+    1
+}
+
+fn is_schema_v1(version: &u32) -> bool {
+    *version == 1
+}
 
 /// Top-level registry structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Registry {
+    /// Schema version: 1 (exactly three sources per category, the default)
+    /// or 2 (source count set by `source_policy`)
+    #[serde(default = "default_schema_version", skip_serializing_if = "is_schema_v1")]
+    pub schema_version: u32,
     /// Semver version string (e.g., "0.1.0")
     pub version: String,
     /// ISO 8601 date string (e.g., "2026-02-01")
@@ -15,6 +34,109 @@ pub struct Registry {
     pub endorsements: Vec<Endorsement>,
     /// Categories keyed by slug (e.g., "rust-learning")
     pub categories: HashMap<String, Category>,
+    /// Optional query normalization rules (stemming, synonyms)
+    #[serde(default, skip_serializing_if = "MatchingRules::is_empty")]
+    pub matching: MatchingRules,
+    /// How many sources each category lists (schema version 2 only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_policy: Option<SourcePolicy>,
+}
+
+impl Registry {
+    /// The source policy in effect: the declared one, or three primary
+    /// sources and no honourable mentions
+    pub fn source_policy(&self) -> SourcePolicy {
+        self.source_policy.unwrap_or_default()
+    }
+
+    /// Categories whose parent is `parent`, or top-level categories (no
+    /// parent) for None, sorted by slug
+    pub fn subcategories(&self, parent: Option<&str>) -> Vec<(&String, &Category)> {
+        let mut children: Vec<(&String, &Category)> = self
+            .categories
+            .iter()
+            .filter(|(_, category)| category.parent.as_deref() == parent)
+            .collect();
+        children.sort_by_key(|(slug, _)| *slug);
+        children
+    }
+
+    /// Slugs of a category's parent, grandparent and so on, nearest first.
+    /// Stops at a missing parent or one already visited, which only
+    /// unvalidated peer registries can have
+    pub fn ancestors(&self, slug: &str) -> Vec<&String> {
+        let mut ancestors: Vec<&String> = Vec::new();
+        let mut current = self.categories.get(slug);
+        while let Some(parent) = current.and_then(|category| category.parent.as_ref()) {
+            if parent == slug || ancestors.contains(&parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = self.categories.get(parent);
+        }
+        ancestors
+    }
+}
+
+fn default_primary_sources() -> usize {
+    3
+}
+
+/// Number of sources per category.
+///
+/// Ranks `1..=primary_sources` are the primary sources; every category has
+/// exactly that many. Ranks after them are honourable mentions (alternates
+/// worth knowing about), of which a category may list up to
+/// `max_honourable_mentions`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourcePolicy {
+    /// Primary sources every category lists
+    #[serde(default = "default_primary_sources")]
+    pub primary_sources: usize,
+    /// Most honourable mentions a category may list after its primary sources
+    #[serde(default)]
+    pub max_honourable_mentions: usize,
+}
+
+impl Default for SourcePolicy {
+    fn default() -> Self {
+        Self {
+            primary_sources: default_primary_sources(),
+            max_honourable_mentions: 0,
+        }
+    }
+}
+
+impl SourcePolicy {
+    /// Split sources into primary sources and honourable mentions, each in
+    /// rank order
+    pub fn split<'a>(&self, sources: &'a [Source]) -> (Vec<&'a Source>, Vec<&'a Source>) {
+        let mut sorted: Vec<&Source> = sources.iter().collect();
+        sorted.sort_by_key(|source| source.rank);
+        sorted
+            .into_iter()
+            .partition(|source| usize::from(source.rank) <= self.primary_sources)
+    }
+}
+
+/// Curator-maintained rules applied when normalizing queries and categories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MatchingRules {
+    /// Reduce words to their Porter stem ("running" and "run" match)
+    #[serde(default)]
+    pub stemming: bool,
+    /// Canonical term -> aliases replaced by it (e.g., "email": ["mail"])
+    #[serde(default)]
+    pub synonyms: BTreeMap<String, Vec<String>>,
+}
+
+impl MatchingRules {
+    /// True if these rules leave normalization unchanged
+    pub fn is_empty(&self) -> bool {
+        !self.stemming && self.synonyms.is_empty()
+    }
 }
 
 /// Curator identity information
@@ -51,15 +173,47 @@ pub struct Category {
     pub description: String,
     /// Query patterns for matching user requests
     pub query_patterns: Vec<String>,
-    /// List of curated sources (exactly 3 for v1)
+    /// Minimum match score for this category, overriding the global threshold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_threshold: Option<f64>,
+    /// Queries this category must not answer; a query containing every word
+    /// of one of them is penalized
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub negative_patterns: Vec<String>,
+    /// Free-form topic labels for browsing (e.g., "privacy", "beginner")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Slug of the broader category this one belongs under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Slugs of categories worth suggesting alongside this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<String>,
+    /// List of curated sources (exactly 3 for v1; see `SourcePolicy` for v2)
     pub sources: Vec<Source>,
+    /// Translations keyed by ISO 639-1 language code (e.g., "es", "de")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub localized: BTreeMap<String, LocalizedCategory>,
+}
+
+/// Category name, description and query patterns in another language
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocalizedCategory {
+    /// Translated display name
+    pub name: String,
+    /// Translated description
+    pub description: String,
+    /// Query patterns in this language, matched in addition to the English ones
+    pub query_patterns: Vec<String>,
 }
 
 /// Individual source within a category
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Source {
-    /// Rank order (1-3 for three sources)
+    /// Rank order, starting at 1. Ranks after the registry's primary
+    /// sources are honourable mentions
     pub rank: u8,
     /// Source display name
     pub name: String,
@@ -70,6 +224,29 @@ pub struct Source {
     pub source_type: SourceType,
     /// Curator's explanation of why this source is valuable
     pub why: String,
+    /// Day the curator last checked the source (ISO 8601, e.g. "2026-09-14")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_verified: Option<NaiveDate>,
+    /// What it costs to use the source
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<SourceCost>,
+    /// License the content is published under (SPDX identifier where one
+    /// exists, e.g. "CC-BY-4.0", "MIT")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// ISO 639-1 code of the language the source is written in (e.g. "en")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Medium the content is delivered in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<SourceFormat>,
+    /// The source is kept for reference but no longer maintained
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// Result of the latest `check-links` run, filled in from the link
+    /// status file when the server loads the registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_checked: Option<LinkCheck>,
 }
 
 /// Source type enumeration
@@ -87,3 +264,81 @@ pub enum SourceType {
     Course,
     Api,
 }
+
+/// Cost of using a source
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceCost {
+    /// Free to use in full
+    Free,
+    /// Usable for free, with paid tiers or content
+    Freemium,
+    /// Requires payment or a subscription
+    Paid,
+}
+
+/// Outcome of checking a source URL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LinkCheck {
+    /// Day the check ran
+    pub date: NaiveDate,
+    /// What the check found
+    pub status: LinkCheckStatus,
+    /// HTTP status of the final response, absent if none arrived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+}
+
+/// Summary status of a checked URL
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkCheckStatus {
+    /// Answered with a success status
+    Ok,
+    /// Answered with a success status after one or more redirects
+    Redirected,
+    /// Answered with a 4xx or 5xx status
+    Broken,
+    /// No usable response: timeout, TLS or connection failure, or a
+    /// redirect loop
+    Unreachable,
+}
+
+/// Medium a source is delivered in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Html,
+    Pdf,
+    Epub,
+    Video,
+    Audio,
+    Interactive,
+}
+
+/// True for a two-letter lowercase ISO 639-1 code (e.g. "en")
+pub fn is_language_code(code: &str) -> bool {
+    code.len() == 2 && code.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+/// Restricts which sources are returned for a category
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceFilter {
+    /// Only sources whose cost is recorded as free
+    pub free_only: bool,
+    /// Only sources recorded as written in this ISO 639-1 language
+    pub language: Option<String>,
+}
+
+impl SourceFilter {
+    /// True if `source` passes the filter. Sources without the relevant
+    /// metadata are excluded, since they cannot be shown to qualify
+    pub fn matches(&self, source: &Source) -> bool {
+        (!self.free_only || source.cost == Some(SourceCost::Free))
+            && self
+                .language
+                .as_deref()
+                .is_none_or(|language| source.language.as_deref() == Some(language))
+    }
+}
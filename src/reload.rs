@@ -0,0 +1,473 @@
+use crate::audit::AuditEntry;
+use crate::config::Config;
+use crate::contributions::Proposal;
+use crate::federation::PeerCache;
+use crate::identity::Identity;
+use crate::matcher::MatchIndex;
+use crate::registry::Registry;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// All curated data served by a node, loaded and validated together.
+#[derive(Debug)]
+pub struct DataSnapshot {
+    pub registry: Registry,
+    pub audit_log: Vec<AuditEntry>,
+    pub identities: HashMap<String, Identity>,
+    pub proposals: HashMap<Uuid, Proposal>,
+    /// Matching index for `registry`, rebuilt whenever the registry changes
+    pub match_index: MatchIndex,
+}
+
+impl DataSnapshot {
+    /// Bundle loaded data and build the registry's matching index
+    pub fn new(
+        registry: Registry,
+        audit_log: Vec<AuditEntry>,
+        identities: HashMap<String, Identity>,
+        proposals: HashMap<Uuid, Proposal>,
+    ) -> Self {
+        let match_index = MatchIndex::new(&registry.categories, &registry.matching);
+        Self {
+            registry,
+            audit_log,
+            identities,
+            proposals,
+            match_index,
+        }
+    }
+}
+
+/// Atomically swappable handle to the current DataSnapshot.
+/// Readers take a cheap Arc clone, so in-flight requests keep the snapshot
+/// they started with while a reload swaps in a new one.
+pub struct DataStore {
+    current: RwLock<Arc<DataSnapshot>>,
+}
+
+impl DataStore {
+    pub fn new(snapshot: DataSnapshot) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(snapshot)),
+        }
+    }
+
+    /// Returns the current snapshot
+    pub fn load(&self) -> Arc<DataSnapshot> {
+        let guard = self.current.read().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(&*guard)
+    }
+
+    /// Replace the current snapshot
+    pub fn store(&self, snapshot: DataSnapshot) {
+        let mut guard = self.current.write().unwrap_or_else(|e| e.into_inner());
+        *guard = Arc::new(snapshot);
+    }
+}
+
+/// Paths of the data files that make up a DataSnapshot
+#[derive(Debug, Clone)]
+pub struct DataPaths {
+    pub registry: PathBuf,
+    pub audit_log: PathBuf,
+    pub identities: PathBuf,
+    pub contributions: PathBuf,
+    /// Link status file applied to the registry, if configured
+    pub link_status: Option<PathBuf>,
+}
+
+impl DataPaths {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            registry: config.registry_path.clone(),
+            audit_log: config.audit_log_path.clone(),
+            identities: config.identities_path.clone(),
+            contributions: config.contributions_path.clone(),
+            link_status: config.link_status_path.clone(),
+        }
+    }
+
+    fn all(&self) -> Vec<&PathBuf> {
+        let mut paths = vec![
+            &self.registry,
+            &self.audit_log,
+            &self.identities,
+            &self.contributions,
+        ];
+        paths.extend(&self.link_status);
+        paths
+    }
+}
+
+/// Load and validate every data file.
+/// Fails on the first invalid file, so a snapshot is only produced if all pass.
+pub async fn load_snapshot(paths: &DataPaths) -> Result<DataSnapshot, anyhow::Error> {
+    let mut registry = crate::registry::load(&paths.registry).await?;
+    if let Some(link_status) = &paths.link_status {
+        let status = crate::linkcheck::load_status(link_status).await?;
+        crate::linkcheck::apply_status(&mut registry, &status);
+    }
+    let audit_log = crate::audit::load(&paths.audit_log).await?;
+    let identities = crate::identity::load(&paths.identities).await?;
+    // Contributions validate voter pubkeys against the freshly loaded identities
+    let proposals = crate::contributions::load(&paths.contributions, &identities).await?;
+
+    Ok(DataSnapshot::new(registry, audit_log, identities, proposals))
+}
+
+/// Warn when the registry's curator pubkey is not the node's own key.
+/// Peers only accept a registry whose `curator.pubkey` is the key that
+/// signed it, so such a registry cannot be federated with.
+pub fn warn_on_foreign_curator(registry: &Registry, node_pubkey: &str) {
+    if registry.curator.pubkey != node_pubkey {
+        tracing::warn!(
+            curator_pubkey = %registry.curator.pubkey,
+            node_pubkey = %node_pubkey,
+            "Registry curator pubkey is not this node's key; peers will not trust its registry \
+             until curator.pubkey is set to the node pubkey"
+        );
+    }
+}
+
+/// Reload all data files and swap them into `store` if every file validates.
+/// On error the previous snapshot stays in place. After a successful swap the
+/// peer cache is reconciled with the new endorsements and new peers are fetched.
+pub async fn reload(
+    paths: &DataPaths,
+    store: &DataStore,
+    peer_cache: &PeerCache,
+) -> Result<(), anyhow::Error> {
+    let snapshot = load_snapshot(paths).await?;
+    let endorsements = snapshot.registry.endorsements.clone();
+    warn_on_foreign_curator(&snapshot.registry, peer_cache.local_pubkey());
+
+    tracing::info!(
+        version = %snapshot.registry.version,
+        categories = snapshot.registry.categories.len(),
+        audit_entries = snapshot.audit_log.len(),
+        identities = snapshot.identities.len(),
+        proposals = snapshot.proposals.len(),
+        "Data files reloaded"
+    );
+    store.store(snapshot);
+
+    let added = peer_cache.sync_endorsements(endorsements).await;
+    for pubkey in added {
+        peer_cache.fetch_peer(&pubkey).await;
+    }
+
+    Ok(())
+}
+
+/// Spawn the background reload task.
+///
+/// Reloads when any data file's modification time changes (checked every
+/// `poll_interval`; `None` disables file watching) or when the process receives
+/// SIGHUP. A reload that fails validation is retried on every poll until the
+/// files load. Stops when `shutdown_rx` changes.
+pub fn spawn_reload_task(
+    paths: DataPaths,
+    store: Arc<DataStore>,
+    peer_cache: Arc<PeerCache>,
+    poll_interval: Option<Duration>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        // Modification times of the files behind the current snapshot. They
+        // only advance on a successful reload, so a file that failed to
+        // validate is retried on every poll until it loads, even when a
+        // follow-up write lands within the same mtime tick.
+        let mut last_modified = modified_times(&paths).await;
+        // Modification times of the last failed reload, to log each failure once
+        let mut failed_modified = None;
+        let mut interval = poll_interval.map(tokio::time::interval);
+        let mut hangup = install_hangup();
+
+        loop {
+            let (trigger, current) = tokio::select! {
+                _ = tick(&mut interval) => {
+                    let current = modified_times(&paths).await;
+                    if current == last_modified {
+                        continue;
+                    }
+                    ("file change", current)
+                }
+                _ = recv_hangup(&mut hangup) => ("SIGHUP", modified_times(&paths).await),
+                _ = shutdown_rx.changed() => {
+                    tracing::info!("Reload task shutting down");
+                    break;
+                }
+            };
+
+            // Polls retrying files that already failed to load stay quiet
+            let retry = trigger == "file change" && failed_modified.as_ref() == Some(&current);
+            if !retry {
+                tracing::info!(trigger, "Reloading data files");
+            }
+            match reload(&paths, &store, &peer_cache).await {
+                Ok(()) => {
+                    last_modified = current;
+                    failed_modified = None;
+                }
+                Err(e) if retry => {
+                    tracing::debug!(error = %e, "Reload retry failed, keeping previous data");
+                }
+                Err(e) => {
+                    tracing::error!(trigger, error = %e, "Reload failed, keeping previous data");
+                    failed_modified = Some(current);
+                }
+            }
+        }
+    })
+}
+
+/// Modification time of each data file (None if unreadable)
+async fn modified_times(paths: &DataPaths) -> Vec<Option<SystemTime>> {
+    let mut times = Vec::with_capacity(4);
+    for path in paths.all() {
+        let modified = tokio::fs::metadata(path)
+            .await
+            .ok()
+            .and_then(|m| m.modified().ok());
+        times.push(modified);
+    }
+    times
+}
+
+/// Wait for the next poll tick, or forever if polling is disabled
+async fn tick(interval: &mut Option<tokio::time::Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending::<()>().await,
+    }
+}
+
+#[cfg(unix)]
+type HangupSignal = tokio::signal::unix::Signal;
+
+#[cfg(not(unix))]
+type HangupSignal = ();
+
+#[cfg(unix)]
+fn install_hangup() -> Option<HangupSignal> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to install SIGHUP handler, reload on signal disabled");
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn install_hangup() -> Option<HangupSignal> {
+    None
+}
+
+/// Wait for SIGHUP, or forever if no handler is installed
+async fn recv_hangup(hangup: &mut Option<HangupSignal>) {
+    #[cfg(unix)]
+    {
+        if let Some(signal) = hangup {
+            if signal.recv().await.is_some() {
+                return;
+            }
+            // Signal stream closed; stop listening
+            *hangup = None;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = hangup;
+
+    std::future::pending::<()>().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matcher::Language;
+
+    const REGISTRY_JSON: &str = include_str!("../registry.json");
+    const AUDIT_LOG_JSON: &str = include_str!("../audit_log.json");
+    const IDENTITIES_JSON: &str = include_str!("../identities.json");
+    const CONTRIBUTIONS_JSON: &str = include_str!("../contributions.json");
+
+    /// Write the shipped data files into a fresh temp directory
+    async fn write_data_dir(name: &str) -> DataPaths {
+        let dir = std::env::temp_dir().join(format!("3gs-reload-{}-{}", name, std::process::id()));
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        let paths = DataPaths {
+            registry: dir.join("registry.json"),
+            audit_log: dir.join("audit_log.json"),
+            identities: dir.join("identities.json"),
+            contributions: dir.join("contributions.json"),
+            link_status: None,
+        };
+        tokio::fs::write(&paths.registry, REGISTRY_JSON).await.unwrap();
+        tokio::fs::write(&paths.audit_log, AUDIT_LOG_JSON).await.unwrap();
+        tokio::fs::write(&paths.identities, IDENTITIES_JSON).await.unwrap();
+        tokio::fs::write(&paths.contributions, CONTRIBUTIONS_JSON).await.unwrap();
+        paths
+    }
+
+    async fn cleanup(paths: &DataPaths) {
+        if let Some(dir) = paths.registry.parent() {
+            let _ = tokio::fs::remove_dir_all(dir).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_snapshot_shipped_files() {
+        let paths = write_data_dir("load").await;
+
+        let snapshot = load_snapshot(&paths).await.expect("Shipped data should load");
+        assert_eq!(snapshot.registry.categories.len(), 10);
+        assert_eq!(
+            snapshot.match_index.language(Language::English).categories.len(),
+            10
+        );
+        assert_eq!(snapshot.audit_log.len(), 40);
+
+        cleanup(&paths).await;
+    }
+
+    #[tokio::test]
+    async fn test_load_snapshot_applies_link_status() {
+        let mut paths = write_data_dir("link-status").await;
+        let link_status = paths.registry.with_file_name("link_status.json");
+        paths.link_status = Some(link_status.clone());
+
+        // A configured but missing status file leaves sources unstamped
+        let snapshot = load_snapshot(&paths).await.unwrap();
+        let book = &snapshot.registry.categories["rust-learning"].sources[0];
+        assert_eq!(book.last_checked, None);
+
+        let status = serde_json::json!({
+            "https://doc.rust-lang.org/book/": {
+                "date": "2026-10-01",
+                "status": "ok",
+                "http_status": 200
+            }
+        });
+        tokio::fs::write(&link_status, status.to_string()).await.unwrap();
+        assert!(paths.all().contains(&&link_status), "status file is watched");
+
+        let snapshot = load_snapshot(&paths).await.unwrap();
+        let book = &snapshot.registry.categories["rust-learning"].sources[0];
+        let check = book.last_checked.as_ref().expect("stamped from the status file");
+        assert_eq!(check.status, crate::registry::types::LinkCheckStatus::Ok);
+        assert_eq!(check.http_status, Some(200));
+
+        cleanup(&paths).await;
+    }
+
+    #[tokio::test]
+    async fn test_reload_swaps_valid_snapshot() {
+        let paths = write_data_dir("swap").await;
+        let store = DataStore::new(load_snapshot(&paths).await.unwrap());
+        let peer_cache = PeerCache::new(vec![], "local-key".to_string());
+
+        let mut registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        registry["version"] = serde_json::json!("9.9.9");
+        tokio::fs::write(&paths.registry, registry.to_string()).await.unwrap();
+
+        reload(&paths, &store, &peer_cache).await.expect("Reload should succeed");
+        assert_eq!(store.load().registry.version, "9.9.9");
+
+        cleanup(&paths).await;
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_old_snapshot_on_error() {
+        let paths = write_data_dir("keep").await;
+        let store = DataStore::new(load_snapshot(&paths).await.unwrap());
+        let peer_cache = PeerCache::new(vec![], "local-key".to_string());
+        let before = store.load();
+
+        // Valid registry change paired with an invalid identities file
+        let mut registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        registry["version"] = serde_json::json!("9.9.9");
+        tokio::fs::write(&paths.registry, registry.to_string()).await.unwrap();
+        tokio::fs::write(&paths.identities, "{ not json").await.unwrap();
+
+        let result = reload(&paths, &store, &peer_cache).await;
+        assert!(result.is_err(), "Reload should fail on invalid identities");
+        assert!(Arc::ptr_eq(&before, &store.load()), "Old snapshot should be kept");
+        assert_ne!(store.load().registry.version, "9.9.9");
+
+        cleanup(&paths).await;
+    }
+
+    #[tokio::test]
+    async fn test_poll_retries_failed_reload_with_unchanged_mtime() {
+        let paths = write_data_dir("retry").await;
+        let store = Arc::new(DataStore::new(load_snapshot(&paths).await.unwrap()));
+        let peer_cache = Arc::new(PeerCache::new(vec![], "local-key".to_string()));
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = spawn_reload_task(
+            paths.clone(),
+            Arc::clone(&store),
+            peer_cache,
+            Some(Duration::from_millis(10)),
+            shutdown_rx,
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // First step of a two-step write: identities are broken
+        tokio::fs::write(&paths.identities, "{ not json").await.unwrap();
+        let mut registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        registry["version"] = serde_json::json!("9.9.9");
+        tokio::fs::write(&paths.registry, registry.to_string()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_ne!(store.load().registry.version, "9.9.9");
+
+        // The second step lands within the same mtime tick
+        let broken_at = std::fs::metadata(&paths.identities).unwrap().modified().unwrap();
+        std::fs::write(&paths.identities, IDENTITIES_JSON).unwrap();
+        let file = std::fs::File::options().write(true).open(&paths.identities).unwrap();
+        file.set_modified(broken_at).unwrap();
+
+        for _ in 0..200 {
+            if store.load().registry.version == "9.9.9" {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(store.load().registry.version, "9.9.9", "Fixed files should load");
+
+        shutdown_tx.send(true).unwrap();
+        handle.await.unwrap();
+        cleanup(&paths).await;
+    }
+
+    #[tokio::test]
+    async fn test_reload_syncs_peer_cache() {
+        let paths = write_data_dir("peers").await;
+        let store = DataStore::new(load_snapshot(&paths).await.unwrap());
+        let peer_cache = PeerCache::new(vec![], "local-key".to_string());
+
+        let mut registry: serde_json::Value = serde_json::from_str(REGISTRY_JSON).unwrap();
+        registry["endorsements"] = serde_json::json!([{
+            "pubkey": "peer-a",
+            "url": "http://127.0.0.1:1",
+            "since": "2026-04-03"
+        }]);
+        tokio::fs::write(&paths.registry, registry.to_string()).await.unwrap();
+
+        reload(&paths, &store, &peer_cache).await.expect("Reload should succeed");
+        assert_eq!(peer_cache.peer_count().await, 1);
+
+        cleanup(&paths).await;
+    }
+}
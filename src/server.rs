@@ -1,18 +1,20 @@
-use crate::audit::{AuditEntry, AuditFilterParams, filter_entries};
-use crate::contributions::{Proposal, ProposalFilterParams, ProposalSummary};
-use crate::federation::PeerCache;
-use crate::identity::Identity;
-use crate::mcp::McpHandler;
-use crate::registry::Registry;
+use crate::analytics::{parse_since, read_events, QueryLog, QueryStats, QueryStatsParams, DEFAULT_UNMATCHED_LIMIT};
+use crate::audit::{AuditEntry, AuditFilterParams, VerificationReport, filter_entries, verification_report};
+use crate::contributions::{ProposalFilterParams, ProposalSummary};
+use crate::federation::{peer_reports, FederationSummary, PeerCache};
+use crate::mcp::{McpHandler, McpReply, SessionKey, SUPPORTED_PROTOCOL_VERSIONS};
+use crate::registry::{registry_etag, sign_registry, PUBKEY_HEADER, SIGNATURE_HEADER};
+use crate::reload::DataStore;
 use axum::{
     extract::{Path, Query, State},
-    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
-use pkarr::PublicKey;
+use pkarr::{Keypair, PublicKey};
 use serde_json::json;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use std::time::Duration;
 use tower_http::cors::CorsLayer;
@@ -20,15 +22,25 @@ use uuid::Uuid;
 
 const LANDING_HTML: &str = include_str!("../docs/index.html");
 
+/// Streamable HTTP session header (issued on initialize, echoed by clients)
+const MCP_SESSION_ID: &str = "mcp-session-id";
+
+/// Streamable HTTP protocol version header (sent by clients after initialize)
+const MCP_PROTOCOL_VERSION: &str = "mcp-protocol-version";
+
 /// Application state shared across all route handlers
 pub struct AppState {
     pub mcp_handler: McpHandler,
-    pub registry: Arc<Registry>,
+    /// Registry, audit log, identities and proposals; swapped atomically on reload
+    pub data: Arc<DataStore>,
     pub pubkey: PublicKey,  // PublicKey is Copy, no Arc needed
-    pub audit_log: Arc<Vec<AuditEntry>>,
-    pub identities: Arc<HashMap<String, Identity>>,
-    pub proposals: Arc<HashMap<Uuid, Proposal>>,
+    /// Node keypair; signs the registry served at /registry
+    pub keypair: Keypair,
     pub peer_cache: Arc<PeerCache>,
+    /// Bearer token for /admin endpoints; None disables them
+    pub admin_token: Option<String>,
+    /// Query analytics log; None when analytics are disabled
+    pub query_log: Option<Arc<QueryLog>>,
 }
 
 /// Build the axum router with all routes and middleware
@@ -38,74 +50,204 @@ pub fn build_router(state: Arc<AppState>) -> Router {
             "https://3gs.ai".parse::<HeaderValue>().unwrap(),
             "https://api.3gs.ai".parse::<HeaderValue>().unwrap(),
         ])
-        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION])
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::AUTHORIZATION,
+            HeaderName::from_static(MCP_SESSION_ID),
+            HeaderName::from_static(MCP_PROTOCOL_VERSION),
+        ])
         .expose_headers([
-            HeaderName::from_static("mcp-session-id"),
+            HeaderName::from_static(MCP_SESSION_ID),
             HeaderName::from_static("x-request-id"),
+            HeaderName::from_static(SIGNATURE_HEADER),
+            HeaderName::from_static(PUBKEY_HEADER),
+            header::ETAG,
         ])
         .max_age(Duration::from_secs(3600));
 
     Router::new()
         .route("/", get(landing_page_endpoint))
-        .route("/mcp", post(mcp_endpoint))
+        .route("/mcp", post(mcp_endpoint).delete(mcp_delete_endpoint))
         .route("/health", get(health_endpoint))
         .route("/registry", get(registry_endpoint))
+        .route("/peers", get(peers_endpoint))
         .route("/audit", get(audit_endpoint))
+        .route("/audit/verify", get(audit_verify_endpoint))
         .route("/identities", get(identities_endpoint))
         .route("/identities/{pubkey}", get(identity_by_pubkey_endpoint))
         .route("/proposals", get(proposals_endpoint))
         .route("/proposals/{id}", get(proposal_by_id_endpoint))
+        .route("/admin/query-stats", get(query_stats_endpoint))
         .layer(cors)
         .with_state(state)
 }
 
-/// POST /mcp - MCP JSON-RPC endpoint
+/// POST /mcp - MCP JSON-RPC endpoint (Streamable HTTP transport)
+///
+/// `initialize` issues a session id in the Mcp-Session-Id response header.
+/// Other messages must echo it: missing -> 400, unknown or expired -> 404.
 async fn mcp_endpoint(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     body: String,
-) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    match state.mcp_handler.handle_json(&body).await {
-        Some(json) => (
-            StatusCode::OK,
-            [(header::CONTENT_TYPE, "application/json")],
-            json,
-        ),
-        None => (
-            StatusCode::NO_CONTENT,
-            [(header::CONTENT_TYPE, "application/json")],
-            String::new(),
-        ),
+) -> Response {
+    let session_id = headers.get(MCP_SESSION_ID).and_then(|v| v.to_str().ok());
+
+    // MCP-Protocol-Version must be one we support and, within a session,
+    // the version negotiated at initialize
+    if let Some(version) = headers.get(MCP_PROTOCOL_VERSION) {
+        let version = version.to_str().unwrap_or_default();
+        let negotiated = match session_id {
+            Some(id) => state.mcp_handler.session(id).await.map(|s| s.protocol_version),
+            None => None,
+        };
+        let accepted = match negotiated {
+            Some(negotiated) => version == negotiated,
+            None => SUPPORTED_PROTOCOL_VERSIONS.contains(&version),
+        };
+        if !accepted {
+            return json_response(
+                StatusCode::BAD_REQUEST,
+                r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32600,"message":"Unsupported MCP-Protocol-Version"}}"#
+                    .to_string(),
+            );
+        }
+    }
+
+    match state
+        .mcp_handler
+        .handle_message(SessionKey::Http(session_id), &body)
+        .await
+    {
+        McpReply::Message {
+            body: Some(json),
+            session_id,
+        } => {
+            let mut response = json_response(StatusCode::OK, json);
+            if let Some(value) = session_id.and_then(|id| HeaderValue::from_str(&id).ok()) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(MCP_SESSION_ID), value);
+            }
+            response
+        }
+        McpReply::Message { body: None, .. } => json_response(StatusCode::NO_CONTENT, String::new()),
+        McpReply::BadRequest(json) => json_response(StatusCode::BAD_REQUEST, json),
+        McpReply::SessionNotFound(json) => json_response(StatusCode::NOT_FOUND, json),
+    }
+}
+
+/// DELETE /mcp - End the MCP session named in the Mcp-Session-Id header
+async fn mcp_delete_endpoint(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> StatusCode {
+    match headers.get(MCP_SESSION_ID).and_then(|v| v.to_str().ok()) {
+        None => StatusCode::BAD_REQUEST,
+        Some(session_id) if state.mcp_handler.end_session(session_id).await => {
+            StatusCode::NO_CONTENT
+        }
+        Some(_) => StatusCode::NOT_FOUND,
     }
 }
 
-/// GET /health - Health check endpoint
+/// Build a response with an application/json content type
+fn json_response(status: StatusCode, body: String) -> Response {
+    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+/// GET /health - Health check endpoint, with endorsed peers counted by status
 async fn health_endpoint(
     State(state): State<Arc<AppState>>,
 ) -> Json<serde_json::Value> {
+    let reports = peer_reports(&state.peer_cache.get_all_cached().await, chrono::Utc::now());
     Json(json!({
         "status": "ok",
         "version": env!("CARGO_PKG_VERSION"),
-        "pubkey": state.pubkey.to_z32()
+        "pubkey": state.pubkey.to_z32(),
+        "federation": FederationSummary::new(&reports)
     }))
 }
 
-/// GET /registry - Registry JSON endpoint
-async fn registry_endpoint(
-    State(state): State<Arc<AppState>>,
-) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    match serde_json::to_string_pretty(&*state.registry) {
-        Ok(json) => (
-            StatusCode::OK,
-            [(header::CONTENT_TYPE, "application/json")],
-            json,
-        ),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            [(header::CONTENT_TYPE, "application/json")],
-            format!(r#"{{"error":"Failed to serialize registry: {}"}}"#, e),
-        ),
+/// GET /peers - Status of every endorsed peer: last fetch, last error and
+/// what its cached registry holds
+async fn peers_endpoint(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let reports = peer_reports(&state.peer_cache.get_all_cached().await, chrono::Utc::now());
+    Json(json!({
+        "summary": FederationSummary::new(&reports),
+        "peers": reports
+    }))
+}
+
+/// GET /registry - Registry JSON endpoint.
+/// Carries a detached Ed25519 signature of the registry's canonical form,
+/// made with the node key, in the X-Registry-Signature header. The ETag is a
+/// hash of the same canonical form; a request whose If-None-Match names it
+/// gets 304 Not Modified with no body.
+async fn registry_endpoint(State(state): State<Arc<AppState>>, headers: HeaderMap) -> Response {
+    let data = state.data.load();
+    let etag = registry_etag(&data.registry);
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| etag_matches(value, &etag));
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        match serde_json::to_string_pretty(&data.registry) {
+            Ok(json) => (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "application/json".to_string()),
+                    (
+                        HeaderName::from_static(SIGNATURE_HEADER),
+                        sign_registry(&data.registry, &state.keypair),
+                    ),
+                    (HeaderName::from_static(PUBKEY_HEADER), state.pubkey.to_z32()),
+                ],
+                json,
+            )
+                .into_response(),
+            Err(e) => {
+                return json_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!(r#"{{"error":"Failed to serialize registry: {}"}}"#, e),
+                );
+            }
+        }
+    };
+
+    let validators = [
+        (header::ETAG, Some(etag)),
+        (header::LAST_MODIFIED, last_modified(&data.registry.updated)),
+    ];
+    for (name, value) in validators {
+        if let Some(value) = value.and_then(|v| HeaderValue::from_str(&v).ok()) {
+            response.headers_mut().insert(name, value);
+        }
     }
+    response
+}
+
+/// HTTP date for a registry's `updated` field: midnight UTC for a plain
+/// date, or the timestamp itself. None if it is neither.
+fn last_modified(updated: &str) -> Option<String> {
+    let modified = match chrono::NaiveDate::parse_from_str(updated, "%Y-%m-%d") {
+        Ok(date) => date.and_hms_opt(0, 0, 0)?.and_utc(),
+        Err(_) => chrono::DateTime::parse_from_rfc3339(updated).ok()?.to_utc(),
+    };
+    Some(modified.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+}
+
+/// Whether an If-None-Match header value names `etag`. If-None-Match uses
+/// weak comparison, so a `W/` prefix does not prevent a match.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
 }
 
 /// GET /audit - Audit log endpoint with optional query filters
@@ -113,7 +255,8 @@ async fn audit_endpoint(
     State(state): State<Arc<AppState>>,
     Query(params): Query<AuditFilterParams>,
 ) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    let filtered = filter_entries(&state.audit_log, &params);
+    let data = state.data.load();
+    let filtered = filter_entries(&data.audit_log, &params);
     let entries: Vec<&AuditEntry> = filtered;
 
     match serde_json::to_string(&entries) {
@@ -130,11 +273,19 @@ async fn audit_endpoint(
     }
 }
 
+/// GET /audit/verify - Signature and hash chain integrity report for the audit log
+async fn audit_verify_endpoint(
+    State(state): State<Arc<AppState>>,
+) -> Json<VerificationReport> {
+    Json(verification_report(&state.data.load().audit_log))
+}
+
 /// GET /identities - Returns all identities as JSON object keyed by pubkey
 async fn identities_endpoint(
     State(state): State<Arc<AppState>>,
 ) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    match serde_json::to_string_pretty(&*state.identities) {
+    let data = state.data.load();
+    match serde_json::to_string_pretty(&data.identities) {
         Ok(json) => (
             StatusCode::OK,
             [(header::CONTENT_TYPE, "application/json")],
@@ -153,7 +304,8 @@ async fn identity_by_pubkey_endpoint(
     State(state): State<Arc<AppState>>,
     Path(pubkey): Path<String>,
 ) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    match state.identities.get(&pubkey) {
+    let data = state.data.load();
+    match data.identities.get(&pubkey) {
         Some(identity) => match serde_json::to_string_pretty(identity) {
             Ok(json) => (
                 StatusCode::OK,
@@ -179,7 +331,8 @@ async fn proposals_endpoint(
     State(state): State<Arc<AppState>>,
     Query(params): Query<ProposalFilterParams>,
 ) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    let mut summaries: Vec<ProposalSummary> = state
+    let data = state.data.load();
+    let mut summaries: Vec<ProposalSummary> = data
         .proposals
         .iter()
         .filter(|(_, proposal)| {
@@ -233,12 +386,10 @@ async fn proposal_by_id_endpoint(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
 ) -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], String) {
-    match state.proposals.get(&id) {
+    let data = state.data.load();
+    match data.proposals.get(&id) {
         Some(proposal) => {
-            let mut value = serde_json::to_value(proposal).unwrap_or_default();
-            if let Some(obj) = value.as_object_mut() {
-                obj.insert("id".to_string(), serde_json::Value::String(id.to_string()));
-            }
+            let value = proposal.to_json_with_id(&id).unwrap_or_default();
             match serde_json::to_string_pretty(&value) {
                 Ok(json) => (
                     StatusCode::OK,
@@ -260,6 +411,77 @@ async fn proposal_by_id_endpoint(
     }
 }
 
+/// Check the `Authorization: Bearer <token>` header against the admin token.
+/// Compares SHA-256 digests so the comparison time does not depend on how
+/// much of the token a caller guessed. An empty bearer value never matches.
+fn is_admin(headers: &HeaderMap, admin_token: &str) -> bool {
+    let Some(provided) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .filter(|v| !v.trim().is_empty())
+    else {
+        return false;
+    };
+    Sha256::digest(provided.as_bytes()) == Sha256::digest(admin_token.as_bytes())
+}
+
+/// GET /admin/query-stats - Aggregated query analytics for curators
+///
+/// Requires `Authorization: Bearer <ADMIN_TOKEN>`. Returns 404 when no admin
+/// token is configured or analytics are disabled, 401 for a missing or wrong
+/// token.
+async fn query_stats_endpoint(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(params): Query<QueryStatsParams>,
+) -> Response {
+    let Some(admin_token) = state.admin_token.as_deref() else {
+        return json_response(StatusCode::NOT_FOUND, r#"{"error":"Not found"}"#.to_string());
+    };
+    if !is_admin(&headers, admin_token) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [
+                (header::CONTENT_TYPE, "application/json"),
+                (header::WWW_AUTHENTICATE, "Bearer"),
+            ],
+            r#"{"error":"Unauthorized"}"#.to_string(),
+        )
+            .into_response();
+    }
+    let Some(query_log) = &state.query_log else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            r#"{"error":"Query analytics are disabled"}"#.to_string(),
+        );
+    };
+
+    let since = match params.since.as_deref().map(parse_since).transpose() {
+        Ok(since) => since,
+        Err(e) => return json_response(StatusCode::BAD_REQUEST, json!({ "error": e }).to_string()),
+    };
+    let limit = params.limit.unwrap_or(DEFAULT_UNMATCHED_LIMIT);
+
+    let events = match read_events(query_log.path()).await {
+        Ok(events) => events,
+        Err(e) => {
+            return json_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                json!({ "error": e.to_string() }).to_string(),
+            );
+        }
+    };
+
+    match serde_json::to_string(&QueryStats::from_events(&events, since, limit)) {
+        Ok(json) => json_response(StatusCode::OK, json),
+        Err(e) => json_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!(r#"{{"error":"Failed to serialize query stats: {}"}}"#, e),
+        ),
+    }
+}
+
 /// GET / - Landing page endpoint
 async fn landing_page_endpoint() -> (StatusCode, [(axum::http::HeaderName, &'static str); 1], &'static str) {
     (StatusCode::OK, [(header::CONTENT_TYPE, "text/html; charset=utf-8")], LANDING_HTML)
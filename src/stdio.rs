@@ -0,0 +1,135 @@
+use crate::mcp::McpHandler;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Serve MCP over a line-delimited stream (stdio transport).
+///
+/// Each input line is one JSON-RPC message; each response is written as one
+/// line and flushed immediately. Notifications produce no output. Blank lines
+/// are skipped. Returns when the input reaches EOF.
+///
+/// Stdout carries only protocol messages, so logging must go to stderr.
+pub async fn serve<R, W>(handler: &McpHandler, reader: R, mut writer: W) -> std::io::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut lines = reader.lines();
+
+    while let Some(line) = lines.next_line().await? {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(response) = handler.handle_json(line).await {
+            writer.write_all(response.as_bytes()).await?;
+            writer.write_all(b"\n").await?;
+            writer.flush().await?;
+        }
+    }
+
+    tracing::info!("stdin closed, stdio transport shutting down");
+    Ok(())
+}
+
+/// Serve MCP over the process's stdin and stdout
+pub async fn serve_stdio(handler: &McpHandler) -> std::io::Result<()> {
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    serve(handler, stdin, tokio::io::stdout()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::federation::PeerCache;
+    use crate::matcher::{MatchConfig, MatchMode};
+    use crate::registry::Registry;
+    use crate::reload::{DataSnapshot, DataStore};
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn test_handler() -> McpHandler {
+        let registry: Registry = serde_json::from_str(include_str!("../registry.json"))
+            .expect("Failed to parse test registry.json");
+
+        let match_config = MatchConfig {
+            match_threshold: 0.4,
+            match_fuzzy_weight: 0.7,
+            match_keyword_weight: 0.3,
+            match_mode: MatchMode::Fuzzy,
+            match_bm25_weight: 0.5,
+        };
+
+        let data = Arc::new(DataStore::new(DataSnapshot::new(
+            registry,
+            vec![],
+            HashMap::new(),
+            HashMap::new(),
+        )));
+        let peer_cache = Arc::new(PeerCache::new(vec![], "test-pubkey-z32".to_string()));
+
+        McpHandler::new(data, match_config, "test-pubkey-z32".to_string(), peer_cache)
+    }
+
+    /// Run the transport over `input` and return the output lines as JSON
+    async fn run(input: &str) -> Vec<Value> {
+        let handler = test_handler();
+        let mut output = Vec::new();
+        serve(&handler, input.as_bytes(), &mut output).await.unwrap();
+
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Each output line should be JSON"))
+            .collect()
+    }
+
+    const INIT: &str = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-11-25","capabilities":{},"clientInfo":{"name":"test","version":"1.0"}}}"#;
+
+    #[tokio::test]
+    async fn test_session_over_lines() {
+        let input = format!(
+            "{}\n{}\n{}\n",
+            INIT,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"get_sources","arguments":{"query":"learn rust"}}}"#,
+        );
+
+        let responses = run(&input).await;
+        assert_eq!(responses.len(), 2, "Notification should produce no output");
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[0]["result"]["protocolVersion"], "2025-11-25");
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["result"]["isError"], false);
+    }
+
+    #[tokio::test]
+    async fn test_requires_initialize() {
+        let responses = run("{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"tools/list\"}\n").await;
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0]["error"]["code"], -32002);
+    }
+
+    #[tokio::test]
+    async fn test_skips_blank_lines_and_reports_parse_errors() {
+        let input = format!("\n{}\n   \n{{ not json\n", INIT);
+
+        let responses = run(&input).await;
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(responses[1]["error"]["code"], -32700);
+    }
+
+    #[tokio::test]
+    async fn test_handles_crlf_and_missing_final_newline() {
+        let input = format!(
+            "{}\r\n{}",
+            INIT, r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#
+        );
+
+        let responses = run(&input).await;
+        assert_eq!(responses.len(), 2);
+        assert!(responses[1]["result"]["tools"].is_array());
+    }
+}
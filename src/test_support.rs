@@ -0,0 +1,37 @@
+//! Helpers shared by the unit tests and, through `tests/common`, the
+//! integration tests
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A unique file path in the system temp directory. The file is removed when
+/// the guard is dropped, so a failing test does not leave it behind.
+pub struct TempPath(PathBuf);
+
+impl TempPath {
+    /// A fresh path ending in `name`, e.g. `"query-log.jsonl"`
+    pub fn new(name: &str) -> Self {
+        let file_name = format!("3gs-{}-{}", uuid::Uuid::new_v4(), name);
+        Self(std::env::temp_dir().join(file_name))
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -1,12 +1,15 @@
+use three_good_sources::analytics::QueryLog;
 use three_good_sources::audit::AuditEntry;
 use three_good_sources::contributions::Proposal;
 use three_good_sources::federation::PeerCache;
 use three_good_sources::identity::Identity;
-use three_good_sources::matcher::MatchConfig;
+use three_good_sources::matcher::{MatchConfig, MatchMode};
 use three_good_sources::mcp::McpHandler;
 use three_good_sources::pubky::identity::generate_or_load_keypair;
 use three_good_sources::registry::Registry;
+use three_good_sources::reload::{DataSnapshot, DataStore};
 use three_good_sources::server::{AppState, build_router};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -16,24 +19,54 @@ use uuid::Uuid;
 /// Spawn a real HTTP server on a random port for integration testing.
 /// Returns the socket address for making requests.
 pub async fn spawn_test_server() -> SocketAddr {
+    // Default match config
+    let match_config = MatchConfig {
+        match_threshold: 0.4,
+        match_fuzzy_weight: 0.7,
+        match_keyword_weight: 0.3,
+        match_mode: MatchMode::Fuzzy,
+        match_bm25_weight: 0.5,
+    };
+
+    spawn_test_server_with_match_config(match_config).await
+}
+
+/// Spawn a test server that matches queries with the given config
+pub async fn spawn_test_server_with_match_config(match_config: MatchConfig) -> SocketAddr {
+    spawn_server(match_config, None, None).await
+}
+
+/// Spawn a test server with an admin token and/or query analytics log
+#[allow(dead_code)]
+pub async fn spawn_test_server_with_analytics(
+    admin_token: Option<&str>,
+    query_log: Option<Arc<QueryLog>>,
+) -> SocketAddr {
+    let match_config = MatchConfig {
+        match_threshold: 0.4,
+        match_fuzzy_weight: 0.7,
+        match_keyword_weight: 0.3,
+        match_mode: MatchMode::Fuzzy,
+        match_bm25_weight: 0.5,
+    };
+
+    spawn_server(match_config, admin_token.map(str::to_string), query_log).await
+}
+
+async fn spawn_server(
+    match_config: MatchConfig,
+    admin_token: Option<String>,
+    query_log: Option<Arc<QueryLog>>,
+) -> SocketAddr {
     // Load real registry from project root (relative to tests/)
     let registry_json = include_str!("../../registry.json");
     let registry: Registry = serde_json::from_str(registry_json)
         .expect("Failed to parse registry.json");
-    let registry = Arc::new(registry);
 
     // Load audit log
     let audit_log_json = include_str!("../../audit_log.json");
     let audit_log: Vec<AuditEntry> = serde_json::from_str(audit_log_json)
         .expect("Failed to parse audit_log.json");
-    let audit_log = Arc::new(audit_log);
-
-    // Default match config
-    let match_config = MatchConfig {
-        match_threshold: 0.4,
-        match_fuzzy_weight: 0.7,
-        match_keyword_weight: 0.3,
-    };
 
     // Generate ephemeral keypair for testing
     let keypair = generate_or_load_keypair(None)
@@ -45,35 +78,40 @@ pub async fn spawn_test_server() -> SocketAddr {
     let identities_json = include_str!("../../identities.json");
     let identities: HashMap<String, Identity> = serde_json::from_str(identities_json)
         .expect("Failed to parse identities.json");
-    let identities = Arc::new(identities);
 
     // Load contributions
     let contributions_json = include_str!("../../contributions.json");
     let contributions: HashMap<Uuid, Proposal> = serde_json::from_str(contributions_json)
         .expect("Failed to parse contributions.json");
-    let proposals = Arc::new(contributions);
+
+    let data = Arc::new(DataStore::new(DataSnapshot::new(
+        registry,
+        audit_log,
+        identities,
+        contributions,
+    )));
 
     // Build peer cache (empty endorsements for testing)
     let peer_cache = Arc::new(PeerCache::new(vec![], pubkey.to_z32()));
 
     // Build MCP handler and app state
-    let mcp_handler = McpHandler::new(
-        Arc::clone(&registry),
+    let mut mcp_handler = McpHandler::new(
+        Arc::clone(&data),
         match_config,
         pubkey_z32,
-        Arc::clone(&audit_log),
-        Arc::clone(&identities),
-        Arc::clone(&proposals),
         Arc::clone(&peer_cache),
     );
+    if let Some(query_log) = &query_log {
+        mcp_handler = mcp_handler.with_query_log(Arc::clone(query_log));
+    }
     let app_state = Arc::new(AppState {
         mcp_handler,
-        registry,
+        data,
         pubkey,
-        audit_log,
-        identities,
-        proposals,
+        keypair,
         peer_cache,
+        admin_token,
+        query_log,
     });
 
     let app = build_router(app_state);
@@ -93,3 +131,48 @@ pub async fn spawn_test_server() -> SocketAddr {
 
     addr
 }
+
+/// Initialize an MCP session against a test server.
+/// Returns a client that sends the issued Mcp-Session-Id on every request,
+/// plus the initialize response body.
+#[allow(dead_code)]
+pub async fn mcp_session(addr: &SocketAddr) -> (reqwest::Client, Value) {
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-11-25",
+                "capabilities": {},
+                "clientInfo": {"name": "test-client", "version": "1.0"}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .expect("initialize should issue an Mcp-Session-Id header")
+        .clone();
+    let body: Value = response.json().await.unwrap();
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("mcp-session-id", session_id);
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .unwrap();
+
+    (client, body)
+}
+
+/// The crate's unit test helpers, shared so integration tests use the same
+/// `TempPath`
+#[allow(dead_code)]
+#[path = "../../src/test_support.rs"]
+pub mod test_support;
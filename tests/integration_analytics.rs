@@ -0,0 +1,164 @@
+//! Integration tests for query analytics
+//!
+//! These tests validate the anonymous query log and its admin report:
+//! - get_sources outcomes are appended to the query log
+//! - GET /admin/query-stats requires the admin bearer token
+//! - /admin endpoints are hidden when no admin token is configured
+//! - Stats count matched categories and unmatched normalized queries
+
+mod common;
+
+use serde_json::Value;
+use std::sync::Arc;
+use three_good_sources::analytics::QueryLog;
+
+const TOKEN: &str = "test-admin-token";
+
+/// Helper to call get_sources tool
+async fn get_sources(client: &reqwest::Client, addr: &std::net::SocketAddr, query: &str) -> Value {
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {
+                "name": "get_sources",
+                "arguments": {"query": query}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_query_stats_counts_matches_and_misses() {
+    let path = common::test_support::TempPath::new("query-log.jsonl");
+    let query_log = Arc::new(QueryLog::open(&path).await.unwrap());
+    let addr = common::spawn_test_server_with_analytics(Some(TOKEN), Some(query_log)).await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    get_sources(&client, &addr, "learn rust").await;
+    get_sources(&client, &addr, "learn rust programming").await;
+    get_sources(&client, &addr, "sourdough bread").await;
+    get_sources(&client, &addr, "sourdough bread").await;
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/admin/query-stats", addr))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let stats: Value = response.json().await.unwrap();
+    assert_eq!(stats["total"], 4);
+    assert_eq!(stats["matched"], 2);
+    assert_eq!(stats["unmatched"], 2);
+    assert_eq!(stats["categories"][0]["slug"], "rust-learning");
+    assert_eq!(stats["categories"][0]["count"], 2);
+    assert_eq!(stats["unmatched_queries"][0]["query"], "sourdough bread");
+    assert_eq!(stats["unmatched_queries"][0]["count"], 2);
+
+    // limit caps the listed queries, since filters by day
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/admin/query-stats?limit=0&since=2999-01-01", addr))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    let stats: Value = response.json().await.unwrap();
+    assert_eq!(stats["total"], 0);
+    assert_eq!(stats["unmatched_queries"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn test_query_stats_requires_token() {
+    let path = common::test_support::TempPath::new("query-log.jsonl");
+    let query_log = Arc::new(QueryLog::open(&path).await.unwrap());
+    let addr = common::spawn_test_server_with_analytics(Some(TOKEN), Some(query_log)).await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/admin/query-stats", addr);
+
+    let response = client.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 401);
+    assert_eq!(response.headers()["www-authenticate"], "Bearer");
+
+    let response = client.get(&url).bearer_auth("wrong-token").send().await.unwrap();
+    assert_eq!(response.status(), 401);
+
+    let response = client
+        .get(&url)
+        .header("authorization", TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401, "token without Bearer scheme is rejected");
+}
+
+#[tokio::test]
+async fn test_query_stats_rejects_empty_bearer() {
+    let path = common::test_support::TempPath::new("query-log.jsonl");
+    let query_log = Arc::new(QueryLog::open(&path).await.unwrap());
+    let addr = common::spawn_test_server_with_analytics(Some(""), Some(query_log)).await;
+    let client = reqwest::Client::new();
+    let url = format!("http://{}/admin/query-stats", addr);
+
+    for authorization in ["Bearer ", "Bearer   "] {
+        let response = client
+            .get(&url)
+            .header("authorization", authorization)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 401, "{:?} is not a token", authorization);
+    }
+}
+
+#[tokio::test]
+async fn test_query_stats_hidden_without_admin_token() {
+    let path = common::test_support::TempPath::new("query-log.jsonl");
+    let query_log = Arc::new(QueryLog::open(&path).await.unwrap());
+    let addr = common::spawn_test_server_with_analytics(None, Some(query_log)).await;
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/admin/query-stats", addr))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_query_stats_analytics_disabled() {
+    let addr = common::spawn_test_server_with_analytics(Some(TOKEN), None).await;
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/admin/query-stats", addr))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "Query analytics are disabled");
+}
+
+#[tokio::test]
+async fn test_query_stats_rejects_bad_since() {
+    let path = common::test_support::TempPath::new("query-log.jsonl");
+    let query_log = Arc::new(QueryLog::open(&path).await.unwrap());
+    let addr = common::spawn_test_server_with_analytics(Some(TOKEN), Some(query_log)).await;
+
+    let response = reqwest::Client::new()
+        .get(format!("http://{}/admin/query-stats?since=last-week", addr))
+        .bearer_auth(TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
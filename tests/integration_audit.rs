@@ -5,6 +5,7 @@
 //! - Query filtering by action, category, since timestamp
 //! - Combined filters
 //! - Entry structure validation
+//! - GET /audit/verify integrity report
 //! - MCP get_audit_log tool via JSON-RPC
 
 mod common;
@@ -213,33 +214,36 @@ async fn test_audit_entry_structure() {
     );
 }
 
-// ===== MCP Tool Tests =====
+#[tokio::test]
+async fn test_audit_verify_reports_valid_chain() {
+    let addr = common::spawn_test_server().await;
+    let client = reqwest::Client::new();
 
-/// Helper to initialize MCP handler
-async fn initialize_mcp(client: &reqwest::Client, addr: &std::net::SocketAddr) {
-    client
-        .post(format!("http://{}/mcp", addr))
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2025-11-25",
-                "capabilities": {},
-                "clientInfo": {"name": "test-client", "version": "1.0"}
-            }
-        }))
+    let response = client
+        .get(format!("http://{}/audit/verify", addr))
         .send()
         .await
         .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let report: Value = response.json().await.unwrap();
+
+    assert_eq!(report["valid"], true, "Shipped audit log should verify: {}", report);
+    assert_eq!(report["entries"], 40);
+    assert_eq!(
+        report["head_hash"].as_str().unwrap().len(),
+        64,
+        "head_hash should be a hex-encoded SHA-256"
+    );
+    assert!(report.get("error").is_none(), "Valid report should omit error");
 }
 
+// ===== MCP Tool Tests =====
+
 #[tokio::test]
 async fn test_audit_mcp_get_audit_log() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -273,9 +277,7 @@ async fn test_audit_mcp_get_audit_log() {
 #[tokio::test]
 async fn test_audit_mcp_get_audit_log_filtered() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
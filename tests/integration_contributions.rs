@@ -164,31 +164,10 @@ async fn test_proposal_by_id_not_found() {
 
 // ===== MCP Tool Tests =====
 
-/// Helper to initialize MCP handler
-async fn initialize_mcp(client: &reqwest::Client, addr: &std::net::SocketAddr) {
-    client
-        .post(format!("http://{}/mcp", addr))
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2025-11-25",
-                "capabilities": {},
-                "clientInfo": {"name": "test-client", "version": "1.0"}
-            }
-        }))
-        .send()
-        .await
-        .unwrap();
-}
-
 #[tokio::test]
 async fn test_mcp_list_proposals() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -216,9 +195,7 @@ async fn test_mcp_list_proposals() {
 #[tokio::test]
 async fn test_mcp_list_proposals_filtered() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -246,9 +223,7 @@ async fn test_mcp_list_proposals_filtered() {
 #[tokio::test]
 async fn test_mcp_get_proposal() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -277,9 +252,7 @@ async fn test_mcp_get_proposal() {
 #[tokio::test]
 async fn test_mcp_get_proposal_not_found() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -307,9 +280,7 @@ async fn test_mcp_get_proposal_not_found() {
 #[tokio::test]
 async fn test_mcp_tools_list_returns_nine() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -326,7 +297,7 @@ async fn test_mcp_tools_list_returns_nine() {
     let body: Value = response.json().await.unwrap();
 
     let tools = body["result"]["tools"].as_array().unwrap();
-    assert_eq!(tools.len(), 9, "Should return 9 tools");
+    assert_eq!(tools.len(), 11, "Should return 11 tools");
 
     let tool_names: Vec<&str> = tools
         .iter()
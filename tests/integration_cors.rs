@@ -39,6 +39,11 @@ async fn test_cors_preflight_allowed_origin() {
         "Should allow POST method, got: {}",
         allow_methods
     );
+    assert!(
+        allow_methods.contains("DELETE"),
+        "Should allow DELETE method for ending MCP sessions, got: {}",
+        allow_methods
+    );
 
     let allow_headers = response
         .headers()
@@ -52,6 +57,11 @@ async fn test_cors_preflight_allowed_origin() {
         "Should allow content-type header, got: {}",
         allow_headers
     );
+    assert!(
+        allow_headers.contains("mcp-session-id"),
+        "Should allow mcp-session-id header, got: {}",
+        allow_headers
+    );
 
     let max_age = response
         .headers()
@@ -120,31 +120,10 @@ async fn test_get_identity_by_pubkey_not_found() {
 
 // ===== MCP Tool Tests =====
 
-/// Helper to initialize MCP handler
-async fn initialize_mcp(client: &reqwest::Client, addr: &std::net::SocketAddr) {
-    client
-        .post(format!("http://{}/mcp", addr))
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2025-11-25",
-                "capabilities": {},
-                "clientInfo": {"name": "test-client", "version": "1.0"}
-            }
-        }))
-        .send()
-        .await
-        .unwrap();
-}
-
 #[tokio::test]
 async fn test_get_identity_mcp_tool_found() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -176,9 +155,7 @@ async fn test_get_identity_mcp_tool_found() {
 #[tokio::test]
 async fn test_get_identity_mcp_tool_not_found() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -206,9 +183,7 @@ async fn test_get_identity_mcp_tool_not_found() {
 #[tokio::test]
 async fn test_get_identity_mcp_tool_missing_params() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize_mcp(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
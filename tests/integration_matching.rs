@@ -7,23 +7,25 @@
 //! - TEST-01: Expected category matches (5 tests)
 //! - TEST-02: No-match scenarios (3 tests)
 //! - Edge cases: high threshold, comprehensive category coverage (2 tests)
+//! - Ranked matches and score explanations (2 tests)
+//! - Scoring modes: fuzzy, BM25 and hybrid measured on the same queries (3 tests)
 
 mod common;
 
 use serde_json::Value;
+use three_good_sources::matcher::{MatchConfig, MatchMode};
 
-/// Helper to initialize MCP handler
-async fn initialize(client: &reqwest::Client, addr: &std::net::SocketAddr) -> Value {
+/// Helper to call get_sources tool
+async fn get_sources(client: &reqwest::Client, addr: &std::net::SocketAddr, query: &str) -> Value {
     let response = client
         .post(format!("http://{}/mcp", addr))
         .json(&serde_json::json!({
             "jsonrpc": "2.0",
-            "id": 1,
-            "method": "initialize",
+            "id": 2,
+            "method": "tools/call",
             "params": {
-                "protocolVersion": "2025-11-25",
-                "capabilities": {},
-                "clientInfo": {"name": "test-client", "version": "1.0"}
+                "name": "get_sources",
+                "arguments": {"query": query}
             }
         }))
         .send()
@@ -33,8 +35,13 @@ async fn initialize(client: &reqwest::Client, addr: &std::net::SocketAddr) -> Va
     response.json().await.unwrap()
 }
 
-/// Helper to call get_sources tool
-async fn get_sources(client: &reqwest::Client, addr: &std::net::SocketAddr, query: &str) -> Value {
+/// Helper to call get_sources with custom threshold
+async fn get_sources_with_threshold(
+    client: &reqwest::Client,
+    addr: &std::net::SocketAddr,
+    query: &str,
+    threshold: f64,
+) -> Value {
     let response = client
         .post(format!("http://{}/mcp", addr))
         .json(&serde_json::json!({
@@ -43,7 +50,10 @@ async fn get_sources(client: &reqwest::Client, addr: &std::net::SocketAddr, quer
             "method": "tools/call",
             "params": {
                 "name": "get_sources",
-                "arguments": {"query": query}
+                "arguments": {
+                    "query": query,
+                    "threshold": threshold
+                }
             }
         }))
         .send()
@@ -53,12 +63,11 @@ async fn get_sources(client: &reqwest::Client, addr: &std::net::SocketAddr, quer
     response.json().await.unwrap()
 }
 
-/// Helper to call get_sources with custom threshold
-async fn get_sources_with_threshold(
+/// Helper to call get_sources with arbitrary extra arguments
+async fn get_sources_with_args(
     client: &reqwest::Client,
     addr: &std::net::SocketAddr,
-    query: &str,
-    threshold: f64,
+    arguments: Value,
 ) -> Value {
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -68,10 +77,7 @@ async fn get_sources_with_threshold(
             "method": "tools/call",
             "params": {
                 "name": "get_sources",
-                "arguments": {
-                    "query": query,
-                    "threshold": threshold
-                }
+                "arguments": arguments
             }
         }))
         .send()
@@ -86,9 +92,7 @@ async fn get_sources_with_threshold(
 #[tokio::test]
 async fn test_learn_rust_matches_rust_learning() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "learn rust").await;
 
     assert_eq!(response["result"]["isError"], false);
@@ -101,9 +105,7 @@ async fn test_learn_rust_matches_rust_learning() {
 #[tokio::test]
 async fn test_bitcoin_node_matches_bitcoin_node_setup() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "bitcoin node").await;
 
     assert_eq!(response["result"]["isError"], false);
@@ -115,9 +117,7 @@ async fn test_bitcoin_node_matches_bitcoin_node_setup() {
 #[tokio::test]
 async fn test_email_server_matches_self_hosted_email() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "email server").await;
 
     assert_eq!(response["result"]["isError"], false);
@@ -129,9 +129,7 @@ async fn test_email_server_matches_self_hosted_email() {
 #[tokio::test]
 async fn test_password_manager_matches_password_management() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "password manager").await;
 
     assert_eq!(response["result"]["isError"], false);
@@ -146,9 +144,7 @@ async fn test_password_manager_matches_password_management() {
 #[tokio::test]
 async fn test_sources_contain_real_urls() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "learn rust").await;
 
     assert_eq!(response["result"]["isError"], false);
@@ -173,9 +169,7 @@ async fn test_sources_contain_real_urls() {
 #[tokio::test]
 async fn test_unrelated_query_returns_no_match() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "quantum physics supercollider").await;
 
     assert_eq!(response["result"]["isError"], true);
@@ -190,9 +184,7 @@ async fn test_unrelated_query_returns_no_match() {
 #[tokio::test]
 async fn test_gibberish_query_returns_no_match() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "xyzzy plugh foobar").await;
 
     assert_eq!(response["result"]["isError"], true);
@@ -201,9 +193,7 @@ async fn test_gibberish_query_returns_no_match() {
 #[tokio::test]
 async fn test_empty_query_returns_error() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
     let response = get_sources(&client, &addr, "").await;
 
     assert_eq!(response["result"]["isError"], true);
@@ -220,9 +210,7 @@ async fn test_empty_query_returns_error() {
 #[tokio::test]
 async fn test_high_threshold_rejects_partial_match() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     // With threshold 0.99, even "learn rust" should fail to match
     let response = get_sources_with_threshold(&client, &addr, "learn rust", 0.99).await;
@@ -236,9 +224,7 @@ async fn test_high_threshold_rejects_partial_match() {
 #[tokio::test]
 async fn test_each_seed_category_is_matchable() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     // All 10 seed categories with their exact names
     let category_queries = vec![
@@ -269,3 +255,276 @@ async fn test_each_seed_category_is_matchable() {
         success_count
     );
 }
+
+// ===== Ranked matches and explanations =====
+
+#[tokio::test]
+async fn test_limit_returns_ranked_alternatives() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let response = get_sources_with_args(
+        &client,
+        &addr,
+        serde_json::json!({"query": "self hosted", "threshold": 0.1, "limit": 5}),
+    )
+    .await;
+
+    assert_eq!(response["result"]["isError"], false);
+    let structured = &response["result"]["structuredContent"];
+    let mut scores = vec![structured["score"].as_f64().unwrap()];
+    for alternative in structured["alternatives"].as_array().unwrap() {
+        scores.push(alternative["score"].as_f64().unwrap());
+    }
+
+    assert_eq!(scores.len(), 5, "Low threshold should fill the limit");
+    assert!(
+        scores.windows(2).all(|pair| pair[0] >= pair[1]),
+        "Scores should be in descending order: {:?}",
+        scores
+    );
+}
+
+#[tokio::test]
+async fn test_explain_reports_per_surface_scores() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let response = get_sources_with_args(
+        &client,
+        &addr,
+        serde_json::json!({"query": "email server", "explain": true}),
+    )
+    .await;
+
+    assert_eq!(response["result"]["isError"], false);
+    let structured = &response["result"]["structuredContent"];
+    assert_eq!(structured["slug"], "self-hosted-email");
+
+    let explanation = &structured["explanation"];
+    for key in ["slug_score", "name_score", "fuzzy_score", "keyword_score"] {
+        let value = explanation[key].as_f64().unwrap();
+        assert!((0.0..=1.0).contains(&value), "{} out of range: {}", key, value);
+    }
+    let best_pattern = explanation["pattern_scores"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["score"].as_f64().unwrap())
+        .fold(0.0, f64::max);
+    assert!(explanation["fuzzy_score"].as_f64().unwrap() >= best_pattern);
+}
+
+// ===== Scoring modes =====
+
+const ALL_MODES: [MatchMode; 3] = [MatchMode::Fuzzy, MatchMode::Bm25, MatchMode::Hybrid];
+
+/// Spawn a test server using the default weights and the given scoring mode
+async fn spawn_server_with_mode(mode: MatchMode) -> std::net::SocketAddr {
+    common::spawn_test_server_with_match_config(MatchConfig {
+        match_threshold: 0.4,
+        match_fuzzy_weight: 0.7,
+        match_keyword_weight: 0.3,
+        match_mode: mode,
+        match_bm25_weight: 0.5,
+    })
+    .await
+}
+
+/// Slug of the matched category, or None when the query did not match
+fn matched_slug(response: &Value) -> Option<String> {
+    if response["result"]["isError"] != false {
+        return None;
+    }
+    response["result"]["structuredContent"]["slug"]
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+#[tokio::test]
+async fn test_expected_matches_in_every_mode() {
+    let cases = [
+        ("learn rust", "rust-learning"),
+        ("bitcoin node", "bitcoin-node-setup"),
+        ("email server", "self-hosted-email"),
+        ("password manager", "password-management"),
+        ("Threat Modeling", "threat-modeling"),
+        ("Nostr Protocol Development", "nostr-development"),
+    ];
+
+    for mode in ALL_MODES {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        for (query, expected) in cases {
+            let response = get_sources(&client, &addr, query).await;
+            assert_eq!(
+                matched_slug(&response).as_deref(),
+                Some(expected),
+                "{:?} mode: query {:?}",
+                mode,
+                query
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_synonyms_and_inflections_match_in_every_mode() {
+    // Only match through registry.json's matching rules (stemming plus
+    // synonyms such as "mail" -> "email" and "debian" -> "linux")
+    let cases = [
+        ("mail server", "self-hosted-email"),
+        ("self-hosted mail server", "self-hosted-email"),
+        ("passwords", "password-management"),
+        ("btc node", "bitcoin-node-setup"),
+        ("hardening debian", "linux-hardening"),
+    ];
+
+    for mode in ALL_MODES {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        for (query, expected) in cases {
+            let response = get_sources(&client, &addr, query).await;
+            assert_eq!(
+                matched_slug(&response).as_deref(),
+                Some(expected),
+                "{:?} mode: query {:?}",
+                mode,
+                query
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_spanish_and_german_queries_match_in_every_mode() {
+    // Matched against each category's `localized` translations; the
+    // language is detected from the query's stop words
+    let cases = [
+        ("¿Cómo configuro un nodo de Bitcoin?", "bitcoin-node-setup", "es"),
+        ("mi propio servidor de correo", "self-hosted-email", "es"),
+        ("Wie lerne ich Rust?", "rust-learning", "de"),
+        ("Passwortmanager für die Familie", "password-management", "de"),
+    ];
+
+    for mode in ALL_MODES {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        for (query, expected, lang) in cases {
+            let response = get_sources(&client, &addr, query).await;
+            assert_eq!(
+                matched_slug(&response).as_deref(),
+                Some(expected),
+                "{:?} mode: query {:?}",
+                mode,
+                query
+            );
+            assert_eq!(response["result"]["structuredContent"]["lang"], lang);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_lang_argument_selects_translation() {
+    // No stop words to detect German from, so this needs the explicit lang
+    let query = "Passwörter sicher speichern";
+
+    for mode in ALL_MODES {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        let response = get_sources(&client, &addr, query).await;
+        assert_eq!(response["result"]["isError"], true, "{:?} mode", mode);
+
+        let response = get_sources_with_args(
+            &client,
+            &addr,
+            serde_json::json!({ "query": query, "lang": "de" }),
+        )
+        .await;
+        let structured = &response["result"]["structuredContent"];
+        assert_eq!(structured["slug"], "password-management", "{:?} mode", mode);
+        assert_eq!(structured["name"], "Passwortverwaltung");
+        assert_eq!(structured["lang"], "de");
+    }
+}
+
+#[tokio::test]
+async fn test_negative_patterns_reject_in_every_mode() {
+    // rust-learning's negative patterns in registry.json (the docs/SCHEMA.md example)
+    for mode in ALL_MODES {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        for query in ["how do I remove rust from my bike", "rust belt"] {
+            let response = get_sources(&client, &addr, query).await;
+            assert_eq!(matched_slug(&response), None, "{:?} mode: query {:?}", mode, query);
+        }
+
+        let response = get_sources(&client, &addr, "learn rust").await;
+        assert_eq!(matched_slug(&response).as_deref(), Some("rust-learning"));
+    }
+}
+
+#[tokio::test]
+async fn test_unrelated_queries_rejected_in_every_mode() {
+    for mode in ALL_MODES {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        for query in ["quantum physics supercollider", "asdfghjkl qwerty"] {
+            let response = get_sources(&client, &addr, query).await;
+            assert_eq!(
+                response["result"]["isError"], true,
+                "{:?} mode should reject {:?}",
+                mode, query
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_long_queries_measured_in_both_modes() {
+    // Natural-language questions much longer than any query pattern
+    let cases = [
+        ("what is the best way to run my own bitcoin full node at home", "bitcoin-node-setup"),
+        ("I want to host my own email server for my family", "self-hosted-email"),
+        ("recommended resources for learning the rust programming language as a beginner", "rust-learning"),
+        ("how do I build an MCP server for an AI assistant", "mcp-development"),
+        ("control my smart home devices without sending data to the cloud", "home-automation-private"),
+        ("how can I harden my linux server against attackers", "linux-hardening"),
+        ("getting started building an app on the nostr protocol", "nostr-development"),
+        ("where can I learn to build apps with pubky and pkarr keys", "pubky-development"),
+        ("which password manager should I use to keep my logins safe", "password-management"),
+    ];
+
+    let mut hits = Vec::new();
+    for mode in [MatchMode::Fuzzy, MatchMode::Bm25] {
+        let addr = spawn_server_with_mode(mode).await;
+        let (client, _) = common::mcp_session(&addr).await;
+
+        let mut correct = 0;
+        for (query, expected) in cases {
+            let response = get_sources(&client, &addr, query).await;
+            match matched_slug(&response) {
+                Some(slug) if slug == expected => correct += 1,
+                // A confident wrong answer is worse than no answer
+                Some(slug) => panic!("{:?} mode matched {:?} to {}", mode, query, slug),
+                None => {}
+            }
+        }
+        hits.push((mode, correct));
+    }
+
+    let (_, fuzzy_hits) = hits[0];
+    let (_, bm25_hits) = hits[1];
+    assert!(
+        bm25_hits > fuzzy_hits,
+        "BM25 should match more long queries than fuzzy: {:?} out of {}",
+        hits,
+        cases.len()
+    );
+}
@@ -7,40 +7,21 @@
 //! - TEST-03: MCP protocol validation (initialize, tools/list, tools/call, error handling)
 //! - JSON-RPC 2.0 compliance (parse errors, batch rejection, notifications)
 //! - Pre-initialization gating
+//! - Structured tool output (structuredContent, outputSchema)
+//! - MCP resources (list, templates, read)
+//! - MCP prompts (list, get)
+//! - Streamable HTTP sessions (Mcp-Session-Id, DELETE /mcp, protocol version header)
 //! - All 4 MCP tools: get_sources, list_categories, get_provenance, get_endorsements
 
 mod common;
 
 use serde_json::Value;
 
-/// Helper to initialize MCP handler
-async fn initialize(client: &reqwest::Client, addr: &std::net::SocketAddr) -> Value {
-    let response = client
-        .post(format!("http://{}/mcp", addr))
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "initialize",
-            "params": {
-                "protocolVersion": "2025-11-25",
-                "capabilities": {},
-                "clientInfo": {"name": "test-client", "version": "1.0"}
-            }
-        }))
-        .send()
-        .await
-        .unwrap();
-
-    assert_eq!(response.status(), 200);
-    response.json().await.unwrap()
-}
-
 #[tokio::test]
 async fn test_initialize_returns_protocol_version() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
 
-    let response = initialize(&client, &addr).await;
+    let (_, response) = common::mcp_session(&addr).await;
 
     assert_eq!(response["jsonrpc"], "2.0");
     assert_eq!(response["result"]["protocolVersion"], "2025-11-25");
@@ -52,10 +33,7 @@ async fn test_initialize_returns_protocol_version() {
 #[tokio::test]
 async fn test_tools_list_returns_six_tools() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    // Initialize first
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     // Now request tools/list
     let response = client
@@ -76,7 +54,7 @@ async fn test_tools_list_returns_six_tools() {
     assert!(body["result"]["tools"].is_array());
 
     let tools = body["result"]["tools"].as_array().unwrap();
-    assert_eq!(tools.len(), 9, "Should return exactly 9 tools");
+    assert_eq!(tools.len(), 11, "Should return exactly 11 tools");
 
     // Verify tool names
     let tool_names: Vec<&str> = tools
@@ -86,10 +64,12 @@ async fn test_tools_list_returns_six_tools() {
 
     assert!(tool_names.contains(&"get_sources"));
     assert!(tool_names.contains(&"list_categories"));
+    assert!(tool_names.contains(&"browse_categories"));
     assert!(tool_names.contains(&"get_provenance"));
     assert!(tool_names.contains(&"get_endorsements"));
     assert!(tool_names.contains(&"get_audit_log"));
     assert!(tool_names.contains(&"get_identity"));
+    assert!(tool_names.contains(&"get_peer_status"));
 
     // Verify schema structure
     for tool in tools {
@@ -103,9 +83,7 @@ async fn test_tools_list_returns_six_tools() {
 #[tokio::test]
 async fn test_tools_call_get_sources() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -140,9 +118,7 @@ async fn test_tools_call_get_sources() {
 #[tokio::test]
 async fn test_tools_call_list_categories() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -172,9 +148,7 @@ async fn test_tools_call_list_categories() {
 #[tokio::test]
 async fn test_tools_call_get_provenance() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -204,9 +178,7 @@ async fn test_tools_call_get_provenance() {
 #[tokio::test]
 async fn test_tools_call_get_endorsements() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -295,7 +267,7 @@ async fn test_pre_init_tools_list_rejected() {
         .await
         .unwrap();
 
-    assert_eq!(response.status(), 200);
+    assert_eq!(response.status(), 400, "Requests without a session are rejected");
     let body: Value = response.json().await.unwrap();
 
     assert_eq!(body["error"]["code"], -32002, "Should be Not initialized");
@@ -304,9 +276,7 @@ async fn test_pre_init_tools_list_rejected() {
 #[tokio::test]
 async fn test_unknown_method_returns_error() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
-
-    initialize(&client, &addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
 
     let response = client
         .post(format!("http://{}/mcp", addr))
@@ -328,7 +298,7 @@ async fn test_unknown_method_returns_error() {
 #[tokio::test]
 async fn test_notification_returns_204() {
     let addr = common::spawn_test_server().await;
-    let client = reqwest::Client::new();
+    let (client, _) = common::mcp_session(&addr).await;
 
     // Send notification (no id field)
     let response = client
@@ -389,6 +359,343 @@ async fn test_all_responses_have_jsonrpc_field() {
     assert_eq!(body["jsonrpc"], "2.0");
 
     // Test successful initialize
-    let body = initialize(&client, &addr).await;
+    let (_, body) = common::mcp_session(&addr).await;
     assert_eq!(body["jsonrpc"], "2.0");
 }
+
+// ===== Streamable HTTP sessions =====
+
+#[tokio::test]
+async fn test_initialize_issues_session_id_header() {
+    let addr = common::spawn_test_server().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {"protocolVersion": "2025-11-25"}
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    let session_id = response
+        .headers()
+        .get("mcp-session-id")
+        .expect("Should issue Mcp-Session-Id")
+        .to_str()
+        .unwrap();
+    assert!(!session_id.is_empty());
+    assert!(session_id.is_ascii());
+}
+
+#[tokio::test]
+async fn test_sessions_are_independent() {
+    let addr = common::spawn_test_server().await;
+    let (_, first) = common::mcp_session(&addr).await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    assert_eq!(first["result"]["protocolVersion"], "2025-11-25");
+
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_unknown_session_returns_404() {
+    let addr = common::spawn_test_server().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .header("mcp-session-id", "not-a-real-session")
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 404);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"]["code"], -32001, "Should be Session not found");
+}
+
+#[tokio::test]
+async fn test_delete_ends_session() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let response = client
+        .delete(format!("http://{}/mcp", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    // The session is gone: further requests and a second DELETE are 404
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = client
+        .delete(format!("http://{}/mcp", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_delete_without_session_returns_400() {
+    let addr = common::spawn_test_server().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .delete(format!("http://{}/mcp", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+}
+
+#[tokio::test]
+async fn test_unsupported_protocol_version_header_rejected() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .header("mcp-protocol-version", "1999-01-01")
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    // Supported, but not the version negotiated for this session
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .header("mcp-protocol-version", "2024-11-05")
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 3, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    let response = client
+        .post(format!("http://{}/mcp", addr))
+        .header("mcp-protocol-version", "2025-11-25")
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 4, "method": "tools/list"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+// ===== MCP resources =====
+
+/// Helper to send a resources/read request and return the JSON-RPC response
+async fn read_resource(client: &reqwest::Client, addr: &std::net::SocketAddr, uri: &str) -> Value {
+    client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "resources/read",
+            "params": {"uri": uri}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_resources_list_covers_all_data() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let body: Value = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "resources/list"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let uris: Vec<&str> = body["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["uri"].as_str().unwrap())
+        .collect();
+
+    // 10 categories + audit log + 1 identity + 1 proposal
+    assert_eq!(uris.len(), 13);
+    assert!(uris.contains(&"3gs://audit"));
+    assert!(uris.contains(&"3gs://category/bitcoin-node-setup"));
+    assert!(uris.contains(
+        &"3gs://identity/197f6b23e16c8532c6abc838facd5ea789be0c76b2920334039bfa8b3d368d61"
+    ));
+    assert!(uris.contains(&"3gs://proposal/a1b2c3d4-e5f6-4a7b-8c9d-0e1f2a3b4c5d"));
+}
+
+#[tokio::test]
+async fn test_resources_read_audit_identity_and_proposal() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let body = read_resource(&client, &addr, "3gs://audit").await;
+    let text = body["result"]["contents"][0]["text"].as_str().unwrap();
+    let entries: Vec<Value> = serde_json::from_str(text).unwrap();
+    assert_eq!(entries.len(), 40);
+
+    let body = read_resource(
+        &client,
+        &addr,
+        "3gs://identity/197f6b23e16c8532c6abc838facd5ea789be0c76b2920334039bfa8b3d368d61",
+    )
+    .await;
+    let text = body["result"]["contents"][0]["text"].as_str().unwrap();
+    let identity: Value = serde_json::from_str(text).unwrap();
+    assert!(identity["name"].is_string());
+    assert!(identity["platforms"].is_array());
+
+    let body = read_resource(&client, &addr, "3gs://proposal/a1b2c3d4-e5f6-4a7b-8c9d-0e1f2a3b4c5d").await;
+    let text = body["result"]["contents"][0]["text"].as_str().unwrap();
+    let proposal: Value = serde_json::from_str(text).unwrap();
+    assert_eq!(proposal["id"], "a1b2c3d4-e5f6-4a7b-8c9d-0e1f2a3b4c5d");
+    assert!(proposal["category"].is_string());
+    assert!(proposal["votes"].is_array());
+}
+
+#[tokio::test]
+async fn test_resources_read_unknown_uri() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let body = read_resource(&client, &addr, "3gs://category/does-not-exist").await;
+    assert_eq!(body["error"]["code"], -32002, "Should be Resource not found");
+    assert_eq!(body["error"]["data"]["uri"], "3gs://category/does-not-exist");
+}
+
+// ===== MCP prompts =====
+
+#[tokio::test]
+async fn test_prompts_get_research_topic() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let body: Value = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "prompts/get",
+            "params": {
+                "name": "research_topic",
+                "arguments": {"topic": "set up a bitcoin node"}
+            }
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert!(body["result"]["description"].is_string());
+    let text = body["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+    assert!(text.contains("set up a bitcoin node"));
+    assert!(text.matches("Why: ").count() >= 3, "Should embed each source's rationale");
+}
+
+// ===== Structured tool output =====
+
+/// Helper to call a tool and return the JSON-RPC result
+async fn call_tool(
+    client: &reqwest::Client,
+    addr: &std::net::SocketAddr,
+    name: &str,
+    arguments: Value,
+) -> Value {
+    let body: Value = client
+        .post(format!("http://{}/mcp", addr))
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": name, "arguments": arguments}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    body["result"].clone()
+}
+
+#[tokio::test]
+async fn test_structured_content_for_all_tools() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let cases = [
+        ("get_sources", serde_json::json!({"query": "learn rust"})),
+        ("get_federated_sources", serde_json::json!({"query": "learn rust"})),
+        ("list_categories", serde_json::json!({})),
+        ("browse_categories", serde_json::json!({"slug": "linux-hardening"})),
+        ("get_provenance", serde_json::json!({})),
+        ("get_endorsements", serde_json::json!({})),
+        ("get_audit_log", serde_json::json!({})),
+        (
+            "get_identity",
+            serde_json::json!({"pubkey": "197f6b23e16c8532c6abc838facd5ea789be0c76b2920334039bfa8b3d368d61"}),
+        ),
+        ("list_proposals", serde_json::json!({})),
+        ("get_proposal", serde_json::json!({"id": "a1b2c3d4-e5f6-4a7b-8c9d-0e1f2a3b4c5d"})),
+    ];
+
+    for (name, arguments) in cases {
+        let result = call_tool(&client, &addr, name, arguments).await;
+        assert_eq!(result["isError"], false, "{} should succeed", name);
+        assert!(result["content"][0]["text"].is_string(), "{} should keep text content", name);
+        assert!(result["structuredContent"].is_object(), "{} should return structuredContent", name);
+    }
+}
+
+#[tokio::test]
+async fn test_structured_audit_log_and_federated_sources() {
+    let addr = common::spawn_test_server().await;
+    let (client, _) = common::mcp_session(&addr).await;
+
+    let result = call_tool(&client, &addr, "get_audit_log", serde_json::json!({})).await;
+    let entries = result["structuredContent"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 40);
+    assert!(entries[0]["previous_hash"].is_null(), "First entry starts the chain");
+    assert!(entries[1]["previous_hash"].is_string());
+
+    let result = call_tool(&client, &addr, "get_federated_sources", serde_json::json!({"query": "learn rust"})).await;
+    let results = result["structuredContent"]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1, "Only the local registry (no endorsed peers)");
+    assert_eq!(results[0]["trust"], "direct");
+    assert_eq!(results[0]["slug"], "rust-learning");
+    assert_eq!(results[0]["sources"].as_array().unwrap().len(), 3);
+}
@@ -1,6 +1,7 @@
 mod common;
 
 use serde_json::Value;
+use three_good_sources::registry::verify_registry;
 
 #[tokio::test]
 async fn test_registry_endpoint_returns_200() {
@@ -120,3 +121,110 @@ async fn test_health_endpoint_returns_status_ok() {
     let pubkey = body["pubkey"].as_str().unwrap();
     assert!(pubkey.len() > 40, "Pubkey too short: {}", pubkey);
 }
+
+#[tokio::test]
+async fn test_registry_signature_verifies_against_node_pubkey() {
+    let addr = common::spawn_test_server().await;
+    let response = reqwest::get(format!("http://{}/registry", addr))
+        .await
+        .unwrap();
+    let signature = response.headers()["x-registry-signature"].to_str().unwrap().to_string();
+    let pubkey = response.headers()["x-registry-pubkey"].to_str().unwrap().to_string();
+    let body = response.bytes().await.unwrap();
+
+    let health: Value = reqwest::get(format!("http://{}/health", addr))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pubkey, health["pubkey"].as_str().unwrap());
+
+    let registry = verify_registry(&body, &signature, &pubkey)
+        .expect("Served registry should verify against the node pubkey");
+    assert_eq!(registry["categories"].as_object().unwrap().len(), 10);
+
+    let mut tampered: Value = serde_json::from_slice(&body).unwrap();
+    tampered["version"] = Value::from("9.9.9");
+    let tampered = serde_json::to_vec(&tampered).unwrap();
+    assert!(verify_registry(&tampered, &signature, &pubkey).is_err());
+}
+
+#[tokio::test]
+async fn test_registry_has_etag_and_last_modified() {
+    let addr = common::spawn_test_server().await;
+    let response = reqwest::get(format!("http://{}/registry", addr))
+        .await
+        .unwrap();
+    let etag = response.headers()["etag"].to_str().unwrap();
+    assert!(etag.starts_with('"') && etag.ends_with('"'));
+    assert_eq!(
+        response.headers()["last-modified"].to_str().unwrap(),
+        "Sun, 01 Feb 2026 00:00:00 GMT"
+    );
+}
+
+#[tokio::test]
+async fn test_registry_conditional_get() {
+    let addr = common::spawn_test_server().await;
+    let url = format!("http://{}/registry", addr);
+    let client = reqwest::Client::new();
+    let etag = client.get(&url).send().await.unwrap().headers()["etag"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    for if_none_match in [
+        etag.clone(),
+        format!("W/{}", etag),
+        format!("\"other\", {}", etag),
+        "*".to_string(),
+    ] {
+        let response = client
+            .get(&url)
+            .header("if-none-match", &if_none_match)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 304, "If-None-Match: {}", if_none_match);
+        assert_eq!(response.headers()["etag"].to_str().unwrap(), etag);
+        assert!(response.bytes().await.unwrap().is_empty());
+    }
+
+    let response = client
+        .get(&url)
+        .header("if-none-match", "\"other\"")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.headers().contains_key("x-registry-signature"));
+}
+
+#[tokio::test]
+async fn test_health_reports_federation_counts() {
+    let addr = common::spawn_test_server().await;
+    let body: Value = reqwest::get(format!("http://{}/health", addr))
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body["federation"],
+        serde_json::json!({"peers": 0, "fresh": 0, "stale": 0, "unreachable": 0, "untrusted": 0})
+    );
+}
+
+#[tokio::test]
+async fn test_peers_endpoint_returns_summary_and_peers() {
+    let addr = common::spawn_test_server().await;
+    let response = reqwest::get(format!("http://{}/peers", addr))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["summary"]["peers"], 0);
+    assert_eq!(body["peers"], serde_json::json!([]));
+}